@@ -60,6 +60,11 @@ impl OsError {
     pub(crate) fn new(line: u32, file: &'static str, error: platform_impl::OsError) -> OsError {
         OsError { line, file, error }
     }
+
+    #[allow(dead_code)]
+    pub(crate) fn platform_error(&self) -> &platform_impl::OsError {
+        &self.error
+    }
 }
 
 #[allow(unused_macros)]
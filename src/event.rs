@@ -253,6 +253,43 @@ pub enum Event<T: 'static> {
     ///
     /// - **macOS / Wayland / Windows / Orbital:** Unsupported.
     MemoryWarning,
+
+    /// Emitted when the user became idle, i.e. didn't use any input device for the requested
+    /// time, e.g. to pause the animations.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Requested with `EventLoopWindowTargetExtWayland::set_idle_notification`,
+    ///   and sent once the user is idle on all the seats.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows / X11:** Unsupported.
+    UserIdle,
+
+    /// Emitted when the user is active again after [`UserIdle`].
+    ///
+    /// [`UserIdle`]: Self::UserIdle
+    UserActive,
+
+    /// Emitted when the clipboard set by the application was replaced by another client, e.g. to
+    /// stop highlighting the copied content.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Sent when the `wl_data_source` of the selection set with
+    ///   `EventLoopWindowTargetExtWayland::write_clipboard` is cancelled.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows / X11:** Unsupported.
+    ClipboardLost,
+
+    /// Emitted when the toplevel windows of the other applications changed, i.e. one was
+    /// opened, closed, or changed its title, app ID or state, e.g. to update a taskbar.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Sent with `ext_foreign_toplevel_list_v1` and
+    ///   `zwlr_foreign_toplevel_manager_v1` when enabled with
+    ///   `EventLoopBuilderExtWayland::with_foreign_toplevels`, the toplevels are listed with
+    ///   `EventLoopWindowTargetExtWayland::{foreign_toplevels, managed_toplevels}`.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows / X11:** Unsupported.
+    ForeignToplevelsChanged,
 }
 
 impl<T> Event<T> {
@@ -269,6 +306,10 @@ impl<T> Event<T> {
             Suspended => Ok(Suspended),
             Resumed => Ok(Resumed),
             MemoryWarning => Ok(MemoryWarning),
+            UserIdle => Ok(UserIdle),
+            UserActive => Ok(UserActive),
+            ClipboardLost => Ok(ClipboardLost),
+            ForeignToplevelsChanged => Ok(ForeignToplevelsChanged),
         }
     }
 }
@@ -331,8 +372,25 @@ pub enum WindowEvent {
     Moved(PhysicalPosition<i32>),
 
     /// The window has been requested to close.
+    ///
+    /// The request can be vetoed by not dropping the window, e.g. to ask the user to save their
+    /// changes first.
     CloseRequested,
 
+    /// The window's surface has been destroyed by the system.
+    ///
+    /// Unlike [`CloseRequested`], this can't be vetoed: the window can't be shown again and
+    /// should be dropped, a new one has to be created instead.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Sent after [`CloseRequested`] when the compositor closes a layer shell
+    ///   window, e.g. because its output went away, or dismisses a popup.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows / X11:** Unsupported.
+    ///
+    /// [`CloseRequested`]: Self::CloseRequested
+    SurfaceDestroyed,
+
     /// The window has been destroyed.
     Destroyed,
 
@@ -340,20 +398,78 @@ pub enum WindowEvent {
     ///
     /// When the user drops multiple files at once, this event will be emitted for each file
     /// separately.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Unsupported.
     DroppedFile(PathBuf),
 
     /// A file is being hovered over the window.
     ///
     /// When the user hovers multiple files at once, this event will be emitted for each file
     /// separately.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Unsupported.
     HoveredFile(PathBuf),
 
     /// A file was hovered, but has exited the window.
     ///
     /// There will be a single `HoveredFileCancelled` event triggered even if multiple files were
     /// hovered.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Unsupported.
     HoveredFileCancelled,
 
+    /// A drag and drop from another application entered the window, offering its data as the
+    /// `mime_types`, with the actions the source allows.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The drop is only accepted with `WindowExtWayland::set_drag_accepting`, and
+    ///   the preferred action is set with `WindowExtWayland::set_preferred_drop_action`.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows / X11:** Unsupported.
+    DragEntered {
+        position: PhysicalPosition<f64>,
+        mime_types: Vec<String>,
+        source_actions: DndAction,
+    },
+
+    /// The drag and drop moved over the window, or the source changed the actions it allows.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / macOS / Orbital / Web / Windows / X11:** Unsupported.
+    DragMoved {
+        position: PhysicalPosition<f64>,
+        source_actions: DndAction,
+    },
+
+    /// The drag and drop left the window without being dropped on it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / macOS / Orbital / Web / Windows / X11:** Unsupported.
+    DragLeft,
+
+    /// The drag and drop was dropped on the window, with the `action` negotiated between the
+    /// source and the window.
+    ///
+    /// The `data` is sent as the `mime_type` the drop was accepted as, and is `None` when it
+    /// wasn't accepted or failed to be received.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / macOS / Orbital / Web / Windows / X11:** Unsupported.
+    DragDropped {
+        action: DndAction,
+        mime_type: Option<String>,
+        data: Option<Vec<u8>>,
+    },
+
     /// The window gained or lost focus.
     ///
     /// The parameter is true if the window has gained focus, and false if it has lost focus.
@@ -384,6 +500,23 @@ pub enum WindowEvent {
     /// The keyboard modifiers have changed.
     ModifiersChanged(Modifiers),
 
+    // Allowing `broken_intra_doc_links` for `KeymapChanged`, because
+    // `EventLoopWindowTargetExtWayland` is only available on Wayland
+    #[cfg_attr(not(wayland_platform), allow(rustdoc::broken_intra_doc_links))]
+    /// The keymap of the keyboard has changed, e.g. because the user switched the keyboard
+    /// layout.
+    ///
+    /// This is sent to the focused window, or to the next window getting the keyboard focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Sent when the compositor sends a different `wl_keyboard` keymap. The new
+    ///   keymap can be read with [`EventLoopWindowTargetExtWayland::xkb_keymap`].
+    /// - **iOS / Android / Web / Windows / macOS / X11 / Orbital:** Unsupported.
+    ///
+    /// [`EventLoopWindowTargetExtWayland::xkb_keymap`]: crate::platform::wayland::EventLoopWindowTargetExtWayland::xkb_keymap
+    KeymapChanged,
+
     /// An event from an input method.
     ///
     /// **Note:** You have to explicitly enable this event using [`Window::set_ime_allowed`].
@@ -451,8 +584,9 @@ pub enum WindowEvent {
     ///
     /// ## Platform-specific
     ///
-    /// - Only available on **macOS** and **iOS**.
+    /// - Only available on **macOS**, **iOS** and **Wayland**.
     /// - On iOS, not recognized by default. It must be enabled when needed.
+    /// - On Wayland, requires the `zwp_pointer_gestures_v1` protocol.
     PinchGesture {
         device_id: DeviceId,
         /// Positive values indicate magnification (zooming in) and  negative
@@ -490,14 +624,60 @@ pub enum WindowEvent {
     ///
     /// ## Platform-specific
     ///
-    /// - Only available on **macOS** and **iOS**.
+    /// - Only available on **macOS**, **iOS** and **Wayland**.
     /// - On iOS, not recognized by default. It must be enabled when needed.
+    /// - On Wayland, requires the `zwp_pointer_gestures_v1` protocol, and is delivered together
+    ///   with the [`PinchGesture`](Self::PinchGesture), since they're the same gesture.
     RotationGesture {
         device_id: DeviceId,
         delta: f32,
         phase: TouchPhase,
     },
 
+    /// Multi-finger swipe gesture on a touchpad, often used for navigation.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **Wayland**, with the `zwp_pointer_gestures_v1` protocol.
+    SwipeGesture {
+        device_id: DeviceId,
+        /// The number of fingers, which doesn't change during the gesture.
+        fingers: u32,
+        /// The motion of the fingers since the previous event.
+        delta: PhysicalPosition<f64>,
+        phase: TouchPhase,
+    },
+
+    /// Multi-finger touch resting on a touchpad, e.g. to stop the kinetic scrolling or to ignore
+    /// a palm, which ends with [`TouchPhase::Ended`] when the fingers are lifted, or with
+    /// [`TouchPhase::Cancelled`] when they start another gesture or move.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **Wayland**, with the `zwp_pointer_gestures_v1` protocol version 3.
+    TouchpadHold {
+        device_id: DeviceId,
+        /// The number of fingers.
+        fingers: u32,
+        phase: TouchPhase,
+    },
+
+    /// An event from the pad of a graphics tablet, i.e. its auxiliary buttons, rings and strips.
+    ///
+    /// The pad events are sent to the window the pad is focused on, which is usually the window
+    /// with the keyboard focus. Devices without a pad don't send them.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **Wayland**, with the `zwp_tablet_manager_v2` protocol, when enabled
+    ///   with [`EventLoopBuilderExtWayland::with_tablets`].
+    ///
+    /// [`EventLoopBuilderExtWayland::with_tablets`]: crate::platform::wayland::EventLoopBuilderExtWayland::with_tablets
+    TabletPad {
+        device_id: DeviceId,
+        event: TabletPadEvent,
+    },
+
     /// Touchpad pressure event.
     ///
     /// At the moment, only supported on Apple forcetouch-capable macbooks.
@@ -558,6 +738,23 @@ pub enum WindowEvent {
     /// - **iOS / Android / X11 / Wayland / Orbital:** Unsupported.
     ThemeChanged(Theme),
 
+    /// The window has been maximized, unmaximized, made fullscreen or left fullscreen, e.g. by a
+    /// compositor keybinding or by snapping the window.
+    ///
+    /// The new state is also returned by [`Window::is_maximized`] and [`Window::fullscreen`]
+    /// afterwards, e.g. to update the maximize button of client side decorations.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Only sent for `xdg_toplevel` windows, when the state changes after the
+    ///   initial configure. The tiled edges aren't reported, they're returned by
+    ///   `WindowExtWayland::toplevel_state`.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows / X11:** Unsupported.
+    ///
+    /// [`Window::is_maximized`]: crate::window::Window::is_maximized
+    /// [`Window::fullscreen`]: crate::window::Window::fullscreen
+    WindowStateChanged { maximized: bool, fullscreen: bool },
+
     /// The window has been occluded (completely hidden from view).
     ///
     /// This is different to window visibility as it depends on whether the window is closed,
@@ -579,7 +776,10 @@ pub enum WindowEvent {
     /// ### Others
     ///
     /// - **Web:** Doesn't take into account CSS [`border`], [`padding`], or [`transform`].
-    /// - **Android / Wayland / Windows / Orbital:** Unsupported.
+    /// - **Wayland:** xdg-shell doesn't report minimization, so this follows the `suspended`
+    ///   state of `xdg_toplevel` v6 instead, which the compositor sets when the window is
+    ///   minimized or otherwise not visible. Unsupported without it, and for layer shell windows.
+    /// - **Android / Windows / Orbital:** Unsupported.
     ///
     /// [`border`]: https://developer.mozilla.org/en-US/docs/Web/CSS/border
     /// [`padding`]: https://developer.mozilla.org/en-US/docs/Web/CSS/padding
@@ -638,6 +838,12 @@ pub enum DeviceEvent {
     /// Change in physical position of a pointing device.
     ///
     /// This represents raw, unfiltered physical motion. Not to be confused with [`WindowEvent::CursorMoved`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The `dx_unaccel` and `dy_unaccel` of `zwp_relative_pointer_v1`, i.e. the
+    ///   motion without the pointer acceleration, sent while the pointer is over any surface of
+    ///   the application. Unsupported when the compositor doesn't support the relative pointer.
     MouseMotion {
         /// (x, y) change in position in unspecified units.
         ///
@@ -944,6 +1150,14 @@ pub enum TouchPhase {
     Moved,
     Ended,
     Cancelled,
+    /// Marks the end of a group of touch events which happened at the same time, e.g. two
+    /// fingers moving together during a pinch. The other fields of such [`Touch`] are
+    /// meaningless.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on **Wayland**, after the touch events of each `wl_touch::frame`.
+    Frame,
 }
 
 /// Represents a touch event
@@ -963,6 +1177,9 @@ pub enum TouchPhase {
 /// touch, such as when the window loses focus, or on iOS if the user moves the
 /// device against their face.
 ///
+/// On Wayland, the events of the touch points which changed at the same time are followed by a
+/// [`TouchPhase::Frame`] event.
+///
 /// ## Platform-specific
 ///
 /// - **Web:** Doesn't take into account CSS [`border`], [`padding`], or [`transform`].
@@ -1112,6 +1329,47 @@ pub enum MouseScrollDelta {
     PixelDelta(PhysicalPosition<f64>),
 }
 
+bitflags::bitflags! {
+    /// The actions of a drag and drop, see [`WindowEvent::DragEntered`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct DndAction: u32 {
+        /// Copy the data.
+        const COPY = 1 << 0;
+        /// Move the data, which the source deletes once it's dropped.
+        const MOVE = 1 << 1;
+        /// Ask the user for the action once it's dropped.
+        const ASK = 1 << 2;
+    }
+}
+
+/// Describes an event from the pad of a graphics tablet, see [`WindowEvent::TabletPad`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TabletPadEvent {
+    /// A button of the pad was pressed or released.
+    Button {
+        /// The index of the button, from `0`.
+        button: u32,
+        state: ElementState,
+    },
+
+    /// A ring of the pad was rotated.
+    Ring {
+        /// The index of the ring, from `0`.
+        ring: u32,
+        /// The rotation since the previous event in degrees, positive values being clockwise.
+        delta: f64,
+    },
+
+    /// A finger moved along a strip of the pad.
+    Strip {
+        /// The index of the strip, from `0`.
+        strip: u32,
+        /// The position of the finger, from `0.0` at the top or left of the strip to `1.0`.
+        position: f64,
+    },
+}
+
 /// Handle to synchronously change the size of the window from the
 /// [`WindowEvent`].
 #[derive(Debug, Clone)]
@@ -1169,6 +1427,10 @@ mod tests {
                 x(LoopExiting);
                 x(Suspended);
                 x(Resumed);
+                x(UserIdle);
+                x(UserActive);
+                x(ClipboardLost);
+                x(ForeignToplevelsChanged);
 
                 // Window events.
                 let with_window_event = |wev| {
@@ -1179,6 +1441,7 @@ mod tests {
                 };
 
                 with_window_event(CloseRequested);
+                with_window_event(SurfaceDestroyed);
                 with_window_event(Destroyed);
                 with_window_event(Focused(true));
                 with_window_event(Moved((0, 0).into()));
@@ -1186,12 +1449,28 @@ mod tests {
                 with_window_event(DroppedFile("x.txt".into()));
                 with_window_event(HoveredFile("x.txt".into()));
                 with_window_event(HoveredFileCancelled);
+                with_window_event(DragEntered {
+                    position: (0, 0).into(),
+                    mime_types: vec!["text/uri-list".into()],
+                    source_actions: event::DndAction::COPY | event::DndAction::MOVE,
+                });
+                with_window_event(DragMoved {
+                    position: (0, 0).into(),
+                    source_actions: event::DndAction::COPY,
+                });
+                with_window_event(DragLeft);
+                with_window_event(DragDropped {
+                    action: event::DndAction::COPY,
+                    mime_type: Some("text/uri-list".into()),
+                    data: Some(b"file:///x.txt".to_vec()),
+                });
                 with_window_event(Ime(Enabled));
                 with_window_event(CursorMoved {
                     device_id: did,
                     position: (0, 0).into(),
                 });
                 with_window_event(ModifiersChanged(event::Modifiers::default()));
+                with_window_event(KeymapChanged);
                 with_window_event(CursorEntered { device_id: did });
                 with_window_event(CursorLeft { device_id: did });
                 with_window_event(MouseWheel {
@@ -1215,6 +1494,24 @@ mod tests {
                     delta: 0.0,
                     phase: event::TouchPhase::Started,
                 });
+                with_window_event(SwipeGesture {
+                    device_id: did,
+                    fingers: 3,
+                    delta: (0.0, 0.0).into(),
+                    phase: event::TouchPhase::Started,
+                });
+                with_window_event(TouchpadHold {
+                    device_id: did,
+                    fingers: 2,
+                    phase: event::TouchPhase::Started,
+                });
+                with_window_event(TabletPad {
+                    device_id: did,
+                    event: event::TabletPadEvent::Ring {
+                        ring: 0,
+                        delta: 0.0,
+                    },
+                });
                 with_window_event(TouchpadPressure {
                     device_id: did,
                     pressure: 0.0,
@@ -1233,6 +1530,10 @@ mod tests {
                     force: Some(event::Force::Normalized(0.0)),
                 }));
                 with_window_event(ThemeChanged(crate::window::Theme::Light));
+                with_window_event(WindowStateChanged {
+                    maximized: true,
+                    fullscreen: false,
+                });
                 with_window_event(Occluded(true));
             }
 
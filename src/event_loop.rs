@@ -283,6 +283,11 @@ impl<T> AsFd for EventLoop<T> {
     /// into other event loop, like [`calloop`] or [`mio`]. When doing so, the
     /// loop must be polled with the [`pump_events`] API.
     ///
+    /// The `fd` stays the same for the lifetime of the loop and becomes readable not only for the
+    /// display server connection, but also for the internal timers and wakeups, like
+    /// [`EventLoopProxy::send_event`], thus it should be registered instead of the connection
+    /// `fd`. Once it's readable, dispatch the events with a `timeout` of `Some(Duration::ZERO)`.
+    ///
     /// [`calloop`]: https://crates.io/crates/calloop
     /// [`mio`]: https://crates.io/crates/mio
     /// [`pump_events`]: crate::platform::pump_events::EventLoopExtPumpEvents::pump_events
@@ -297,6 +302,8 @@ impl<T> AsRawFd for EventLoop<T> {
     /// into other event loop, like [`calloop`] or [`mio`]. When doing so, the
     /// loop must be polled with the [`pump_events`] API.
     ///
+    /// See the [`AsFd`] implementation for when the `fd` becomes readable.
+    ///
     /// [`calloop`]: https://crates.io/crates/calloop
     /// [`mio`]: https://crates.io/crates/mio
     /// [`pump_events`]: crate::platform::pump_events::EventLoopExtPumpEvents::pump_events
@@ -451,7 +458,7 @@ unsafe impl rwh_05::HasRawDisplayHandle for OwnedDisplayHandle {
 
 /// Used to send custom events to [`EventLoop`].
 pub struct EventLoopProxy<T: 'static> {
-    event_loop_proxy: platform_impl::EventLoopProxy<T>,
+    pub(crate) event_loop_proxy: platform_impl::EventLoopProxy<T>,
 }
 
 impl<T: 'static> Clone for EventLoopProxy<T> {
@@ -112,6 +112,11 @@ impl MonitorHandle {
     /// Returns a human-readable name of the monitor.
     ///
     /// Returns `None` if the monitor doesn't exist anymore.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The connector name advertised by the compositor, e.g. `DP-1`. Use
+    ///   `MonitorHandleExtWayland::description` for a more descriptive name.
     #[inline]
     pub fn name(&self) -> Option<String> {
         self.inner.name()
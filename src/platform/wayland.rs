@@ -1,16 +1,262 @@
+use std::ffi::c_void;
+use std::os::fd::OwnedFd;
+use std::ptr::NonNull;
+use std::time::Duration;
+
 use crate::{
-    event_loop::{EventLoopBuilder, EventLoopWindowTarget},
+    dpi::{LogicalPosition, LogicalSize, PhysicalSize, Size},
+    error::{ExternalError, NotSupportedError, OsError},
+    event::{DeviceId, WindowEvent},
+    event_loop::{EventLoopBuilder, EventLoopProxy, EventLoopWindowTarget},
+    keyboard::ModifiersState,
     monitor::MonitorHandle,
-    window::{Window, WindowBuilder},
+    window::{CursorIcon, Window, WindowBuilder, WindowId},
+};
+
+use crate::platform_impl::wayland::{error, warn};
+pub use sctk::reexports::protocols::xdg::shell::client::xdg_positioner::{
+    Anchor as PopupAnchor, ConstraintAdjustment as PopupConstraintAdjustment,
+    Gravity as PopupGravity,
 };
+use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::XdgToplevel;
+use sctk::shell::wlr_layer::LayerSurface;
 pub use sctk::shell::wlr_layer::{Anchor, KeyboardInteractivity, Layer};
 
+/// The window management actions supported by the compositor, see
+/// [`WindowExtWayland::wm_capabilities`].
+pub use sctk::reexports::csd_frame::WindowManagerCapabilities as WmCapabilities;
+
+/// The state of an `xdg_toplevel` window, like maximized or tiled, see
+/// [`WindowExtWayland::toplevel_state`].
+pub use sctk::reexports::csd_frame::WindowState as ToplevelState;
+
+/// The pixel format of a [`CapturedFrame`], as in `wl_shm::format`.
+pub use sctk::reexports::client::protocol::wl_shm::Format as ShmFormat;
+
+pub use crate::event::DndAction;
 pub use crate::window::Theme;
 
 /// Additional methods on [`EventLoopWindowTarget`] that are specific to Wayland.
 pub trait EventLoopWindowTargetExtWayland {
     /// True if the [`EventLoopWindowTarget`] uses Wayland.
     fn is_wayland(&self) -> bool;
+
+    /// The timestamp in milliseconds of the input event which caused the event currently being
+    /// delivered, as reported by the compositor.
+    ///
+    /// The timestamps have an undefined base, so they're only useful to compare with each other.
+    /// Currently only available for [`WindowEvent::MouseInput`].
+    fn event_timestamp(&self) -> Option<u32>;
+
+    /// Returns the keymap sent by the compositor in the `XKB_KEYMAP_FORMAT_TEXT_V1` format.
+    ///
+    /// This allows building your own xkb state for custom key handling. When there're multiple
+    /// seats, the keymap of the first seat with a keyboard is returned.
+    ///
+    /// Returns `None` if there's no keyboard or the compositor didn't send an xkb keymap yet.
+    fn xkb_keymap(&self) -> Option<Vec<u8>>;
+
+    /// Returns a duplicate of the file descriptor of the keymap sent by the compositor, with the
+    /// size of the keymap, to `mmap` it without copying.
+    ///
+    /// This is the same keymap as [`xkb_keymap`], though the size may include trailing nul bytes.
+    /// The caller owns the returned file descriptor. The file must be mapped with `MAP_PRIVATE`,
+    /// since the compositor may share it with other clients.
+    ///
+    /// Returns `None` if there's no keyboard or the compositor didn't send an xkb keymap yet.
+    ///
+    /// [`xkb_keymap`]: Self::xkb_keymap
+    fn keyboard_keymap_fd(&self) -> Option<(OwnedFd, u32)>;
+
+    /// Returns the current state of the keyboard modifiers, as last reported by
+    /// [`WindowEvent::ModifiersChanged`].
+    ///
+    /// When there're multiple seats, the modifiers of the first seat with the keyboard focus on
+    /// one of the windows are returned. Returns no modifiers when none of the windows has the
+    /// keyboard focus, since the compositor doesn't report the modifiers then.
+    fn modifiers(&self) -> ModifiersState;
+
+    /// Whether any of the windows has the keyboard focus of any seat, i.e. whether the
+    /// application is the focused one.
+    ///
+    /// This follows the [`WindowEvent::Focused`] events of all the windows, so it's updated
+    /// before they're delivered. The surfaces created with [`create_surface`] aren't considered.
+    ///
+    /// [`WindowEvent::Focused`]: crate::event::WindowEvent::Focused
+    /// [`create_surface`]: Self::create_surface
+    fn any_window_focused(&self) -> bool;
+
+    /// Returns the text of the clipboard, i.e. the `wl_data_device` selection, or `None` when
+    /// it has no text or the compositor doesn't support the clipboard.
+    ///
+    /// The text is read from the client owning the selection, blocking until it's sent or for up
+    /// to a second, after which this gives up and returns `None`, so this should be used on a
+    /// user request, like pasting. The seat with the keyboard focus on one of the windows is
+    /// preferred, since compositors only send the selection to the focused client.
+    fn read_clipboard(&self) -> Option<String>;
+
+    /// Set the text of the clipboard, i.e. the `wl_data_device` selection.
+    ///
+    /// Compositors only accept the selection from the client with the keyboard focus, so this
+    /// does nothing when none of the windows has it. The text is offered as
+    /// `text/plain;charset=utf-8`, `UTF8_STRING` and `text/plain` until another client sets the
+    /// selection, which sends [`Event::ClipboardLost`].
+    ///
+    /// [`Event::ClipboardLost`]: crate::event::Event::ClipboardLost
+    fn write_clipboard(&self, text: String);
+
+    /// Returns the mime types the clipboard is offered as, e.g. `text/html` or `image/png`, to
+    /// pick the one to read with [`read_clipboard_mime`].
+    ///
+    /// [`read_clipboard_mime`]: Self::read_clipboard_mime
+    fn clipboard_mime_types(&self) -> Vec<String>;
+
+    /// Returns the data of the clipboard as the mime type, or `None` when it isn't offered as
+    /// that mime type.
+    ///
+    /// Like [`read_clipboard`], this blocks until the client owning the selection sent it.
+    ///
+    /// [`read_clipboard`]: Self::read_clipboard
+    fn read_clipboard_mime(&self, mime: &str) -> Option<Vec<u8>>;
+
+    /// Read the data of the clipboard as the mime type without blocking, calling `callback` with
+    /// it on a later iteration of the event loop, or with `None` when it isn't offered as that
+    /// mime type.
+    ///
+    /// The data is read on the event loop as the client owning the selection sends it, so a slow
+    /// client doesn't stall the application, unlike with [`read_clipboard_mime`].
+    ///
+    /// [`read_clipboard_mime`]: Self::read_clipboard_mime
+    fn read_clipboard_async(&self, mime: &str, callback: impl FnOnce(Option<Vec<u8>>) + 'static);
+
+    /// Set the clipboard to the data for each mime type, so the client pasting it can pick the
+    /// representation it supports, e.g. `text/html` along with `text/plain;charset=utf-8`.
+    ///
+    /// Like [`write_clipboard`], this does nothing when none of the windows has the keyboard
+    /// focus.
+    ///
+    /// [`write_clipboard`]: Self::write_clipboard
+    fn write_clipboard_mime(&self, offers: Vec<(String, Vec<u8>)>);
+
+    /// Returns the seats, i.e. the groups of input devices used by a single user each.
+    fn seats(&self) -> Vec<SeatHandle>;
+
+    /// Returns the version winit bound the global with the given `interface` name at, e.g.
+    /// `"wp_viewporter"`.
+    ///
+    /// Returns `None` when the compositor doesn't advertise the global or winit doesn't use it.
+    fn protocol_version(&self, interface: &str) -> Option<u32>;
+
+    /// Returns the version winit bound `zwlr_layer_shell_v1` at, to gate the features of the
+    /// newer versions on, see [`protocol_version`].
+    ///
+    /// Winit binds at most version 4, since it doesn't use the requests added later. Returns
+    /// `None` when the compositor doesn't support the layer shell.
+    ///
+    /// [`protocol_version`]: Self::protocol_version
+    fn layer_shell_version(&self) -> Option<u32>;
+
+    /// Returns the interface name and version of every global advertised by the compositor,
+    /// e.g. for diagnostics.
+    ///
+    /// The versions are the ones advertised by the compositor, winit may bind the globals at
+    /// lower versions, see [`protocol_version`]. The list follows the globals added and removed
+    /// while the event loop runs.
+    ///
+    /// [`protocol_version`]: Self::protocol_version
+    fn available_globals(&self) -> Vec<(String, u32)>;
+
+    /// Whether the compositor sent the information of all the monitors.
+    ///
+    /// Winit waits for the initial information when creating the event loop, so this is usually
+    /// `true`, unless a monitor was just connected. The monitors without the information are
+    /// not returned by [`EventLoopWindowTarget::available_monitors`].
+    fn outputs_ready(&self) -> bool;
+
+    /// Send [`Event::UserIdle`] once the user didn't use the input devices of any seat for
+    /// `timeout`, and [`Event::UserActive`] once they're used again, or stop with `None`.
+    ///
+    /// This follows `ext_idle_notifier_v1` and only observes the idleness, unlike the idle
+    /// inhibitors which prevent it. The compositor could take longer than `timeout` or consider
+    /// the user active while something inhibits idleness, e.g. a playing video. Changing the
+    /// timeout sends [`Event::UserActive`] when the user was idle, since it's measured anew. Does
+    /// nothing when the compositor doesn't support the protocol.
+    ///
+    /// [`Event::UserIdle`]: crate::event::Event::UserIdle
+    /// [`Event::UserActive`]: crate::event::Event::UserActive
+    fn set_idle_notification(&self, timeout: Option<Duration>);
+
+    /// Set the namespace of the layer shell windows created afterwards without
+    /// [`WindowBuilderExtWayland::with_layer_namespace`].
+    ///
+    /// Compositors apply their rules, e.g. for blur or animations, to the layer shell windows by
+    /// namespace, so sharing one treats the windows of a shell as a group.
+    fn set_default_layer_namespace(&self, namespace: impl Into<String>);
+
+    /// Create a bare `wl_surface` on winit's connection, e.g. for an auxiliary rendering surface
+    /// which isn't a window, returning the pointer to the `wl_surface` and its [`SurfaceToken`].
+    ///
+    /// Giving the surface a role and presenting to it is left to the user, winit only dispatches
+    /// its events, which are polled with the token. The surface is destroyed when the token is
+    /// dropped.
+    ///
+    /// Returns `None` when the [`EventLoopWindowTarget`] doesn't use Wayland.
+    fn create_surface(&self) -> Option<(NonNull<c_void>, SurfaceToken)>;
+
+    /// Perform a roundtrip with the compositor, returning the time it took.
+    ///
+    /// This blocks until the compositor processes all the requests sent so far, so it could be
+    /// used to detect stalls of the compositor. The events received in the meantime are
+    /// delivered by the event loop as usual.
+    ///
+    /// Errors with the connection are logged, and reported by the event loop afterwards.
+    fn roundtrip(&self) -> Duration;
+
+    /// Returns the toplevel windows of all the applications, including this one, in the order
+    /// they were opened, e.g. for a taskbar.
+    ///
+    /// [`Event::ForeignToplevelsChanged`] is sent when they change. `ext_foreign_toplevel_list_v1`
+    /// intentionally doesn't expose the state of the toplevels, like whether they're minimized,
+    /// see [`EventLoopWindowTargetExtWayland::managed_toplevels`] for that.
+    /// Returns an empty list unless enabled with
+    /// [`EventLoopBuilderExtWayland::with_foreign_toplevels`], or when the compositor doesn't
+    /// support the protocol, which is often restricted to the desktop shell.
+    ///
+    /// [`Event::ForeignToplevelsChanged`]: crate::event::Event::ForeignToplevelsChanged
+    fn foreign_toplevels(&self) -> Vec<ForeignToplevel>;
+
+    /// Returns the toplevel windows of all the applications which can be controlled, with their
+    /// state, in the order they were opened.
+    ///
+    /// These are listed with `zwlr_foreign_toplevel_manager_v1`, which doesn't share the
+    /// identifiers with `ext_foreign_toplevel_list_v1`, so they're separate from
+    /// [`EventLoopWindowTargetExtWayland::foreign_toplevels`].
+    /// [`Event::ForeignToplevelsChanged`] is sent when they change. Returns an empty list unless
+    /// enabled with [`EventLoopBuilderExtWayland::with_foreign_toplevels`], or when the
+    /// compositor doesn't support the protocol.
+    ///
+    /// [`Event::ForeignToplevelsChanged`]: crate::event::Event::ForeignToplevelsChanged
+    fn managed_toplevels(&self) -> Vec<ManagedToplevel>;
+
+    /// Activate the toplevel of another application, i.e. raise and focus it, e.g. when its
+    /// taskbar button is clicked.
+    ///
+    /// It's activated on the seat with the keyboard focus on one of the windows. Ignored when
+    /// the toplevel is closed.
+    fn activate_managed_toplevel(&self, toplevel: &ManagedToplevel);
+
+    /// Minimize the toplevel of another application.
+    ///
+    /// Ignored when the toplevel is closed.
+    fn minimize_managed_toplevel(&self, toplevel: &ManagedToplevel);
+
+    /// Flush the requests queued on the connection to the compositor right away, instead of
+    /// waiting for the next iteration of the event loop, e.g. after committing a frame in
+    /// response to a key press.
+    ///
+    /// When the socket is full, the rest is flushed by the event loop. Errors with the
+    /// connection are logged, and reported by the event loop afterwards.
+    fn flush(&self);
 }
 
 impl EventLoopWindowTargetExtWayland for EventLoopWindowTarget {
@@ -18,6 +264,430 @@ impl EventLoopWindowTargetExtWayland for EventLoopWindowTarget {
     fn is_wayland(&self) -> bool {
         self.p.is_wayland()
     }
+
+    #[inline]
+    fn event_timestamp(&self) -> Option<u32> {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.event_timestamp()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => None,
+        }
+    }
+
+    #[inline]
+    fn xkb_keymap(&self) -> Option<Vec<u8>> {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.xkb_keymap()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => None,
+        }
+    }
+
+    #[inline]
+    fn keyboard_keymap_fd(&self) -> Option<(OwnedFd, u32)> {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.keyboard_keymap_fd()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => None,
+        }
+    }
+
+    #[inline]
+    fn read_clipboard(&self) -> Option<String> {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.read_clipboard()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => None,
+        }
+    }
+
+    #[inline]
+    fn write_clipboard(&self, text: String) {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.write_clipboard(text)
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => (),
+        }
+    }
+
+    #[inline]
+    fn clipboard_mime_types(&self) -> Vec<String> {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.clipboard_mime_types()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn read_clipboard_mime(&self, mime: &str) -> Option<Vec<u8>> {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.read_clipboard_mime(mime)
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => None,
+        }
+    }
+
+    #[inline]
+    fn read_clipboard_async(&self, mime: &str, callback: impl FnOnce(Option<Vec<u8>>) + 'static) {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.read_clipboard_async(mime, Box::new(callback))
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(ref window_target) => {
+                window_target.call_later(Box::new(move || callback(None)))
+            }
+        }
+    }
+
+    #[inline]
+    fn write_clipboard_mime(&self, offers: Vec<(String, Vec<u8>)>) {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.write_clipboard_mime(offers)
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => (),
+        }
+    }
+
+    #[inline]
+    fn any_window_focused(&self) -> bool {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.any_window_focused()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => false,
+        }
+    }
+
+    #[inline]
+    fn modifiers(&self) -> ModifiersState {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.modifiers()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => ModifiersState::empty(),
+        }
+    }
+
+    #[inline]
+    fn seats(&self) -> Vec<SeatHandle> {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.seats()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn protocol_version(&self, interface: &str) -> Option<u32> {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.protocol_version(interface)
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => None,
+        }
+    }
+
+    #[inline]
+    fn layer_shell_version(&self) -> Option<u32> {
+        self.protocol_version("zwlr_layer_shell_v1")
+    }
+
+    #[inline]
+    fn available_globals(&self) -> Vec<(String, u32)> {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.available_globals()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn set_default_layer_namespace(&self, namespace: impl Into<String>) {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.set_default_layer_namespace(namespace.into())
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => (),
+        }
+    }
+
+    #[inline]
+    fn set_idle_notification(&self, timeout: Option<Duration>) {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.set_idle_notification(timeout)
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => (),
+        }
+    }
+
+    #[inline]
+    fn outputs_ready(&self) -> bool {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.outputs_ready()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => true,
+        }
+    }
+
+    #[inline]
+    fn create_surface(&self) -> Option<(NonNull<c_void>, SurfaceToken)> {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                let inner = window_target.create_surface();
+                Some((inner.wl_surface_ptr(), SurfaceToken { inner }))
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => None,
+        }
+    }
+
+    #[inline]
+    fn roundtrip(&self) -> Duration {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.roundtrip()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => Duration::ZERO,
+        }
+    }
+
+    #[inline]
+    fn foreign_toplevels(&self) -> Vec<ForeignToplevel> {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.foreign_toplevels()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn managed_toplevels(&self) -> Vec<ManagedToplevel> {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.managed_toplevels()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn activate_managed_toplevel(&self, toplevel: &ManagedToplevel) {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.activate_managed_toplevel(toplevel)
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => (),
+        }
+    }
+
+    #[inline]
+    fn minimize_managed_toplevel(&self, toplevel: &ManagedToplevel) {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.minimize_managed_toplevel(toplevel)
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => (),
+        }
+    }
+
+    #[inline]
+    fn flush(&self) {
+        match self.p {
+            crate::platform_impl::EventLoopWindowTarget::Wayland(ref window_target) => {
+                window_target.flush()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopWindowTarget::X(_) => (),
+        }
+    }
+}
+
+/// A seat, i.e. a group of input devices used by a single user.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SeatHandle {
+    pub(crate) id: u32,
+    pub(crate) name: Option<String>,
+}
+
+impl SeatHandle {
+    /// The identifier of the seat, matching the [`DeviceIdExtWayland::seat_id`] of the devices
+    /// on this seat.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// The name of the seat, e.g. `seat0`.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+/// The handle to a bare `wl_surface`, see [`EventLoopWindowTargetExtWayland::create_surface`].
+#[derive(Debug)]
+pub struct SurfaceToken {
+    inner: crate::platform_impl::wayland::BareSurface,
+}
+
+impl SurfaceToken {
+    /// Request a frame callback, which the compositor sends after the next commit of the surface
+    /// when it's a good time to draw the next frame.
+    #[inline]
+    pub fn request_frame(&self) {
+        self.inner.request_frame()
+    }
+
+    /// Whether the frame callback requested with [`SurfaceToken::request_frame`] was received
+    /// since the last call.
+    #[inline]
+    pub fn take_frame(&self) -> bool {
+        self.inner.take_frame_done()
+    }
+
+    /// The monitors the surface is shown on, as reported by the enter and leave events.
+    #[inline]
+    pub fn monitors(&self) -> Vec<MonitorHandle> {
+        self.inner
+            .monitors()
+            .into_iter()
+            .map(|inner| MonitorHandle {
+                inner: crate::platform_impl::MonitorHandle::Wayland(inner),
+            })
+            .collect()
+    }
+
+    /// The integer scale of the monitors the surface is shown on.
+    #[inline]
+    pub fn scale_factor(&self) -> i32 {
+        self.inner.scale_factor()
+    }
+}
+
+/// The timing of the latest frames of a window, see [`WindowExtWayland::last_frame_timing`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameTiming {
+    /// The time the last frame was presented, in the clock of the compositor, which is usually
+    /// `CLOCK_MONOTONIC`.
+    pub presented_at: Duration,
+
+    /// The refresh interval of the output the last frame was presented on.
+    ///
+    /// `None` when the output doesn't have a constant refresh rate, e.g. with variable refresh
+    /// rate.
+    pub refresh_interval: Option<Duration>,
+
+    /// The average interval between the latest presented frames.
+    pub average_frame_interval: Duration,
+
+    /// The mean deviation of the latest frame intervals from their average.
+    pub frame_interval_jitter: Duration,
+}
+
+/// A toplevel window of any application, see
+/// [`EventLoopWindowTargetExtWayland::foreign_toplevels`].
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ForeignToplevel {
+    /// The identifier of the toplevel, which is unique and never reused by the compositor.
+    pub identifier: String,
+
+    /// The title of the toplevel, which is empty when it has none.
+    pub title: String,
+
+    /// The app ID of the toplevel, which is empty when it has none.
+    pub app_id: String,
+}
+
+/// A toplevel window of any application which can be controlled, see
+/// [`EventLoopWindowTargetExtWayland::managed_toplevels`].
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ManagedToplevel {
+    pub(crate) id: u64,
+
+    /// The title of the toplevel, which is empty when it has none.
+    pub title: String,
+
+    /// The app ID of the toplevel, which is empty when it has none.
+    pub app_id: String,
+
+    /// Whether the toplevel is maximized.
+    pub maximized: bool,
+
+    /// Whether the toplevel is minimized.
+    pub minimized: bool,
+
+    /// Whether the toplevel is activated, i.e. focused.
+    pub activated: bool,
+
+    /// Whether the toplevel is fullscreen.
+    pub fullscreen: bool,
+}
+
+impl ManagedToplevel {
+    /// The identifier of the toplevel, which is unique and never reused by the event loop.
+    #[inline]
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+/// Additional methods on [`DeviceId`] that are specific to Wayland.
+pub trait DeviceIdExtWayland {
+    /// Returns the identifier of the seat the device belongs to, see [`SeatHandle::id`].
+    ///
+    /// Wayland doesn't expose individual input devices, thus all the devices on a seat share the
+    /// same [`DeviceId`].
+    fn seat_id(&self) -> Option<u32>;
+}
+
+impl DeviceIdExtWayland for DeviceId {
+    #[inline]
+    fn seat_id(&self) -> Option<u32> {
+        match self.0 {
+            crate::platform_impl::DeviceId::Wayland(ref device_id) => Some(device_id.seat_id()),
+            #[cfg(x11_platform)]
+            crate::platform_impl::DeviceId::X(_) => None,
+        }
+    }
 }
 
 /// Additional methods on [`EventLoopBuilder`] that are specific to Wayland.
@@ -30,6 +700,71 @@ pub trait EventLoopBuilderExtWayland {
     /// By default, the window is only allowed to be created on the main
     /// thread, to make platform compatibility easier.
     fn with_any_thread(&mut self, any_thread: bool) -> &mut Self;
+
+    /// Deliver the keyboard input raw, e.g. to forward it to a remote machine with its own
+    /// keyboard layout.
+    ///
+    /// Every `wl_keyboard::key` event is delivered as [`DeviceEvent::Key`], with the evdev
+    /// keycode available through [`PhysicalKeyExtScancode::to_scancode`], instead of
+    /// [`WindowEvent::KeyboardInput`]. The keys aren't processed by xkb, so there's no text,
+    /// composition or key repeat, though [`WindowEvent::ModifiersChanged`] is still delivered.
+    ///
+    /// [`DeviceEvent::Key`]: crate::event::DeviceEvent::Key
+    /// [`PhysicalKeyExtScancode::to_scancode`]: crate::platform::scancode::PhysicalKeyExtScancode::to_scancode
+    fn with_raw_keyboard(&mut self, raw_keyboard: bool) -> &mut Self;
+
+    /// Deliver every configure changing the size of a window as its own
+    /// [`WindowEvent::Resized`], e.g. to animate the content for every intermediate size of an
+    /// interactive resize.
+    ///
+    /// By default, the configures received in one dispatch of the event loop are coalesced, and
+    /// a single `Resized` with the latest size is delivered. With the raw configures, the
+    /// intermediate sizes are delivered before it, though a redraw is still only requested for
+    /// the latest size. Each `Resized` usually makes the application reallocate its buffers, so
+    /// this costs more work for the sizes which are never presented, and could make the resize
+    /// lag behind the pointer when the application can't keep up with the compositor.
+    fn with_raw_configures(&mut self, raw_configures: bool) -> &mut Self;
+
+    /// Bind `zwp_tablet_manager_v2` to deliver the input of the tablet pads as
+    /// [`WindowEvent::TabletPad`].
+    ///
+    /// The compositors stop emulating the pointer for the tablet tools of the seats once winit
+    /// binds their tablets, and winit doesn't deliver the tool events, so the stylus no longer
+    /// moves the pointer when this is enabled. By default, the tablets aren't bound.
+    ///
+    /// [`WindowEvent::TabletPad`]: crate::event::WindowEvent::TabletPad
+    fn with_tablets(&mut self, tablets: bool) -> &mut Self;
+
+    /// Bind `ext_foreign_toplevel_list_v1` and `zwlr_foreign_toplevel_manager_v1` to list the
+    /// toplevels of all the applications with
+    /// [`EventLoopWindowTargetExtWayland::foreign_toplevels`] and
+    /// [`EventLoopWindowTargetExtWayland::managed_toplevels`].
+    ///
+    /// The compositor sends the title and app ID of every toplevel, and their updates, to the
+    /// clients bound to the list, so only enable it when the toplevels are shown, e.g. in a
+    /// taskbar. By default, the list isn't bound.
+    fn with_foreign_toplevels(&mut self, foreign_toplevels: bool) -> &mut Self;
+
+    /// Trace the protocol messages winit exchanges with the compositor with `log::trace!`, under
+    /// the `winit::wayland::protocol` target, e.g. to capture the configures of the windows and
+    /// their acks in the log of the application.
+    ///
+    /// The messages are formatted like the `WAYLAND_DEBUG=client` trace of `libwayland-client`,
+    /// which is only printed to stderr. Only the messages handled by winit itself are traced,
+    /// i.e. the configures, acks, commits and frame callbacks of the windows and the focus of
+    /// the seats, but not the buffers attached by the graphics APIs, nor the keys, which could
+    /// be passwords. By default, the messages aren't traced, which only costs checking a flag.
+    fn with_protocol_logging(&mut self, protocol_logging: bool) -> &mut Self;
+
+    /// Log under the `log_target` instead of the paths of winit's modules, e.g. to tell apart the
+    /// logs of several plugins embedded in one host process.
+    ///
+    /// This applies to the logs of the Wayland backend and of the Wayland extension traits, like
+    /// the warnings about the methods ignored on X11 windows. The code shared with the other
+    /// platforms, the libraries winit uses, and the trace of
+    /// [`EventLoopBuilderExtWayland::with_protocol_logging`] keep their targets. Since only one
+    /// event loop exists at a time, the target applies until the next event loop is built.
+    fn with_log_target(&mut self, log_target: impl Into<String>) -> &mut Self;
 }
 
 impl<T> EventLoopBuilderExtWayland for EventLoopBuilder<T> {
@@ -44,76 +779,725 @@ impl<T> EventLoopBuilderExtWayland for EventLoopBuilder<T> {
         self.platform_specific.any_thread = any_thread;
         self
     }
+
+    #[inline]
+    fn with_raw_keyboard(&mut self, raw_keyboard: bool) -> &mut Self {
+        self.platform_specific.raw_keyboard = raw_keyboard;
+        self
+    }
+
+    #[inline]
+    fn with_raw_configures(&mut self, raw_configures: bool) -> &mut Self {
+        self.platform_specific.raw_configures = raw_configures;
+        self
+    }
+
+    #[inline]
+    fn with_tablets(&mut self, tablets: bool) -> &mut Self {
+        self.platform_specific.tablets = tablets;
+        self
+    }
+
+    #[inline]
+    fn with_foreign_toplevels(&mut self, foreign_toplevels: bool) -> &mut Self {
+        self.platform_specific.foreign_toplevels = foreign_toplevels;
+        self
+    }
+
+    #[inline]
+    fn with_protocol_logging(&mut self, protocol_logging: bool) -> &mut Self {
+        self.platform_specific.protocol_logging = protocol_logging;
+        self
+    }
+
+    #[inline]
+    fn with_log_target(&mut self, log_target: impl Into<String>) -> &mut Self {
+        self.platform_specific.log_target = Some(log_target.into());
+        self
+    }
 }
 
-/// Additional methods on [`Window`] that are specific to Wayland.
-pub trait WindowExtWayland {
-    fn set_layer(&self, layer: Layer);
-    fn set_anchor(&self, anchor: Anchor);
-    fn set_exclusive_zone(&self, exclusive_zone: i32);
-    fn set_margin(&self, top: i32, right: i32, bottom: i32, left: i32);
-    fn set_keyboard_interactivity(&self, keyboard_interactivity: KeyboardInteractivity);
-}
+/// Additional methods on [`EventLoopProxy`] that are specific to Wayland.
+pub trait EventLoopProxyExtWayland {
+    /// Deliver a synthetic [`WindowEvent`] to the window with the given id.
+    ///
+    /// The event is delivered by the event loop as if it was received from the compositor,
+    /// which is useful to test the event handling without a real user input.
+    fn send_window_event(&self, window_id: WindowId, event: WindowEvent);
+}
+
+impl<T: 'static> EventLoopProxyExtWayland for EventLoopProxy<T> {
+    #[inline]
+    fn send_window_event(&self, window_id: WindowId, event: WindowEvent) {
+        match self.event_loop_proxy {
+            crate::platform_impl::EventLoopProxy::Wayland(ref proxy) => {
+                proxy.send_window_event(window_id.0, event)
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::EventLoopProxy::X(_) => {
+                warn!("send_window_event is ignored on X11")
+            }
+        }
+    }
+}
+
+/// Additional methods on [`Window`] that are specific to Wayland.
+///
+/// ## Rendering
+///
+/// Explicit synchronization with `wp_linux_drm_syncobj_manager_v1` isn't handled by winit,
+/// since the acquire and release points belong to the buffer attached by the renderer, which
+/// winit doesn't commit. The Vulkan and EGL implementations negotiate it on the window's
+/// `wl_surface` themselves when the compositor supports it.
+pub trait WindowExtWayland {
+    fn set_layer(&self, layer: Layer);
+
+    /// Change the edges the layer shell window is anchored to.
+    ///
+    /// The margin and exclusive zone are committed together with the new anchor, so the area
+    /// reserved for the window moves to the new edges at once. When changing them as well, set
+    /// them before the anchor.
+    fn set_anchor(&self, anchor: Anchor);
+
+    /// Change the size of the area reserved for the layer shell window at its anchored edge.
+    ///
+    /// The new size is applied with the next commit of the surface, usually when presenting the
+    /// next frame, so multiple changes in between are coalesced. Every applied change makes the
+    /// compositor lay the other windows out again, which is expensive, so when animating e.g. an
+    /// auto-hiding dock, prefer animating its content and change the zone only at the start and
+    /// the end of the animation.
+    fn set_exclusive_zone(&self, exclusive_zone: i32);
+    fn set_margin(&self, top: i32, right: i32, bottom: i32, left: i32);
+    fn set_keyboard_interactivity(&self, keyboard_interactivity: KeyboardInteractivity);
+
+    /// Take the keyboard focus for the layer shell window with the
+    /// [`KeyboardInteractivity::OnDemand`] interactivity, e.g. when the user clicked a search
+    /// field in a bar.
+    ///
+    /// The focus is taken by committing the [`KeyboardInteractivity::Exclusive`] interactivity
+    /// for the window until it gets the focus, [`WindowEvent::Focused`] is sent then. The
+    /// `OnDemand` interactivity is applied again with the next commit of the window, e.g. when
+    /// redrawing in response to the focus change, and the compositor keeps the focus on the
+    /// window until the user moves it elsewhere.
+    ///
+    /// This is ignored for the other interactivities, for XDG windows and when the window already
+    /// has the focus.
+    ///
+    /// [`WindowEvent::Focused`]: crate::event::WindowEvent::Focused
+    fn request_keyboard_focus(&self);
+
+    /// Give up the keyboard focus of the layer shell window with the
+    /// [`KeyboardInteractivity::OnDemand`] interactivity, e.g. when the user clicked away from a
+    /// search field in a bar.
+    ///
+    /// This works like [`request_keyboard_focus`], with the [`KeyboardInteractivity::None`]
+    /// interactivity committed until the window loses the focus.
+    ///
+    /// [`request_keyboard_focus`]: Self::request_keyboard_focus
+    fn release_keyboard_focus(&self);
+
+    /// Commit the window's surface, applying all the pending state on it.
+    ///
+    /// This is meant for applications that present to the surface on their own, bypassing the
+    /// usual presentation flow, e.g. after attaching a buffer out of band.
+    fn commit(&self);
+
+    /// Show the compositor's window menu at the given position in surface local coordinates.
+    ///
+    /// The menu is requested with the serial of the latest pointer button press, so this should
+    /// be called in response to one, e.g. a right click on the client side title bar. This is the
+    /// same as [`Window::show_window_menu`] with a [`LogicalPosition`].
+    ///
+    /// This is ignored for layer shell windows.
+    ///
+    /// [`LogicalPosition`]: crate::dpi::LogicalPosition
+    fn show_window_menu(&self, position: (i32, i32));
+
+    /// Returns the maximum size the compositor suggests for the window, in logical pixels.
+    ///
+    /// This is usually the size of the work area of the output the window is on, and could be
+    /// used to clamp the saved window geometry. Winit already applies the bounds when the
+    /// compositor lets the client pick the size of the window.
+    ///
+    /// Returns `None` when the compositor didn't suggest any bounds, and for layer shell windows.
+    fn suggested_bounds(&self) -> Option<(u32, u32)>;
+
+    /// Returns whether the compositor draws the decorations of the window, as negotiated with
+    /// `zxdg_toplevel_decoration_v1`.
+    ///
+    /// Returns `false` when winit draws the decorations, or before the first configure. The
+    /// compositor may refuse to stop drawing the decorations of a window with decorations
+    /// disabled, in which case this still returns `true`. The compositor can change the mode at
+    /// any time, e.g. when the window is maximized, so this should be queried again on
+    /// [`WindowEvent::Resized`].
+    ///
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
+    fn has_server_side_decorations(&self) -> bool;
+
+    /// Returns the window management actions the compositor supports for the window, like
+    /// maximizing, minimizing or showing the window menu.
+    ///
+    /// Client side decorations should hide the buttons of the unsupported actions. This is
+    /// updated with every configure, and all the actions are assumed to be supported until the
+    /// compositor says otherwise. Layer shell windows and popups support none of them.
+    fn wm_capabilities(&self) -> WmCapabilities;
+
+    /// Returns the state of the window from the latest configure, like maximized, fullscreen or
+    /// tiled on some edges.
+    ///
+    /// Client side decorations should draw square corners without shadows when the window is
+    /// maximized or tiled, like winit's own decorations do. The state is updated with every
+    /// configure, and [`WindowEvent::WindowStateChanged`] is sent when the maximized or
+    /// fullscreen state changes, but not when only the tiled edges change. Empty before the first
+    /// configure, and for layer shell windows and popups.
+    ///
+    /// [`WindowEvent::WindowStateChanged`]: crate::event::WindowEvent::WindowStateChanged
+    fn toplevel_state(&self) -> ToplevelState;
+
+    /// Returns the `(top, right, bottom, left)` insets of the decorations drawn by winit, in
+    /// physical pixels.
+    ///
+    /// These are the differences between [`Window::outer_size`] and [`Window::inner_size`] on
+    /// each side. They're all zero when the compositor draws the decorations, when the window is
+    /// undecorated or fullscreen, and for layer shell windows and popups. The title bar height
+    /// depends on the scale factor, so this should be queried again on
+    /// [`WindowEvent::Resized`].
+    ///
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
+    fn decoration_insets(&self) -> (u32, u32, u32, u32);
+
+    /// Returns the timing of the latest frames presented by the compositor, as reported by
+    /// `wp_presentation`.
+    ///
+    /// The feedback is only requested for the frames announced with
+    /// [`Window::pre_present_notify`], so it must be called before presenting each frame. Returns
+    /// `None` until two frames were presented, or when the compositor doesn't support
+    /// `wp_presentation`.
+    fn last_frame_timing(&self) -> Option<FrameTiming>;
+
+    /// Returns the interval between the refreshes of the output the window is presented on.
+    ///
+    /// This is the [`FrameTiming::refresh_interval`] reported with the latest presented frames
+    /// when available, falling back to the refresh rate of the current mode of the window's
+    /// [`Window::current_monitor`]. Returns `None` when neither is known, e.g. when the output
+    /// has a variable refresh rate and the compositor doesn't report it; the actual cadence is
+    /// [`FrameTiming::average_frame_interval`] then.
+    fn current_refresh_interval(&self) -> Option<Duration>;
+
+    /// Set how the scale factor is picked when the window is on multiple monitors with different
+    /// scales, see [`ScalePreference`].
+    ///
+    /// The scale forced with [`WindowBuilderExtWayland::with_preferred_integer_scale`] takes
+    /// precedence. The default is [`ScalePreference::Compositor`].
+    fn set_scale_preference(&self, scale_preference: ScalePreference);
+
+    /// Limit [`WindowEvent::RedrawRequested`] to `fps` times per second, or remove the limit with
+    /// `None`, e.g. to save battery by drawing at 30 FPS on a 60 Hz output.
+    ///
+    /// The redraw requested before the frame interval elapsed is delivered once it did, so the
+    /// cap only works for applications presenting from `RedrawRequested`. A cap of `0` is treated
+    /// as `None`.
+    ///
+    /// `wp_fifo_v1` isn't supported, so the cap is approximated by timing the redraws on the
+    /// client, like [`ControlFlow::WaitUntil`] would, rather than by presentation barriers in the
+    /// compositor. Thus the frames aren't aligned to the refreshes of the output and could be
+    /// presented with some jitter, use [`WindowBuilderExtWayland::with_redraw_throttle`] along
+    /// with the cap to follow the frame callbacks as well.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+    fn set_frame_rate_cap(&self, fps: Option<u32>);
+
+    /// Returns the `instance` given with [`WindowBuilderExtWayland::with_name`].
+    ///
+    /// It's never sent to the compositor, so it only helps the application to tell its windows
+    /// apart when they share the application ID.
+    fn instance_name(&self) -> Option<String>;
+
+    /// Set the title and the application ID of the window together.
+    ///
+    /// Both requests are sent to the compositor at once, with the application ID first, so the
+    /// taskbars don't show the new title under the old application ID in between. Like with
+    /// [`Window::set_title`], the NUL characters are removed and the strings are truncated to
+    /// 1024 bytes, so they're always valid to send.
+    ///
+    /// This is ignored for layer shell windows and popups.
+    fn set_title_and_app_id(&self, title: impl Into<String>, app_id: impl Into<String>);
+
+    /// Returns whether the cursor icon is shown as is, instead of falling back to another icon.
+    ///
+    /// This looks the icon up in the cursor theme set with [`WindowExtWayland::set_cursor_theme`],
+    /// or in the system cursor theme. It's always `true` when the compositor draws the cursors
+    /// with `wp_cursor_shape_v1`, which has a shape for every icon.
+    fn cursor_available(&self, icon: CursorIcon) -> bool;
+
+    /// Set the parent of the window, so the compositor stacks it above the parent, e.g. for
+    /// dialogs.
+    ///
+    /// Passing `None` unsets the parent. This is ignored for layer shell windows and parents.
+    fn set_parent(&self, parent: Option<&Window>);
+
+    /// Mark the window as a modal dialog of its parent, so the compositor can dim or disable the
+    /// parent while it's open, e.g. for the windows built with
+    /// [`WindowBuilderExtWayland::as_modal_dialog`].
+    ///
+    /// This uses `xdg_wm_dialog_v1`, which winit doesn't support yet, so this only logs a
+    /// warning, like when the compositor doesn't advertise the global. The parent must still
+    /// ignore the input on its own while the dialog is open.
+    fn set_modal(&self, modal: bool);
+
+    /// Returns the integer scale of the buffers attached to the window's surface.
+    ///
+    /// Unlike [`Window::scale_factor`], this is always `1` when the compositor supports fractional
+    /// scaling, since the buffers are scaled with the viewport instead. The buffers must be
+    /// allocated at [`Window::inner_size`] in both cases, but their size must be divisible by
+    /// the buffer scale.
+    fn buffer_scale(&self) -> u32;
+
+    /// Set the geometry of the window's content within its surface, in logical pixels.
+    ///
+    /// The compositor uses it for snapping and tiling, so an application drawing its own
+    /// decorations with shadows should exclude the shadows from it. The `(x, y, width, height)`
+    /// overrides the geometry winit computes from the inner size and its own client side
+    /// decorations until it's cleared with `None`, and is applied on the next commit of the
+    /// surface.
+    ///
+    /// This is ignored for layer shell windows.
+    fn set_window_geometry(&self, geometry: Option<(i32, i32, i32, i32)>);
+
+    /// Set the region of the window to confine the cursor to with [`CursorGrabMode::Confined`],
+    /// as `(x, y, width, height)` in logical pixels relative to the window's surface.
+    ///
+    /// `None` confines the cursor to the whole surface, which is the default. When the cursor is
+    /// already confined, the region is updated live on the next commit of the surface, so it can
+    /// follow e.g. a resized viewport.
+    ///
+    /// [`CursorGrabMode::Confined`]: crate::window::CursorGrabMode::Confined
+    fn set_cursor_confine_region(&self, region: Option<(i32, i32, u32, u32)>);
+
+    /// Set the region of the window acting as its title bar, as `(x, y, width, height)` in
+    /// logical pixels relative to the window's surface.
+    ///
+    /// Pressing the left mouse button in the region moves the window once the cursor moves, and a
+    /// double click toggles its maximized state, like the client side decorations of GTK and Qt
+    /// do. The clicks in the region aren't delivered as [`WindowEvent::MouseInput`]. `None`, the
+    /// default, removes the region.
+    ///
+    /// This is ignored for layer shell windows and popups.
+    ///
+    /// [`WindowEvent::MouseInput`]: crate::event::WindowEvent::MouseInput
+    fn set_title_bar_region(&self, region: Option<(i32, i32, u32, u32)>);
+
+    /// Draw the named cursors from the given XCursor theme at the given size, in logical pixels,
+    /// instead of the ones from `XCURSOR_THEME` and `XCURSOR_SIZE`.
+    ///
+    /// `None` keeps the name of the system theme and only changes the size. The current cursor is
+    /// updated right away. The cursors are then always drawn by winit, even when the compositor
+    /// supports the cursor shape protocol, since it would use its own theme otherwise.
+    fn set_cursor_theme(&self, name: Option<String>, size: u32);
+
+    /// Returns the transform the compositor prefers the window's buffers to be rendered with,
+    /// e.g. to match a rotated monitor.
+    ///
+    /// Rendering the buffers pre-transformed lets the compositor skip the transformation when
+    /// presenting them. The transform must be applied to the buffers with the viewport or
+    /// `wl_surface::set_buffer_transform`, which is left to the renderer.
+    ///
+    /// Returns `None` when the compositor doesn't advertise the preferred transform.
+    fn preferred_buffer_transform(&self) -> Option<OutputTransform>;
+
+    /// Returns the DRM device the compositor prefers the window's dmabuf buffers to be allocated
+    /// on, as reported by the `zwp_linux_dmabuf_v1` surface feedback.
+    ///
+    /// On multi-GPU systems this is the device the compositor uses for the window's current
+    /// monitor, so allocating on it avoids copies between the GPUs. The device may change when
+    /// the window moves between monitors.
+    ///
+    /// The surface feedback is only requested on the first call, so this returns `None` until
+    /// the compositor sent it, and when the compositor doesn't support `zwp_linux_dmabuf_v1`
+    /// version 4.
+    fn preferred_dmabuf_device(&self) -> Option<libc::dev_t>;
+
+    /// Returns `true` when the pointer of any seat is over the window, as of `wl_pointer::enter`.
+    ///
+    /// The pointer focus is independent of the keyboard focus, e.g. to show the hover effects
+    /// on the windows which aren't focused.
+    fn has_pointer_focus(&self) -> bool;
+
+    /// Returns `true` when the keyboard of any seat is focused on the window, as of
+    /// `wl_keyboard::enter`.
+    ///
+    /// This is the focus reported by [`WindowEvent::Focused`] and [`Window::has_focus`].
+    fn has_keyboard_focus(&self) -> bool;
+
+    /// Set the action preferred for the drag and drops dropped on the window, among the actions
+    /// the source allows in [`WindowEvent::DragEntered`].
+    ///
+    /// All the actions of the source are accepted, with the compositor picking the preferred one
+    /// when the source allows it, and the negotiated action is reported by
+    /// [`WindowEvent::DragDropped`]. Only a single action can be preferred, and an empty one
+    /// rejects all of them. This applies to the current drag and drop and the following ones.
+    fn set_preferred_drop_action(&self, action: DndAction);
+
+    /// Accept the drag and drop over the window as the `mime_type`, or reject it with `None`,
+    /// which the source shows with its cursor.
+    ///
+    /// This applies to the current drag and drop only, so it's called on
+    /// [`WindowEvent::DragEntered`], and on [`WindowEvent::DragMoved`] to accept it over some
+    /// regions of the window only. The `mime_type` must be one of the offered ones, and the
+    /// data of the accepted drop is sent by [`WindowEvent::DragDropped`]. The drops are rejected
+    /// until they're accepted.
+    fn set_drag_accepting(&self, mime_type: Option<String>);
+}
+
+impl WindowExtWayland for Window {
+
+    fn set_layer(&self, layer: Layer) {
+        self.window.maybe_queue_on_main(move |w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                error!("set_layer is ignored on X11 windows");
+                return;
+            };
+            window.set_layer(layer);
+        });
+    }
+
+    fn set_anchor(&self, anchor: Anchor) {
+        self.window.maybe_queue_on_main(move |w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                error!("set_anchor is ignored on X11 windows");
+                return;
+            };
+            window.set_anchor(anchor);
+        });
+    }
+
+    fn set_exclusive_zone(&self, exclusive_zone: i32) {
+        self.window.maybe_queue_on_main(move |w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                error!("set_exclusive_zone is ignored on X11 windows");
+                return;
+            };
+            window.set_exclusive_zone(exclusive_zone);
+        });
+    }
+
+    fn set_margin(&self, top: i32, right: i32, bottom: i32, left: i32) {
+        self.window.maybe_queue_on_main(move |w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                error!("set_margin is ignored on X11 windows");
+                return;
+            };
+            window.set_margin(top, right, bottom, left);
+        });
+    }
+
+    fn set_keyboard_interactivity(&self, keyboard_interactivity: KeyboardInteractivity) {
+        self.window.maybe_queue_on_main(move |w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                error!("set_keyboard_interactivity is ignored on X11 windows");
+                return;
+            };
+            window.set_keyboard_interactivity(keyboard_interactivity);
+        });
+    }
+
+    fn request_keyboard_focus(&self) {
+        self.window.maybe_queue_on_main(|w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                warn!("request_keyboard_focus is ignored on X11 windows");
+                return;
+            };
+            window.request_keyboard_focus();
+        });
+    }
+
+    fn release_keyboard_focus(&self) {
+        self.window.maybe_queue_on_main(|w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                warn!("release_keyboard_focus is ignored on X11 windows");
+                return;
+            };
+            window.release_keyboard_focus();
+        });
+    }
+
+    fn commit(&self) {
+        self.window.maybe_queue_on_main(move |w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                warn!("commit is ignored on X11 windows");
+                return;
+            };
+            window.commit();
+        });
+    }
+
+    fn show_window_menu(&self, position: (i32, i32)) {
+        self.window.maybe_queue_on_main(move |w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                warn!("show_window_menu is ignored on X11 windows");
+                return;
+            };
+            window.show_window_menu_at(position.0, position.1);
+        });
+    }
+
+    fn set_parent(&self, parent: Option<&Window>) {
+        let parent = parent.and_then(xdg_toplevel);
+        self.window.maybe_queue_on_main(move |w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                warn!("set_parent is ignored on X11 windows");
+                return;
+            };
+            window.set_parent(parent.as_ref());
+        });
+    }
+
+    fn set_modal(&self, modal: bool) {
+        self.window.maybe_queue_on_main(move |w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                warn!("set_modal is ignored on X11 windows");
+                return;
+            };
+            window.set_modal(modal);
+        });
+    }
+
+    fn buffer_scale(&self) -> u32 {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => window.buffer_scale(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => 1,
+        })
+    }
+
+    fn suggested_bounds(&self) -> Option<(u32, u32)> {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => window.suggested_bounds(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => None,
+        })
+    }
+
+    fn toplevel_state(&self) -> ToplevelState {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => window.toplevel_state(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => ToplevelState::empty(),
+        })
+    }
+
+    fn has_server_side_decorations(&self) -> bool {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => {
+                window.has_server_side_decorations()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => false,
+        })
+    }
+
+    fn wm_capabilities(&self) -> WmCapabilities {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => window.wm_capabilities(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => WmCapabilities::empty(),
+        })
+    }
+
+    fn decoration_insets(&self) -> (u32, u32, u32, u32) {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => window.decoration_insets(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => (0, 0, 0, 0),
+        })
+    }
+
+    fn last_frame_timing(&self) -> Option<FrameTiming> {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => window.last_frame_timing(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => None,
+        })
+    }
+
+    fn set_frame_rate_cap(&self, fps: Option<u32>) {
+        self.window.maybe_queue_on_main(move |w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                warn!("set_frame_rate_cap is ignored on X11 windows");
+                return;
+            };
+            window.set_frame_rate_cap(fps);
+        });
+    }
+
+    fn set_scale_preference(&self, scale_preference: ScalePreference) {
+        self.window.maybe_queue_on_main(move |w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                warn!("set_scale_preference is ignored on X11 windows");
+                return;
+            };
+            window.set_scale_preference(scale_preference);
+        });
+    }
+
+    fn current_refresh_interval(&self) -> Option<Duration> {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => window.current_refresh_interval(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => None,
+        })
+    }
+
+    fn instance_name(&self) -> Option<String> {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => window.instance_name(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => None,
+        })
+    }
+
+    fn set_title_and_app_id(&self, title: impl Into<String>, app_id: impl Into<String>) {
+        let title = title.into();
+        let app_id = app_id.into();
+        self.window.maybe_queue_on_main(move |w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                warn!("set_title_and_app_id is ignored on X11 windows");
+                return;
+            };
+            window.set_title_and_app_id(title, app_id);
+        });
+    }
+
+    fn cursor_available(&self, icon: CursorIcon) -> bool {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => window.cursor_available(icon),
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => true,
+        })
+    }
 
-impl WindowExtWayland for Window {
+    fn set_window_geometry(&self, geometry: Option<(i32, i32, i32, i32)>) {
+        self.window.maybe_queue_on_main(move |w| {
+            let crate::platform_impl::Window::Wayland(ref window) = w else {
+                warn!("set_window_geometry is ignored on X11 windows");
+                return;
+            };
+            window.set_window_geometry(geometry);
+        });
+    }
 
-    fn set_layer(&self, layer: Layer) {
+    fn set_cursor_confine_region(&self, region: Option<(i32, i32, u32, u32)>) {
         self.window.maybe_queue_on_main(move |w| {
             let crate::platform_impl::Window::Wayland(ref window) = w else {
-                log::error!("set_layer is ignored on X11 windows");
+                warn!("set_cursor_confine_region is ignored on X11 windows");
                 return;
             };
-            window.set_layer(layer);
+            window.set_cursor_confine_region(region);
         });
     }
 
-    fn set_anchor(&self, anchor: Anchor) {
+    fn set_title_bar_region(&self, region: Option<(i32, i32, u32, u32)>) {
         self.window.maybe_queue_on_main(move |w| {
             let crate::platform_impl::Window::Wayland(ref window) = w else {
-                log::error!("set_anchor is ignored on X11 windows");
+                warn!("set_title_bar_region is ignored on X11 windows");
                 return;
             };
-            window.set_anchor(anchor);
+            window.set_title_bar_region(region);
         });
     }
 
-    fn set_exclusive_zone(&self, exclusive_zone: i32) {
+    fn set_cursor_theme(&self, name: Option<String>, size: u32) {
         self.window.maybe_queue_on_main(move |w| {
             let crate::platform_impl::Window::Wayland(ref window) = w else {
-                log::error!("set_exclusive_zone is ignored on X11 windows");
+                warn!("set_cursor_theme is ignored on X11 windows");
                 return;
             };
-            window.set_exclusive_zone(exclusive_zone);
+            window.set_cursor_theme(name, size);
         });
     }
 
-    fn set_margin(&self, top: i32, right: i32, bottom: i32, left: i32) {
+    fn preferred_buffer_transform(&self) -> Option<OutputTransform> {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => {
+                window.preferred_buffer_transform()
+            }
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => None,
+        })
+    }
+
+    fn preferred_dmabuf_device(&self) -> Option<libc::dev_t> {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => window.preferred_dmabuf_device(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => None,
+        })
+    }
+
+    fn has_pointer_focus(&self) -> bool {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => window.has_pointer_focus(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => false,
+        })
+    }
+
+    fn has_keyboard_focus(&self) -> bool {
+        self.window.maybe_wait_on_main(|w| match w {
+            crate::platform_impl::Window::Wayland(ref window) => window.has_focus(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::Window::X(_) => false,
+        })
+    }
+
+    fn set_preferred_drop_action(&self, action: DndAction) {
         self.window.maybe_queue_on_main(move |w| {
             let crate::platform_impl::Window::Wayland(ref window) = w else {
-                log::error!("set_margin is ignored on X11 windows");
+                warn!("set_preferred_drop_action is ignored on X11 windows");
                 return;
             };
-            window.set_margin(top, right, bottom, left);
+            window.set_preferred_drop_action(action);
         });
     }
 
-    fn set_keyboard_interactivity(&self, keyboard_interactivity: KeyboardInteractivity) {
+    fn set_drag_accepting(&self, mime_type: Option<String>) {
         self.window.maybe_queue_on_main(move |w| {
             let crate::platform_impl::Window::Wayland(ref window) = w else {
-                log::error!("set_keyboard_interactivity is ignored on X11 windows");
+                warn!("set_drag_accepting is ignored on X11 windows");
                 return;
             };
-            window.set_keyboard_interactivity(keyboard_interactivity);
+            window.set_drag_accepting(mime_type);
         });
     }
 }
 
+/// Get the `xdg_toplevel` of the window to use it as a parent.
+fn xdg_toplevel(window: &Window) -> Option<XdgToplevel> {
+    window.window.maybe_wait_on_main(|w| match w {
+        crate::platform_impl::Window::Wayland(ref window) => window.xdg_toplevel(),
+        #[cfg(x11_platform)]
+        crate::platform_impl::Window::X(_) => None,
+    })
+}
+
+/// Get the layer surface of the window to use it as a popup parent.
+fn layer_surface(window: &Window) -> Option<LayerSurface> {
+    window.window.maybe_wait_on_main(|w| match w {
+        crate::platform_impl::Window::Wayland(ref window) => window.layer_surface(),
+        #[cfg(x11_platform)]
+        crate::platform_impl::Window::X(_) => None,
+    })
+}
+
 /// Additional methods on [`WindowBuilder`] that are specific to Wayland.
 pub trait WindowBuilderExtWayland {
     /// Build window with the given name.
     ///
     /// The `general` name sets an application ID, which should match the `.desktop`
-    /// file distributed with your program. The `instance` is a `no-op`.
+    /// file distributed with your program. The compositors group the windows by the
+    /// application ID, and Wayland has no equivalent of the `instance`, so it's only recorded
+    /// to tell the windows apart, see [`WindowExtWayland::instance_name`].
     ///
     /// For details about application ID conventions, see the
     /// [Desktop Entry Spec](https://specifications.freedesktop.org/desktop-entry-spec/desktop-entry-spec-latest.html#desktop-file-id)
@@ -122,8 +1506,33 @@ pub trait WindowBuilderExtWayland {
     ///
     /// Building this window will fail if the compositor does not support the `zwlr_layer_shell_v1`
     /// protocol.
+    ///
+    /// The size from [`WindowBuilder::with_inner_size`] is requested before the window is mapped.
+    /// A zero width or height lets the compositor pick it, which requires anchoring the window to
+    /// both of the opposite edges with [`WindowBuilderExtWayland::with_anchor`].
+    ///
+    /// When the compositor closes the window, e.g. because its output was unplugged,
+    /// [`WindowEvent::CloseRequested`] is sent followed by [`WindowEvent::SurfaceDestroyed`],
+    /// and the window should be recreated.
+    ///
+    /// An existing window can't be turned into a layer shell window or back, since Wayland
+    /// doesn't allow changing the role of a surface once it's assigned. Create a new window
+    /// instead; only the rendering surface has to be recreated for it, e.g. the `EGLSurface`,
+    /// while the GL context or the Vulkan device can be kept. The layer of a layer shell window
+    /// can be changed with [`WindowExtWayland::set_layer`].
+    ///
+    /// [`WindowEvent::CloseRequested`]: crate::event::WindowEvent::CloseRequested
+    /// [`WindowEvent::SurfaceDestroyed`]: crate::event::WindowEvent::SurfaceDestroyed
     fn with_layer_shell(self, layer: Layer) -> Self;
 
+    /// Set the namespace of the layer shell window, which compositors use to apply their rules
+    /// to the window, e.g. `"panel"` or `"notifications"`.
+    ///
+    /// Overrides the one set with
+    /// [`EventLoopWindowTargetExtWayland::set_default_layer_namespace`]. Ignored unless the
+    /// window is created with [`WindowBuilderExtWayland::with_layer_shell`].
+    fn with_layer_namespace(self, namespace: impl Into<String>) -> Self;
+
     fn with_anchor(self, anchor: Anchor) -> Self;
 
     fn with_exclusive_zone(self, exclusive_zone: i32) -> Self;
@@ -133,6 +1542,144 @@ pub trait WindowBuilderExtWayland {
     fn with_keyboard_interactivity(self, keyboard_interactivity: KeyboardInteractivity) -> Self;
 
     fn with_output(self, output: Option<String>) -> Self;
+
+    /// Create this window as a layer shell surface covering the whole `monitor`, e.g. for kiosks.
+    ///
+    /// This is a shorthand for [`Layer::Top`] anchored to all the edges of the monitor, with the
+    /// exclusive zone of `-1` and the size picked by the compositor, so the window follows the
+    /// resolution of the monitor. When `monitor` is `None`, the compositor picks the monitor.
+    fn with_layer_fullscreen(self, monitor: Option<&MonitorHandle>) -> Self;
+
+    /// Create this window as a layer shell surface of the given size, in logical pixels, centered
+    /// on the monitor by the compositor, e.g. for modal dialogs.
+    ///
+    /// This is a shorthand for the given `layer` with no anchor, so the compositor centers the
+    /// window, and the size from [`WindowBuilder::with_inner_size`] replaced by `width` and
+    /// `height`. The margin has no effect without an anchor, so it's cleared.
+    fn with_centered_layer(self, width: u32, height: u32, layer: Layer) -> Self;
+
+    /// Restore the geometry of a layer shell window saved from an earlier session, i.e. its
+    /// `anchor`, `margin` as `(top, right, bottom, left)`, and `size` in logical pixels.
+    ///
+    /// This is a shorthand for [`WindowBuilderExtWayland::with_anchor`],
+    /// [`WindowBuilderExtWayland::with_margin`] and [`WindowBuilder::with_inner_size`], so the
+    /// geometry to save is the one last set by the application and the latest
+    /// [`Window::inner_size`]. Ignored unless the window is created with
+    /// [`WindowBuilderExtWayland::with_layer_shell`].
+    ///
+    /// The position of the other windows can't be restored, since Wayland doesn't let the
+    /// clients position their windows by design, only their size.
+    fn with_saved_geometry(
+        self,
+        anchor: Anchor,
+        margin: (i32, i32, i32, i32),
+        size: LogicalSize<u32>,
+    ) -> Self;
+
+    /// Create this window as a wallpaper covering the whole `monitor`, behind all the other
+    /// windows.
+    ///
+    /// This is a shorthand for [`Layer::Background`] anchored to all the edges of the monitor,
+    /// with the exclusive zone of `0`, so the wallpaper covers the area left by the panels, and
+    /// the size picked by the compositor. The window never takes the keyboard focus and lets the
+    /// pointer and touch input through, which could be changed after building it with
+    /// [`WindowExtWayland::set_keyboard_interactivity`] and [`Window::set_cursor_hittest`].
+    ///
+    /// When `monitor` is `None`, the compositor picks the monitor. To cover all the monitors,
+    /// build a window for each of them.
+    #[allow(clippy::wrong_self_convention)]
+    fn as_wallpaper(self, monitor: Option<&MonitorHandle>) -> Self;
+
+    /// Build the window as a child of the `parent` window, so the compositor stacks it above the
+    /// parent, e.g. for dialogs.
+    ///
+    /// This is ignored for layer shell windows and parents.
+    fn with_parent(self, parent: &Window) -> Self;
+
+    /// Build the window as a modal dialog of the `parent` window, i.e. a non-resizable child of
+    /// the parent, see [`WindowBuilderExtWayland::with_parent`].
+    ///
+    /// Compositors usually keep the dialog above the parent and minimize and restore it along
+    /// with the parent. The modality itself isn't communicated, since winit doesn't support
+    /// `xdg_wm_dialog_v1` yet, so the parent must ignore the input on its own while the dialog
+    /// is open.
+    #[allow(clippy::wrong_self_convention)]
+    fn as_modal_dialog(self, parent: &Window) -> Self;
+
+    /// Build the window as an `xdg_popup` of the `parent` layer shell window, placed with the
+    /// given `positioner`.
+    ///
+    /// The size of the popup is taken from [`WindowBuilder::with_inner_size`]. The popup grabs
+    /// the input of the seat which last pressed a button on the parent, so it's dismissed by the
+    /// compositor when clicking outside of it, which is reported as
+    /// [`WindowEvent::CloseRequested`] followed by [`WindowEvent::SurfaceDestroyed`]. Without
+    /// such a press, e.g. when opened from the keyboard, the popup doesn't grab the input.
+    ///
+    /// The popup can be built hidden and shown later with [`Window::set_visible`], but once it's
+    /// hidden it can't be shown again, so create a new popup instead.
+    ///
+    /// This is ignored when `parent` is not a layer shell window.
+    #[allow(clippy::wrong_self_convention)]
+    fn as_layer_popup(self, parent: &Window, positioner: PopupPositioner) -> Self;
+
+    /// Limit [`WindowEvent::RedrawRequested`] to at most one per frame callback of the
+    /// compositor, coalescing all the [`Window::request_redraw`] calls in between.
+    ///
+    /// Winit requests the frame callback on behalf of the user when delivering the redraw, so
+    /// the user must present to the window in response to every redraw, otherwise the redraws
+    /// stall until something else commits the window's surface.
+    ///
+    /// This is ignored for layer shell windows and popups, which don't use frame callbacks.
+    fn with_redraw_throttle(self, throttle: bool) -> Self;
+
+    /// Build the window as a tooltip, which never takes the keyboard focus.
+    ///
+    /// For layer shell windows, this places the window on [`Layer::Overlay`] with
+    /// [`KeyboardInteractivity::None`], overriding [`WindowBuilderExtWayland::with_layer_shell`]
+    /// and [`WindowBuilderExtWayland::with_keyboard_interactivity`]. For popups created with
+    /// [`WindowBuilderExtWayland::as_layer_popup`], this skips the input grab, so the popup is
+    /// only dismissed together with its parent.
+    ///
+    /// This is ignored for the other windows.
+    fn with_tooltip(self, tooltip: bool) -> Self;
+
+    /// Use the given integer scale for the window's buffers and as its scale factor, instead of
+    /// the scale preferred by the compositor, e.g. for pixel art at `2` on a `1.5` scale monitor.
+    ///
+    /// The compositor scales the buffers to the monitor, and [`WindowEvent::ScaleFactorChanged`]
+    /// is never sent. `None` follows the scale preferred by the compositor, which is the default.
+    ///
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    fn with_preferred_integer_scale(self, scale: Option<i32>) -> Self;
+
+    /// Open the window at `preferred` size, shrunk to the bounds suggested by the compositor
+    /// with the initial configure, but not below `min`, e.g. to not open a window sized for a 4K
+    /// monitor past the edges of a laptop screen.
+    ///
+    /// This replaces the size from [`WindowBuilder::with_inner_size`]. The bounds are sent by
+    /// the compositor along with the initial configure, typically for the monitor the window
+    /// will open on, which winit waits for before mapping the window, so no timeout is
+    /// involved. When the compositor doesn't suggest bounds, the window opens at `preferred`
+    /// size. Unlike [`WindowBuilder::with_min_inner_size`], `min` doesn't constrain the later
+    /// resizes. Ignored for layer shell windows and popups.
+    fn with_size_clamped_to_bounds<S: Into<Size>>(self, preferred: S, min: S) -> Self;
+
+    /// Set how the window is decorated, see [`DecorationsMode`].
+    ///
+    /// The default is [`DecorationsMode::Full`].
+    fn with_decorations_mode(self, mode: DecorationsMode) -> Self;
+
+    /// Build the window as input only, e.g. for an edge gesture zone, which receives the
+    /// pointer, touch and keyboard input without ever being drawn.
+    ///
+    /// winit attaches a transparent single pixel buffer scaled to the window with the
+    /// viewport, and commits it on every configure, so the window is mapped and resized without
+    /// rendering. The window must not be drawn into, and [`WindowEvent::RedrawRequested`] can
+    /// be ignored. Combine with [`WindowBuilderExtWayland::with_layer_shell`] for an overlay.
+    ///
+    /// Ignored when the compositor doesn't support `wp_single_pixel_buffer_manager_v1` and
+    /// `wp_viewporter`.
+    fn with_input_only(self, input_only: bool) -> Self;
 }
 
 impl WindowBuilderExtWayland for WindowBuilder {
@@ -151,6 +1698,12 @@ impl WindowBuilderExtWayland for WindowBuilder {
         self
     }
 
+    #[inline]
+    fn with_layer_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.window.platform_specific.wayland.layer_namespace = Some(namespace.into());
+        self
+    }
+
     #[inline]
     fn with_anchor(mut self, anchor: Anchor) -> Self {
         self.window.platform_specific.wayland.anchor = Some(anchor);
@@ -183,12 +1736,266 @@ impl WindowBuilderExtWayland for WindowBuilder {
         self.window.platform_specific.wayland.output = output;
         self
     }
+
+    #[inline]
+    fn with_layer_fullscreen(mut self, monitor: Option<&MonitorHandle>) -> Self {
+        let wayland = &mut self.window.platform_specific.wayland;
+        wayland.layer_shell = Some(Layer::Top);
+        wayland.anchor = Some(Anchor::all());
+        wayland.exclusive_zone = Some(-1);
+        wayland.output = monitor.and_then(MonitorHandle::name);
+        // The zero size lets the compositor use the size of the monitor.
+        self.window.inner_size = Some(LogicalSize::new(0, 0).into());
+        self
+    }
+
+    #[inline]
+    fn with_centered_layer(mut self, width: u32, height: u32, layer: Layer) -> Self {
+        let wayland = &mut self.window.platform_specific.wayland;
+        wayland.layer_shell = Some(layer);
+        wayland.anchor = Some(Anchor::empty());
+        wayland.margin = None;
+        self.window.inner_size = Some(LogicalSize::new(width, height).into());
+        self
+    }
+
+    #[inline]
+    fn with_saved_geometry(
+        mut self,
+        anchor: Anchor,
+        margin: (i32, i32, i32, i32),
+        size: LogicalSize<u32>,
+    ) -> Self {
+        let wayland = &mut self.window.platform_specific.wayland;
+        wayland.anchor = Some(anchor);
+        wayland.margin = Some(margin);
+        self.window.inner_size = Some(size.into());
+        self
+    }
+
+    #[inline]
+    fn as_wallpaper(mut self, monitor: Option<&MonitorHandle>) -> Self {
+        let wayland = &mut self.window.platform_specific.wayland;
+        wayland.layer_shell = Some(Layer::Background);
+        wayland.anchor = Some(Anchor::all());
+        wayland.exclusive_zone = Some(0);
+        wayland.keyboard_interactivity = Some(KeyboardInteractivity::None);
+        wayland.input_passthrough = true;
+        wayland.output = monitor.and_then(MonitorHandle::name);
+        // The zero size lets the compositor use the size of the monitor.
+        self.window.inner_size = Some(LogicalSize::new(0, 0).into());
+        self
+    }
+
+    #[inline]
+    fn with_parent(mut self, parent: &Window) -> Self {
+        self.window.platform_specific.wayland.parent = xdg_toplevel(parent);
+        self
+    }
+
+    #[inline]
+    fn as_modal_dialog(mut self, parent: &Window) -> Self {
+        self.window.platform_specific.wayland.parent = xdg_toplevel(parent);
+        self.window.resizable = false;
+        self
+    }
+
+    #[inline]
+    fn as_layer_popup(mut self, parent: &Window, positioner: PopupPositioner) -> Self {
+        match layer_surface(parent) {
+            Some(parent) => {
+                self.window.platform_specific.wayland.layer_popup = Some((parent, positioner))
+            }
+            None => warn!("Popup parent is not a layer shell window"),
+        }
+        self
+    }
+
+    #[inline]
+    fn with_redraw_throttle(mut self, throttle: bool) -> Self {
+        self.window.platform_specific.wayland.redraw_throttle = throttle;
+        self
+    }
+
+    #[inline]
+    fn with_tooltip(mut self, tooltip: bool) -> Self {
+        self.window.platform_specific.wayland.tooltip = tooltip;
+        self
+    }
+
+    #[inline]
+    fn with_preferred_integer_scale(mut self, scale: Option<i32>) -> Self {
+        self.window
+            .platform_specific
+            .wayland
+            .preferred_integer_scale = scale;
+        self
+    }
+
+    #[inline]
+    fn with_size_clamped_to_bounds<S: Into<Size>>(mut self, preferred: S, min: S) -> Self {
+        self.window.inner_size = Some(preferred.into());
+        self.window.platform_specific.wayland.initial_min_size = Some(min.into());
+        self
+    }
+
+    #[inline]
+    fn with_decorations_mode(mut self, mode: DecorationsMode) -> Self {
+        self.window.platform_specific.wayland.decorations_mode = mode;
+        self
+    }
+
+    #[inline]
+    fn with_input_only(mut self, input_only: bool) -> Self {
+        self.window.platform_specific.wayland.input_only = input_only;
+        self
+    }
+}
+
+/// The placement of a popup relative to its parent, see
+/// [`WindowBuilderExtWayland::as_layer_popup`].
+///
+/// All the values are in logical pixels, relative to the parent window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PopupPositioner {
+    pub(crate) anchor_rect: (i32, i32, i32, i32),
+    pub(crate) anchor: PopupAnchor,
+    pub(crate) gravity: PopupGravity,
+    pub(crate) offset: (i32, i32),
+    pub(crate) constraint_adjustment: PopupConstraintAdjustment,
+}
+
+impl PopupPositioner {
+    /// Anchor the popup to the rectangle at `x` and `y` of the given size, e.g. the button which
+    /// opened the menu.
+    ///
+    /// By default the popup is centered on the rectangle and the compositor doesn't adjust it
+    /// when it doesn't fit on the screen.
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self {
+            anchor_rect: (x, y, width.max(1), height.max(1)),
+            anchor: PopupAnchor::None,
+            gravity: PopupGravity::None,
+            offset: (0, 0),
+            constraint_adjustment: PopupConstraintAdjustment::empty(),
+        }
+    }
+
+    /// The edge or corner of the anchor rectangle the popup is attached to.
+    pub fn with_anchor(mut self, anchor: PopupAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// The direction in which the popup extends from the anchor point.
+    pub fn with_gravity(mut self, gravity: PopupGravity) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// The offset of the popup from the anchor point.
+    pub fn with_offset(mut self, x: i32, y: i32) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    /// How the compositor may move or resize the popup when it would be constrained, e.g. by the
+    /// edges of the monitor.
+    pub fn with_constraint_adjustment(mut self, adjustment: PopupConstraintAdjustment) -> Self {
+        self.constraint_adjustment = adjustment;
+        self
+    }
 }
 
 /// Additional methods on `MonitorHandle` that are specific to Wayland.
 pub trait MonitorHandleExtWayland {
     /// Returns the inner identifier of the monitor.
     fn native_id(&self) -> u32;
+
+    /// Returns the physical size of the monitor in millimeters, as reported by the compositor.
+    ///
+    /// Returns `None` when the size is unknown, which is the case for some virtual outputs.
+    fn physical_size_mm(&self) -> Option<(u32, u32)>;
+
+    /// Returns the subpixel layout of the monitor.
+    ///
+    /// Returns [`SubpixelLayout::Unknown`] until the compositor reports the geometry of the
+    /// monitor.
+    fn subpixel_layout(&self) -> SubpixelLayout;
+
+    /// Returns the description of the monitor advertised by the compositor, e.g.
+    /// `Dell U2720Q`, to show to the user along with [`MonitorHandle::name`].
+    ///
+    /// The description has no defined format and isn't guaranteed to be unique. Returns `None`
+    /// when the compositor doesn't advertise it.
+    fn description(&self) -> Option<String>;
+
+    /// Returns an identifier of the monitor which persists when it's disconnected and connected
+    /// again, unlike [`native_id`], e.g. to remember per-monitor settings.
+    ///
+    /// The identifier is made of the make and model of the monitor and the name of the connector
+    /// it's plugged into, e.g. `Dell Inc./DELL U2720Q/DP-1`, so it changes when the monitor is
+    /// plugged into another connector. Wayland doesn't expose the serial numbers, so that's the
+    /// only way to tell apart the monitors of the same model. Returns `None` when the compositor
+    /// advertises none of them.
+    ///
+    /// [`native_id`]: Self::native_id
+    fn stable_id(&self) -> Option<String>;
+
+    /// Turn the monitor on or off, e.g. for digital signage on a schedule.
+    ///
+    /// This uses `zwlr_output_power_management_v1`, which is usually restricted to the desktop
+    /// shell. Errors when the compositor doesn't support the protocol.
+    ///
+    /// The `event_loop` is the one the monitor comes from, whose connection has the global bound.
+    fn set_power(
+        &self,
+        event_loop: &EventLoopWindowTarget,
+        mode: PowerMode,
+    ) -> Result<(), NotSupportedError>;
+
+    /// Set the gamma ramps of the monitor, e.g. for night light or color calibration.
+    ///
+    /// Each ramp maps the channel values to the output ones, and must have the size the
+    /// compositor reports for the monitor, usually 256 or 1024 entries. The ramps are kept until
+    /// the monitor is removed or the connection is closed, after which the compositor restores
+    /// the original ones.
+    ///
+    /// This uses `zwlr_gamma_control_v1`, which only one client can hold for each monitor. The
+    /// first call for a monitor blocks on a roundtrip with the compositor to get the size of its
+    /// ramps, so the ramps are always checked against it.
+    fn set_gamma_ramp(
+        &self,
+        event_loop: &EventLoopWindowTarget,
+        red: &[u16],
+        green: &[u16],
+        blue: &[u16],
+    ) -> Result<(), GammaRampError>;
+
+    /// Capture the contents of the monitor, e.g. for screenshots, calling `callback` with them on
+    /// a later iteration of the event loop, once the compositor copied them.
+    ///
+    /// This uses `zwlr_screencopy_manager_v1`, which is usually restricted to trusted clients.
+    /// The callback gets [`ExternalError::NotSupported`] when the compositor doesn't support the
+    /// protocol, and [`ExternalError::Os`] when the capture failed.
+    fn capture_frame(
+        &self,
+        event_loop: &EventLoopWindowTarget,
+        callback: impl FnOnce(Result<CapturedFrame, ExternalError>) + 'static,
+    );
+
+    /// Capture the region of the monitor, like [`capture_frame`].
+    ///
+    /// The region is in logical coordinates relative to the monitor, and is clipped to it.
+    ///
+    /// [`capture_frame`]: Self::capture_frame
+    fn capture_frame_region(
+        &self,
+        event_loop: &EventLoopWindowTarget,
+        position: LogicalPosition<i32>,
+        size: LogicalSize<i32>,
+        callback: impl FnOnce(Result<CapturedFrame, ExternalError>) + 'static,
+    );
 }
 
 impl MonitorHandleExtWayland for MonitorHandle {
@@ -196,4 +2003,338 @@ impl MonitorHandleExtWayland for MonitorHandle {
     fn native_id(&self) -> u32 {
         self.inner.native_identifier()
     }
+
+    #[inline]
+    fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        match self.inner {
+            crate::platform_impl::MonitorHandle::Wayland(ref monitor) => monitor.physical_size_mm(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::MonitorHandle::X(_) => None,
+        }
+    }
+
+    #[inline]
+    fn subpixel_layout(&self) -> SubpixelLayout {
+        match self.inner {
+            crate::platform_impl::MonitorHandle::Wayland(ref monitor) => monitor.subpixel_layout(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::MonitorHandle::X(_) => SubpixelLayout::Unknown,
+        }
+    }
+
+    #[inline]
+    fn description(&self) -> Option<String> {
+        match self.inner {
+            crate::platform_impl::MonitorHandle::Wayland(ref monitor) => monitor.description(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::MonitorHandle::X(_) => None,
+        }
+    }
+
+    #[inline]
+    fn stable_id(&self) -> Option<String> {
+        match self.inner {
+            crate::platform_impl::MonitorHandle::Wayland(ref monitor) => monitor.stable_id(),
+            #[cfg(x11_platform)]
+            crate::platform_impl::MonitorHandle::X(_) => None,
+        }
+    }
+
+    #[inline]
+    fn set_power(
+        &self,
+        event_loop: &EventLoopWindowTarget,
+        mode: PowerMode,
+    ) -> Result<(), NotSupportedError> {
+        match (&event_loop.p, &self.inner) {
+            (
+                crate::platform_impl::EventLoopWindowTarget::Wayland(window_target),
+                crate::platform_impl::MonitorHandle::Wayland(monitor),
+            ) => window_target.set_monitor_power(monitor, mode),
+            #[cfg(x11_platform)]
+            _ => Err(NotSupportedError::new()),
+        }
+    }
+
+    #[inline]
+    fn set_gamma_ramp(
+        &self,
+        event_loop: &EventLoopWindowTarget,
+        red: &[u16],
+        green: &[u16],
+        blue: &[u16],
+    ) -> Result<(), GammaRampError> {
+        match (&event_loop.p, &self.inner) {
+            (
+                crate::platform_impl::EventLoopWindowTarget::Wayland(window_target),
+                crate::platform_impl::MonitorHandle::Wayland(monitor),
+            ) => window_target.set_monitor_gamma_ramp(monitor, red, green, blue),
+            #[cfg(x11_platform)]
+            _ => Err(GammaRampError::NotSupported),
+        }
+    }
+
+    #[inline]
+    fn capture_frame(
+        &self,
+        event_loop: &EventLoopWindowTarget,
+        callback: impl FnOnce(Result<CapturedFrame, ExternalError>) + 'static,
+    ) {
+        match (&event_loop.p, &self.inner) {
+            (
+                crate::platform_impl::EventLoopWindowTarget::Wayland(window_target),
+                crate::platform_impl::MonitorHandle::Wayland(monitor),
+            ) => window_target.capture_monitor(monitor, None, Box::new(callback)),
+            #[cfg(x11_platform)]
+            (crate::platform_impl::EventLoopWindowTarget::X(window_target), _) => window_target
+                .call_later(Box::new(move || {
+                    callback(Err(ExternalError::NotSupported(NotSupportedError::new())))
+                })),
+            #[cfg(x11_platform)]
+            _ => unreachable!("the monitors of the Wayland event loop are Wayland monitors"),
+        }
+    }
+
+    #[inline]
+    fn capture_frame_region(
+        &self,
+        event_loop: &EventLoopWindowTarget,
+        position: LogicalPosition<i32>,
+        size: LogicalSize<i32>,
+        callback: impl FnOnce(Result<CapturedFrame, ExternalError>) + 'static,
+    ) {
+        match (&event_loop.p, &self.inner) {
+            (
+                crate::platform_impl::EventLoopWindowTarget::Wayland(window_target),
+                crate::platform_impl::MonitorHandle::Wayland(monitor),
+            ) => {
+                let region = Some((position, size));
+                window_target.capture_monitor(monitor, region, Box::new(callback))
+            }
+            #[cfg(x11_platform)]
+            (crate::platform_impl::EventLoopWindowTarget::X(window_target), _) => window_target
+                .call_later(Box::new(move || {
+                    callback(Err(ExternalError::NotSupported(NotSupportedError::new())))
+                })),
+            #[cfg(x11_platform)]
+            _ => unreachable!("the monitors of the Wayland event loop are Wayland monitors"),
+        }
+    }
+}
+
+/// How the scale factor of a window on multiple monitors is picked, see
+/// [`WindowExtWayland::set_scale_preference`].
+///
+/// The scale changes are delivered with [`WindowEvent::ScaleFactorChanged`], like the ones from
+/// the compositor.
+///
+/// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum ScalePreference {
+    /// Use the scale preferred by the compositor.
+    ///
+    /// Only the compositor knows how much of the window is on each monitor, so it picks the
+    /// monitor with the most overlap when it supports `wp_fractional_scale_v1` or
+    /// `wl_compositor` v6. Otherwise, the highest scale of the monitors is used.
+    #[default]
+    Compositor,
+
+    /// Use the highest integer scale of the monitors the window is on, e.g. for crisp text.
+    Highest,
+
+    /// Use the lowest integer scale of the monitors the window is on, e.g. to draw fewer pixels.
+    Lowest,
+
+    /// Use the integer scale of the given monitor, whether the window is on it or not.
+    Monitor(MonitorHandle),
+}
+
+/// How the window is decorated, see [`WindowBuilderExtWayland::with_decorations_mode`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecorationsMode {
+    /// The decorations are drawn by the compositor, or by winit when the compositor doesn't,
+    /// following [`WindowBuilder::with_decorations`].
+    #[default]
+    Full,
+
+    /// The window is undecorated, but winit still resizes it when pressing on its edges, e.g. for
+    /// applications drawing their own title bar.
+    ///
+    /// The resize border is 8 logical pixels wide, thus it scales with the scale factor. It's
+    /// disabled while the window is maximized, fullscreen, tiled or not resizable, and when the
+    /// window is decorated again with [`Window::set_decorations`].
+    ResizeOnly,
+}
+
+/// The subpixel geometry of a monitor, as reported by `wl_output`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SubpixelLayout {
+    /// The layout is not known.
+    #[default]
+    Unknown,
+
+    /// The monitor has no subpixels.
+    None,
+
+    /// Horizontal subpixels in the RGB order.
+    HorizontalRGB,
+
+    /// Horizontal subpixels in the BGR order.
+    HorizontalBGR,
+
+    /// Vertical subpixels in the RGB order.
+    VerticalRGB,
+
+    /// Vertical subpixels in the BGR order.
+    VerticalBGR,
+}
+
+/// The power mode of a monitor, see [`MonitorHandleExtWayland::set_power`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PowerMode {
+    /// The monitor is on.
+    On,
+
+    /// The monitor is off, i.e. in power saving.
+    Off,
+}
+
+/// The error of [`MonitorHandleExtWayland::set_gamma_ramp`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GammaRampError {
+    /// The compositor doesn't support `zwlr_gamma_control_manager_v1`.
+    NotSupported,
+
+    /// The ramps don't have the expected number of entries, which is the size reported by the
+    /// compositor, or the size of the red ramp when the ramps differ.
+    InvalidSize { expected: usize },
+
+    /// The compositor refused the gamma control of the monitor, e.g. because another client
+    /// holds it or the monitor has no gamma ramps.
+    Failed,
+}
+
+impl std::fmt::Display for GammaRampError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotSupported => f.write_str("the gamma control isn't supported"),
+            Self::InvalidSize { expected } => {
+                write!(f, "the gamma ramps must have {expected} entries")
+            }
+            Self::Failed => f.write_str("the compositor refused the gamma control"),
+        }
+    }
+}
+
+impl std::error::Error for GammaRampError {}
+
+/// The contents of a monitor, see [`MonitorHandleExtWayland::capture_frame`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedFrame {
+    /// The size of the frame in pixels.
+    pub size: PhysicalSize<u32>,
+
+    /// The number of bytes between the starts of the rows, which may be more than the width
+    /// times the bytes per pixel.
+    pub stride: u32,
+
+    /// The format of the pixels, usually [`ShmFormat::Xrgb8888`] or [`ShmFormat::Argb8888`].
+    pub format: ShmFormat,
+
+    /// Whether the rows are from the bottom to the top.
+    pub y_invert: bool,
+
+    /// The pixels, with `stride` bytes for each row.
+    pub pixels: Vec<u8>,
+}
+
+/// The transform of the content relative to the monitor, as in `wl_output::transform`.
+///
+/// The rotations are counter-clockwise, and the flips are around the vertical axis and applied
+/// before rotating.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputTransform {
+    /// No transform.
+    #[default]
+    Normal,
+
+    /// Rotated by 90 degrees.
+    Rotate90,
+
+    /// Rotated by 180 degrees.
+    Rotate180,
+
+    /// Rotated by 270 degrees.
+    Rotate270,
+
+    /// Flipped.
+    Flipped,
+
+    /// Flipped and rotated by 90 degrees.
+    Flipped90,
+
+    /// Flipped and rotated by 180 degrees.
+    Flipped180,
+
+    /// Flipped and rotated by 270 degrees.
+    Flipped270,
+}
+
+/// The cause of an [`OsError`] on Wayland.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaylandError {
+    /// The compositor doesn't support the protocol with the given interface name, e.g.
+    /// `zwlr_layer_shell_v1` when building the window with
+    /// [`WindowBuilderExtWayland::with_layer_shell`].
+    MissingProtocol(&'static str),
+
+    /// The compositor reported a protocol error, or sent a malformed message.
+    ProtocolError,
+
+    /// The connection to the compositor couldn't be established or was closed.
+    ConnectionClosed,
+
+    /// Any other error, e.g. from the event loop.
+    Other,
+}
+
+/// Additional methods on [`OsError`] that are specific to Wayland.
+pub trait OsErrorExtWayland {
+    /// Returns the cause of the error, e.g. to fall back to a normal window when the compositor
+    /// doesn't support layer shell.
+    ///
+    /// Returns `None` when the error isn't coming from the Wayland backend.
+    fn wayland_error(&self) -> Option<WaylandError>;
+}
+
+impl OsErrorExtWayland for OsError {
+    fn wayland_error(&self) -> Option<WaylandError> {
+        match self.platform_error() {
+            crate::platform_impl::OsError::WaylandError(error) => Some(error.cause()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_wallpapers() {
+        let builder = WindowBuilder::new().as_wallpaper(None);
+        let wayland = &builder.window.platform_specific.wayland;
+        assert_eq!(wayland.layer_shell, Some(Layer::Background));
+        assert_eq!(wayland.anchor, Some(Anchor::all()));
+        assert_eq!(wayland.exclusive_zone, Some(0));
+        assert_eq!(
+            wayland.keyboard_interactivity,
+            Some(KeyboardInteractivity::None)
+        );
+        assert!(wayland.input_passthrough);
+        assert_eq!(wayland.output, None);
+    }
 }
@@ -404,9 +404,9 @@ impl<T: 'static> EventLoop<T> {
                                     motion_event.pointer_at_index(motion_event.pointer_index()),
                                 ))
                             }
-                            event::TouchPhase::Moved | event::TouchPhase::Cancelled => {
-                                Box::new(motion_event.pointers())
-                            }
+                            event::TouchPhase::Moved
+                            | event::TouchPhase::Cancelled
+                            | event::TouchPhase::Frame => Box::new(motion_event.pointers()),
                         };
 
                     for pointer in pointers {
@@ -35,7 +35,11 @@ use crate::{
 };
 
 #[cfg(wayland_platform)]
-use sctk::shell::wlr_layer::{Anchor, KeyboardInteractivity, Layer};
+use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::XdgToplevel;
+#[cfg(wayland_platform)]
+use sctk::shell::wlr_layer::{Anchor, KeyboardInteractivity, Layer, LayerSurface};
+#[cfg(wayland_platform)]
+use crate::platform::wayland::{DecorationsMode, PopupPositioner};
 pub(crate) use self::common::xkb::{physicalkey_to_scancode, scancode_to_physicalkey};
 pub(crate) use crate::cursor::OnlyCursorImageBuilder as PlatformCustomCursorBuilder;
 pub(crate) use crate::icon::RgbaIcon as PlatformIcon;
@@ -55,10 +59,22 @@ pub(crate) enum Backend {
     Wayland,
 }
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) forced_backend: Option<Backend>,
     pub(crate) any_thread: bool,
+    #[cfg(wayland_platform)]
+    pub(crate) raw_keyboard: bool,
+    #[cfg(wayland_platform)]
+    pub(crate) raw_configures: bool,
+    #[cfg(wayland_platform)]
+    pub(crate) tablets: bool,
+    #[cfg(wayland_platform)]
+    pub(crate) foreign_toplevels: bool,
+    #[cfg(wayland_platform)]
+    pub(crate) protocol_logging: bool,
+    #[cfg(wayland_platform)]
+    pub(crate) log_target: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -105,6 +121,36 @@ pub struct WaylandWindowBuilderAttributes {
     pub margin: Option<(i32, i32, i32, i32)>,
     pub keyboard_interactivity: Option<KeyboardInteractivity>,
     pub output: Option<String>,
+
+    /// The parent window to stack this window above.
+    pub parent: Option<XdgToplevel>,
+
+    /// The layer shell window to create this window as a popup of.
+    pub layer_popup: Option<(LayerSurface, PopupPositioner)>,
+
+    /// Whether to deliver at most one redraw per frame callback.
+    pub redraw_throttle: bool,
+
+    /// Whether the window is a tooltip, which never takes the keyboard focus.
+    pub tooltip: bool,
+
+    /// The integer scale to use instead of the one preferred by the compositor.
+    pub preferred_integer_scale: Option<i32>,
+
+    /// How the window is decorated.
+    pub decorations_mode: DecorationsMode,
+
+    /// Whether the pointer and touch input passes through the window.
+    pub input_passthrough: bool,
+
+    /// The namespace of the layer shell window.
+    pub layer_namespace: Option<String>,
+
+    /// The minimum of the initial size clamped to the bounds suggested by the compositor.
+    pub initial_min_size: Option<Size>,
+
+    /// Whether the window only receives input, without being drawn.
+    pub input_only: bool,
 }
 
 impl Default for PlatformSpecificWindowBuilderAttributes {
@@ -129,6 +175,16 @@ impl Default for PlatformSpecificWindowBuilderAttributes {
                 margin: None,
                 keyboard_interactivity: None,
                 output: None,
+                parent: None,
+                layer_popup: None,
+                redraw_throttle: false,
+                tooltip: false,
+                preferred_integer_scale: None,
+                decorations_mode: DecorationsMode::Full,
+                input_passthrough: false,
+                layer_namespace: None,
+                initial_min_size: None,
+                input_only: false,
             },
         }
     }
@@ -769,6 +825,12 @@ impl<T: 'static> EventLoop<T> {
             );
         }
 
+        // The target also applies to the Wayland extensions used with the X11 windows.
+        #[cfg(wayland_platform)]
+        {
+            *wayland::LOG_TARGET.write().unwrap() = attributes.log_target.clone();
+        }
+
         // NOTE: Wayland first because of X11 could be present under Wayland as well. Empty
         // variables are also treated as not set.
         let backend = match (
@@ -807,15 +869,17 @@ impl<T: 'static> EventLoop<T> {
         // Create the display based on the backend.
         match backend {
             #[cfg(wayland_platform)]
-            Backend::Wayland => EventLoop::new_wayland_any_thread().map_err(Into::into),
+            Backend::Wayland => EventLoop::new_wayland_any_thread(attributes).map_err(Into::into),
             #[cfg(x11_platform)]
             Backend::X => EventLoop::new_x11_any_thread().map_err(Into::into),
         }
     }
 
     #[cfg(wayland_platform)]
-    fn new_wayland_any_thread() -> Result<EventLoop<T>, EventLoopError> {
-        wayland::EventLoop::new().map(|evlp| EventLoop::Wayland(Box::new(evlp)))
+    fn new_wayland_any_thread(
+        attributes: &PlatformSpecificEventLoopAttributes,
+    ) -> Result<EventLoop<T>, EventLoopError> {
+        wayland::EventLoop::new(attributes).map(|evlp| EventLoop::Wayland(Box::new(evlp)))
     }
 
     #[cfg(x11_platform)]
@@ -1,19 +1,21 @@
 //! The event-loop routines.
 
 use std::cell::{Cell, RefCell};
-use std::io::Result as IOResult;
+use std::io::{ErrorKind, Result as IOResult};
 use std::marker::PhantomData;
 use std::mem;
-use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, OwnedFd, RawFd};
 use std::rc::Rc;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+use sctk::reexports::calloop::timer::{TimeoutAction, Timer};
 use sctk::reexports::calloop::Error as CalloopError;
 use sctk::reexports::calloop_wayland_source::WaylandSource;
+use sctk::reexports::client::backend::WaylandError as WaylandBackendError;
 use sctk::reexports::client::globals;
-use sctk::reexports::client::{Connection, QueueHandle};
+use sctk::reexports::client::{Connection, Proxy, QueueHandle};
 
 use crate::dpi::LogicalSize;
 use crate::error::{EventLoopError, OsError as RootOsError};
@@ -21,9 +23,14 @@ use crate::event::{Event, InnerSizeWriter, StartCause, WindowEvent};
 use crate::event_loop::{
     ControlFlow, DeviceEvents, EventLoopWindowTarget as RootEventLoopWindowTarget,
 };
+use crate::keyboard::ModifiersState;
 use crate::platform::pump_events::PumpStatus;
+use crate::platform::wayland::{ForeignToplevel, ManagedToplevel, SeatHandle};
 use crate::platform_impl::platform::min_timeout;
-use crate::platform_impl::{EventLoopWindowTarget as PlatformEventLoopWindowTarget, OsError};
+use crate::platform_impl::{
+    EventLoopWindowTarget as PlatformEventLoopWindowTarget, OsError,
+    PlatformSpecificEventLoopAttributes,
+};
 
 mod proxy;
 pub mod sink;
@@ -31,9 +38,12 @@ pub mod sink;
 pub use proxy::EventLoopProxy;
 use sink::EventSink;
 
+use super::seat::{read_clipboard, text_mime_type, text_offers, ClipboardCallback};
 use super::state::{WindowCompositorUpdate, WinitState};
 use super::window::state::FrameCallbackState;
-use super::{logical_to_physical_rounded, DeviceId, WaylandError, WindowId};
+use super::{
+    error, logical_to_physical_rounded, warn, BareSurface, DeviceId, WaylandError, WindowId,
+};
 
 type WaylandDispatcher = calloop::Dispatcher<'static, WaylandSource<WinitState>, WinitState>;
 
@@ -70,13 +80,18 @@ pub struct EventLoop<T: 'static> {
 }
 
 impl<T: 'static> EventLoop<T> {
-    pub fn new() -> Result<EventLoop<T>, EventLoopError> {
+    pub fn new(
+        attributes: &PlatformSpecificEventLoopAttributes,
+    ) -> Result<EventLoop<T>, EventLoopError> {
         macro_rules! map_err {
             ($e:expr, $err:expr) => {
                 $e.map_err(|error| os_error!($err(error).into()))
             };
         }
 
+        // Trace from the very first messages with the compositor.
+        super::PROTOCOL_LOGGING.store(attributes.protocol_logging, Ordering::Relaxed);
+
         let connection = map_err!(Connection::connect_to_env(), WaylandError::Connection)?;
 
         let (globals, mut event_queue) = map_err!(
@@ -90,8 +105,16 @@ impl<T: 'static> EventLoop<T> {
             WaylandError::Calloop
         )?;
 
-        let mut winit_state = WinitState::new(&globals, &queue_handle, event_loop.handle())
-            .map_err(|error| os_error!(error))?;
+        let mut winit_state = WinitState::new(
+            &globals,
+            &queue_handle,
+            event_loop.handle(),
+            attributes.tablets,
+            attributes.foreign_toplevels,
+        )
+        .map_err(|error| os_error!(error))?;
+        winit_state.raw_keyboard = attributes.raw_keyboard;
+        winit_state.raw_configures = attributes.raw_configures;
 
         // NOTE: do a roundtrip after binding the globals to prevent potential
         // races with the server.
@@ -165,6 +188,7 @@ impl<T: 'static> EventLoop<T> {
             queue_handle,
             control_flow: Cell::new(ControlFlow::default()),
             exit: Cell::new(None),
+            event_timestamp: Cell::new(None),
             state: RefCell::new(winit_state),
         };
 
@@ -265,19 +289,30 @@ impl<T: 'static> EventLoop<T> {
             //
             // Checking for flush error is essential to perform an exit with error, since
             // once we have a protocol error, we could get stuck retrying...
-            if self.connection.flush().is_err() {
-                self.set_exit_code(1);
-                return;
+            match self.connection.flush() {
+                Ok(()) => (),
+                // The socket buffer is full, the data will be flushed on the next iteration.
+                Err(WaylandBackendError::Io(error)) if error.kind() == ErrorKind::WouldBlock => (),
+                Err(error) => {
+                    error!("Error flushing the Wayland connection: {error}");
+                    let exit_code = match error {
+                        WaylandBackendError::Io(error) => error.raw_os_error().unwrap_or(1),
+                        WaylandBackendError::Protocol(_) => 1,
+                    };
+                    self.connection_lost(&mut callback);
+                    self.set_exit_code(exit_code);
+                    return;
+                }
             }
 
             if let Err(error) = self.loop_dispatch(timeout) {
                 // NOTE We exit on errors from dispatches, since if we've got protocol error
                 // libwayland-client/wayland-rs will inform us anyway, but crashing downstream is not
-                // really an option. Instead we inform that the event loop got destroyed. We may
-                // communicate an error that something was terminated, but winit doesn't provide us
-                // with an API to do that via some event.
+                // really an option. Instead we inform that the windows got destroyed and that the
+                // event loop is exiting.
                 // Still, we set the exit code to the error's OS error code, or to 1 if not possible.
                 let exit_code = error.raw_os_error().unwrap_or(1);
+                self.connection_lost(&mut callback);
                 self.set_exit_code(exit_code);
                 return;
             }
@@ -345,8 +380,24 @@ impl<T: 'static> EventLoop<T> {
             callback(Event::UserEvent(user_event), &self.window_target);
         }
 
+        // Call back the clipboard reads completed on the event loop.
+        let clipboard_reads = self.with_state(|state| std::mem::take(&mut state.clipboard_reads));
+        for (callback, data) in clipboard_reads {
+            callback(data);
+        }
+
+        // Call back the monitor captures completed on the event loop.
+        let captured_frames = self.with_state(|state| std::mem::take(&mut state.captured_frames));
+        for (callback, result) in captured_frames {
+            callback(result);
+        }
+
         // Drain the pending compositor updates.
-        self.with_state(|state| compositor_updates.append(&mut state.window_compositor_updates));
+        self.with_state(|state| {
+            // The window could have moved to the monitors with other scales.
+            state.apply_scale_preferences();
+            compositor_updates.append(&mut state.window_compositor_updates)
+        });
 
         for mut compositor_update in compositor_updates.drain(..) {
             let window_id = compositor_update.window_id;
@@ -395,6 +446,28 @@ impl<T: 'static> EventLoop<T> {
                 }
             }
 
+            // Deliver the sizes of the configures coalesced into the update first.
+            if !compositor_update.coalesced_sizes.is_empty() {
+                let scale_factor = self.with_state(|state| {
+                    let windows = state.windows.get_mut();
+                    let window = windows.get(&window_id).unwrap().lock().unwrap();
+                    window.scale_factor()
+                });
+
+                for size in compositor_update.coalesced_sizes.drain(..) {
+                    callback(
+                        Event::WindowEvent {
+                            window_id: crate::window::WindowId(window_id),
+                            event: WindowEvent::Resized(logical_to_physical_rounded(
+                                size,
+                                scale_factor,
+                            )),
+                        },
+                        &self.window_target,
+                    );
+                }
+            }
+
             // NOTE: Rescale changed the physical size which winit operates in, thus we should
             // resize.
             if compositor_update.resized || compositor_update.scale_changed {
@@ -435,28 +508,47 @@ impl<T: 'static> EventLoop<T> {
                     &self.window_target,
                 );
             }
+
+            if compositor_update.surface_destroyed {
+                callback(
+                    Event::WindowEvent {
+                        window_id: crate::window::WindowId(window_id),
+                        event: WindowEvent::SurfaceDestroyed,
+                    },
+                    &self.window_target,
+                );
+            }
         }
 
         // Push the events directly from the window.
         self.with_state(|state| {
             buffer_sink.append(&mut state.window_events_sink.lock().unwrap());
         });
-        for event in buffer_sink.drain() {
+        for (event, timestamp) in buffer_sink.drain() {
             let event = event.map_nonuser_event().unwrap();
+            self.set_event_timestamp(timestamp);
             callback(event, &self.window_target);
         }
+        self.set_event_timestamp(None);
 
         // Handle non-synthetic events.
         self.with_state(|state| {
             buffer_sink.append(&mut state.events_sink);
         });
-        for event in buffer_sink.drain() {
+        for (event, timestamp) in buffer_sink.drain() {
             let event = event.map_nonuser_event().unwrap();
+            self.set_event_timestamp(timestamp);
             callback(event, &self.window_target);
         }
+        self.set_event_timestamp(None);
 
         // Collect the window ids
         self.with_state(|state| {
+            // Forget the bare surfaces which were dropped by the user.
+            state
+                .bare_surfaces
+                .retain(|_, frame_done| frame_done.strong_count() != 0);
+
             window_ids.extend(state.window_requests.get_mut().keys());
         });
 
@@ -477,7 +569,38 @@ impl<T: 'static> EventLoop<T> {
                     .lock()
                     .unwrap();
 
-                if window.frame_callback_state() == FrameCallbackState::Requested {
+                window.create_dmabuf_feedback(&state.dmabuf_state);
+
+                // The window can't be drawn before it's configured.
+                if !window.is_configured()
+                    || window.frame_callback_state() == FrameCallbackState::Requested
+                {
+                    return None;
+                }
+
+                // Hold the redraw until the frame interval of the frame rate cap elapsed.
+                let now = Instant::now();
+                if let Some(deadline) = window.frame_rate_deadline(now) {
+                    let redraw_requested = window_requests
+                        .get(window_id)
+                        .unwrap()
+                        .redraw_requested
+                        .load(Ordering::Relaxed);
+                    if redraw_requested && window.frame_rate_timer.is_none() {
+                        let window_id = *window_id;
+                        window.frame_rate_timer = state
+                            .loop_handle
+                            .insert_source(Timer::from_deadline(deadline), move |_, _, state| {
+                                // Required to handle the wakeups from the timer.
+                                state.dispatched_events = true;
+                                if let Some(window) = state.windows.get_mut().get(&window_id) {
+                                    window.lock().unwrap().frame_rate_timer = None;
+                                }
+                                TimeoutAction::Drop
+                            })
+                            .ok();
+                    }
+
                     return None;
                 }
 
@@ -491,6 +614,15 @@ impl<T: 'static> EventLoop<T> {
                 // Redraw the frame while at it.
                 redraw_requested |= window.refresh_frame();
 
+                // Hold the next redraw until the compositor wants a new frame.
+                if redraw_requested && window.redraw_throttle {
+                    window.request_frame_callback();
+                }
+
+                if redraw_requested {
+                    window.redraw_delivered(now);
+                }
+
                 redraw_requested.then_some(WindowEvent::RedrawRequested)
             });
 
@@ -554,9 +686,46 @@ impl<T: 'static> EventLoop<T> {
         std::mem::swap(&mut self.window_ids, &mut window_ids);
     }
 
+    /// Notify the user that all the windows are gone, since the connection to the compositor
+    /// was lost and nothing could be done with them anymore.
+    fn connection_lost<F>(&mut self, callback: &mut F)
+    where
+        F: FnMut(Event<T>, &RootEventLoopWindowTarget),
+    {
+        let window_ids: Vec<WindowId> = self.with_state(|state| {
+            state.windows.get_mut().clear();
+            state
+                .window_requests
+                .get_mut()
+                .drain()
+                .map(|(window_id, _)| window_id)
+                .collect()
+        });
+
+        for window_id in window_ids {
+            callback(
+                Event::WindowEvent {
+                    window_id: crate::window::WindowId(window_id),
+                    event: WindowEvent::Destroyed,
+                },
+                &self.window_target,
+            );
+        }
+    }
+
     #[inline]
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
-        EventLoopProxy::new(self.user_events_sender.clone())
+        let window_target = match &self.window_target.p {
+            PlatformEventLoopWindowTarget::Wayland(window_target) => window_target,
+            #[cfg(x11_platform)]
+            _ => unreachable!(),
+        };
+
+        EventLoopProxy::new(
+            self.user_events_sender.clone(),
+            window_target.state.borrow().window_events_sink.clone(),
+            window_target.event_loop_awakener.clone(),
+        )
     }
 
     #[inline]
@@ -582,7 +751,7 @@ impl<T: 'static> EventLoop<T> {
         };
 
         self.event_loop.dispatch(timeout, state).map_err(|error| {
-            log::error!("Error dispatching event loop: {}", error);
+            error!("Error dispatching event loop: {}", error);
             error.into()
         })
     }
@@ -603,6 +772,16 @@ impl<T: 'static> EventLoop<T> {
         })
     }
 
+    fn set_event_timestamp(&self, timestamp: Option<u32>) {
+        match &self.window_target.p {
+            PlatformEventLoopWindowTarget::Wayland(window_target) => {
+                window_target.event_timestamp.set(timestamp)
+            }
+            #[cfg(x11_platform)]
+            _ => unreachable!(),
+        }
+    }
+
     fn control_flow(&self) -> ControlFlow {
         self.window_target.p.control_flow()
     }
@@ -645,6 +824,9 @@ pub struct EventLoopWindowTarget {
     /// The application's exit state.
     pub(crate) exit: Cell<Option<i32>>,
 
+    /// The timestamp of the input event which caused the event being dispatched.
+    pub(crate) event_timestamp: Cell<Option<u32>>,
+
     // TODO remove that RefCell once we can pass `&mut` in `Window::new`.
     /// Winit state.
     pub state: RefCell<WinitState>,
@@ -657,6 +839,237 @@ pub struct EventLoopWindowTarget {
 }
 
 impl EventLoopWindowTarget {
+    #[inline]
+    pub fn event_timestamp(&self) -> Option<u32> {
+        self.event_timestamp.get()
+    }
+
+    #[inline]
+    pub fn seats(&self) -> Vec<SeatHandle> {
+        let state = self.state.borrow();
+        state
+            .seat_state
+            .seats()
+            .map(|seat| SeatHandle {
+                id: seat.id().protocol_id(),
+                name: state.seat_state.info(&seat).and_then(|info| info.name),
+            })
+            .collect()
+    }
+
+    #[inline]
+    pub fn xkb_keymap(&self) -> Option<Vec<u8>> {
+        self.state
+            .borrow()
+            .seats
+            .values()
+            .find_map(|seat| seat.xkb_keymap())
+            .map(ToOwned::to_owned)
+    }
+
+    pub fn keyboard_keymap_fd(&self) -> Option<(OwnedFd, u32)> {
+        let state = self.state.borrow();
+        let (fd, size) = state.seats.values().find_map(|seat| seat.xkb_keymap_fd())?;
+        match fd.try_clone() {
+            Ok(fd) => Some((fd, size)),
+            Err(err) => {
+                warn!("failed to duplicate the keymap fd: {err}");
+                None
+            }
+        }
+    }
+
+    #[inline]
+    pub fn modifiers(&self) -> ModifiersState {
+        self.state
+            .borrow()
+            .seats
+            .values()
+            .find_map(|seat| seat.focused_modifiers())
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn any_window_focused(&self) -> bool {
+        self.state
+            .borrow()
+            .windows
+            .borrow()
+            .values()
+            .any(|window| window.lock().unwrap().has_focus())
+    }
+
+    #[inline]
+    pub fn create_surface(&self) -> BareSurface {
+        BareSurface::new(
+            &mut self.state.borrow_mut(),
+            &self.queue_handle,
+            self.event_loop_awakener.clone(),
+        )
+    }
+
+    #[inline]
+    pub fn set_default_layer_namespace(&self, namespace: String) {
+        self.state.borrow_mut().default_layer_namespace = Some(namespace);
+    }
+
+    pub fn read_clipboard(&self) -> Option<String> {
+        let mime_type = text_mime_type(&self.clipboard_mime_types())?;
+        let data = self.read_clipboard_mime(mime_type)?;
+        match String::from_utf8(data) {
+            Ok(text) => Some(text),
+            Err(err) => {
+                warn!("The text of the clipboard isn't UTF-8: {err}");
+                None
+            }
+        }
+    }
+
+    #[inline]
+    pub fn write_clipboard(&self, text: String) {
+        self.write_clipboard_mime(text_offers(text));
+    }
+
+    pub fn read_clipboard_mime(&self, mime_type: &str) -> Option<Vec<u8>> {
+        let pipe = {
+            let state = self.state.borrow();
+            if let Some(offers) = state.own_clipboard() {
+                return offers
+                    .iter()
+                    .find(|(offered, _)| offered == mime_type)
+                    .map(|(_, data)| data.clone());
+            }
+
+            state.receive_clipboard(mime_type)?
+        };
+
+        // Send the request to the compositor before waiting for the owner of the selection.
+        if let Err(error) = self.connection.flush() {
+            warn!("Error flushing the Wayland connection: {error}");
+            return None;
+        }
+
+        read_clipboard(pipe)
+    }
+
+    pub fn read_clipboard_async(&self, mime_type: &str, callback: ClipboardCallback) {
+        let state = &mut *self.state.borrow_mut();
+        if let Some(offers) = state.own_clipboard() {
+            let data = offers
+                .iter()
+                .find(|(offered, _)| offered == mime_type)
+                .map(|(_, data)| data.clone());
+            state.clipboard_reads.push((callback, data));
+        } else if let Some(pipe) = state.receive_clipboard(mime_type) {
+            state.read_clipboard_async(pipe, callback);
+        } else {
+            state.clipboard_reads.push((callback, None));
+        }
+
+        // Call back on the next iteration when the read is already done.
+        if !state.clipboard_reads.is_empty() {
+            self.event_loop_awakener.ping();
+        }
+    }
+
+    #[inline]
+    pub fn write_clipboard_mime(&self, offers: Vec<(String, Vec<u8>)>) {
+        self.state
+            .borrow_mut()
+            .set_clipboard(offers, &self.queue_handle);
+    }
+
+    #[inline]
+    pub fn clipboard_mime_types(&self) -> Vec<String> {
+        self.state.borrow().clipboard_mime_types()
+    }
+
+    pub fn set_idle_notification(&self, timeout: Option<Duration>) {
+        let state = &mut *self.state.borrow_mut();
+        let Some(idle_notifier) = state.idle_notifier.as_mut() else {
+            return;
+        };
+
+        let seats = state.seat_state.seats();
+        if let Some(event) = idle_notifier.set_timeout(timeout, seats, &self.queue_handle) {
+            state.events_sink.push_event(event);
+            self.event_loop_awakener.ping();
+        }
+    }
+
+    #[inline]
+    pub fn outputs_ready(&self) -> bool {
+        self.state.borrow().outputs_ready()
+    }
+
+    #[inline]
+    pub fn protocol_version(&self, interface: &str) -> Option<u32> {
+        self.state.borrow().protocol_version(interface)
+    }
+
+    pub fn available_globals(&self) -> Vec<(String, u32)> {
+        self.state
+            .borrow()
+            .registry_state
+            .globals()
+            .map(|global| (global.interface.clone(), global.version))
+            .collect()
+    }
+
+    pub fn roundtrip(&self) -> Duration {
+        let mut state = self.state.borrow_mut();
+        let mut wayland_source = self.wayland_dispatcher.as_source_mut();
+        let event_queue = wayland_source.queue();
+
+        let start = Instant::now();
+        if let Err(error) = event_queue.roundtrip(&mut state) {
+            error!("Error during the roundtrip with the compositor: {error}");
+        }
+        let elapsed = start.elapsed();
+
+        // Deliver the events dispatched during the roundtrip.
+        self.event_loop_awakener.ping();
+
+        elapsed
+    }
+
+    pub fn foreign_toplevels(&self) -> Vec<ForeignToplevel> {
+        self.state
+            .borrow()
+            .foreign_toplevel_list
+            .as_ref()
+            .map(|foreign_toplevel_list| foreign_toplevel_list.toplevels())
+            .unwrap_or_default()
+    }
+
+    pub fn managed_toplevels(&self) -> Vec<ManagedToplevel> {
+        self.state
+            .borrow()
+            .foreign_toplevel_manager
+            .as_ref()
+            .map(|foreign_toplevel_manager| foreign_toplevel_manager.toplevels())
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn activate_managed_toplevel(&self, toplevel: &ManagedToplevel) {
+        self.state.borrow().activate_managed_toplevel(toplevel);
+    }
+
+    #[inline]
+    pub fn minimize_managed_toplevel(&self, toplevel: &ManagedToplevel) {
+        self.state.borrow().minimize_managed_toplevel(toplevel);
+    }
+
+    pub fn flush(&self) {
+        match self.connection.flush() {
+            Ok(()) => (),
+            // The event loop flushes the rest once the socket is writable.
+            Err(WaylandBackendError::Io(error)) if error.kind() == ErrorKind::WouldBlock => (),
+            Err(error) => warn!("Error flushing the Wayland connection: {error}"),
+        }
+    }
+
     pub(crate) fn set_control_flow(&self, control_flow: ControlFlow) {
         self.control_flow.set(control_flow)
     }
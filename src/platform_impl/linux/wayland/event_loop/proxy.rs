@@ -1,27 +1,49 @@
 //! An event loop proxy.
 
 use std::sync::mpsc::SendError;
+use std::sync::{Arc, Mutex};
 
 use sctk::reexports::calloop::channel::Sender;
+use sctk::reexports::calloop::ping::Ping;
 
+use crate::event::WindowEvent;
 use crate::event_loop::EventLoopClosed;
 
+use super::sink::EventSink;
+use super::WindowId;
+
 /// A handle that can be sent across the threads and used to wake up the `EventLoop`.
 pub struct EventLoopProxy<T: 'static> {
     user_events_sender: Sender<T>,
+
+    /// The event sink to deliver synthetic window events.
+    window_events_sink: Arc<Mutex<EventSink>>,
+
+    /// Source to wake-up the event-loop for synthetic window events.
+    event_loop_awakener: Ping,
 }
 
 impl<T: 'static> Clone for EventLoopProxy<T> {
     fn clone(&self) -> Self {
         EventLoopProxy {
             user_events_sender: self.user_events_sender.clone(),
+            window_events_sink: self.window_events_sink.clone(),
+            event_loop_awakener: self.event_loop_awakener.clone(),
         }
     }
 }
 
 impl<T: 'static> EventLoopProxy<T> {
-    pub fn new(user_events_sender: Sender<T>) -> Self {
-        Self { user_events_sender }
+    pub fn new(
+        user_events_sender: Sender<T>,
+        window_events_sink: Arc<Mutex<EventSink>>,
+        event_loop_awakener: Ping,
+    ) -> Self {
+        Self {
+            user_events_sender,
+            window_events_sink,
+            event_loop_awakener,
+        }
     }
 
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
@@ -29,4 +51,12 @@ impl<T: 'static> EventLoopProxy<T> {
             .send(event)
             .map_err(|SendError(error)| EventLoopClosed(error))
     }
+
+    pub fn send_window_event(&self, window_id: WindowId, event: WindowEvent) {
+        self.window_events_sink
+            .lock()
+            .unwrap()
+            .push_window_event(event, window_id);
+        self.event_loop_awakener.ping();
+    }
 }
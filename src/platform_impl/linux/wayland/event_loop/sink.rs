@@ -12,7 +12,8 @@ use super::{DeviceId, WindowId};
 /// to the winit's user.
 #[derive(Default)]
 pub struct EventSink {
-    pub window_events: Vec<Event<()>>,
+    /// The events along with the timestamps of the input events that caused them.
+    pub window_events: Vec<(Event<()>, Option<u32>)>,
 }
 
 impl EventSink {
@@ -26,22 +27,40 @@ impl EventSink {
         self.window_events.is_empty()
     }
 
+    /// Add new event, which isn't for a window or a device, to a queue.
+    #[inline]
+    pub fn push_event(&mut self, event: Event<()>) {
+        self.window_events.push((event, None));
+    }
+
     /// Add new device event to a queue.
     #[inline]
     pub fn push_device_event(&mut self, event: DeviceEvent, device_id: DeviceId) {
-        self.window_events.push(Event::DeviceEvent {
+        let event = Event::DeviceEvent {
             event,
             device_id: RootDeviceId(PlatformDeviceId::Wayland(device_id)),
-        });
+        };
+        self.window_events.push((event, None));
     }
 
     /// Add new window event to a queue.
     #[inline]
     pub fn push_window_event(&mut self, event: WindowEvent, window_id: WindowId) {
-        self.window_events.push(Event::WindowEvent {
+        let event = Event::WindowEvent {
+            event,
+            window_id: RootWindowId(window_id),
+        };
+        self.window_events.push((event, None));
+    }
+
+    /// Add new window event caused by the input event with the given timestamp to a queue.
+    #[inline]
+    pub fn push_timed_window_event(&mut self, event: WindowEvent, window_id: WindowId, time: u32) {
+        let event = Event::WindowEvent {
             event,
             window_id: RootWindowId(window_id),
-        });
+        };
+        self.window_events.push((event, Some(time)));
     }
 
     #[inline]
@@ -50,7 +69,7 @@ impl EventSink {
     }
 
     #[inline]
-    pub fn drain(&mut self) -> Drain<'_, Event<()>> {
+    pub fn drain(&mut self) -> Drain<'_, (Event<()>, Option<u32>)> {
         self.window_events.drain(..)
     }
 }
@@ -3,9 +3,13 @@
 //! Winit's Wayland backend.
 
 use std::fmt::Display;
-use std::sync::Arc;
+use std::fs::File;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
 
 use sctk::reexports::client::globals::{BindError, GlobalError};
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{self, ConnectError, DispatchError, Proxy};
 
@@ -14,12 +18,68 @@ use crate::dpi::{LogicalSize, PhysicalSize};
 pub use crate::platform_impl::platform::{OsError, WindowId};
 pub use event_loop::{EventLoop, EventLoopProxy, EventLoopWindowTarget};
 pub use output::{MonitorHandle, VideoModeHandle};
+pub use surface::BareSurface;
 pub use window::Window;
 
+/// Whether the messages exchanged with the compositor are traced, see
+/// `EventLoopBuilderExtWayland::with_protocol_logging`.
+static PROTOCOL_LOGGING: AtomicBool = AtomicBool::new(false);
+
+/// Trace a message exchanged with the compositor in the `WAYLAND_DEBUG` format, when the protocol
+/// logging is enabled.
+macro_rules! trace_protocol {
+    ($($arg:tt)+) => {
+        if $crate::platform_impl::wayland::protocol_logging() {
+            log::trace!(target: "winit::wayland::protocol", $($arg)+);
+        }
+    };
+}
+
+/// The target of the logs set for the event loop, see
+/// `EventLoopBuilderExtWayland::with_log_target`, logging under the module paths when it's
+/// `None`.
+pub(crate) static LOG_TARGET: RwLock<Option<String>> = RwLock::new(None);
+
+/// Log under the target set for the event loop, see [`LOG_TARGET`].
+macro_rules! log_to_target {
+    ($level:expr, $($arg:tt)+) => {
+        match $crate::platform_impl::wayland::LOG_TARGET.read().unwrap().as_deref() {
+            Some(target) => log::log!(target: target, $level, $($arg)+),
+            None => log::log!($level, $($arg)+),
+        }
+    };
+}
+
+/// Like `log::error!`, under the target set for the event loop.
+macro_rules! log_error {
+    ($($arg:tt)+) => {
+        $crate::platform_impl::wayland::log_to_target!(log::Level::Error, $($arg)+)
+    };
+}
+
+/// Like `log::warn!`, under the target set for the event loop.
+macro_rules! log_warn {
+    ($($arg:tt)+) => {
+        $crate::platform_impl::wayland::log_to_target!(log::Level::Warn, $($arg)+)
+    };
+}
+
+/// Like `log::info!`, under the target set for the event loop.
+macro_rules! log_info {
+    ($($arg:tt)+) => {
+        $crate::platform_impl::wayland::log_to_target!(log::Level::Info, $($arg)+)
+    };
+}
+
+// NOTE: the macros are used with the names of the `log` ones, which `warn` can't be defined as,
+// since it's a builtin attribute.
+pub(crate) use {log_error as error, log_info as info, log_to_target, log_warn as warn};
+
 mod event_loop;
 mod output;
 mod seat;
 mod state;
+mod surface;
 mod types;
 mod window;
 
@@ -31,8 +91,11 @@ pub enum WaylandError {
     /// Error binding the global.
     Global(GlobalError),
 
-    // Bind error.
-    Bind(BindError),
+    // Bind error, with the interface name of the global.
+    Bind(&'static str, BindError),
+
+    /// The optional global required for the operation is missing.
+    MissingProtocol(&'static str),
 
     /// Error during the dispatching the event queue.
     Dispatch(DispatchError),
@@ -49,7 +112,10 @@ impl Display for WaylandError {
         match self {
             WaylandError::Connection(error) => error.fmt(f),
             WaylandError::Global(error) => error.fmt(f),
-            WaylandError::Bind(error) => error.fmt(f),
+            WaylandError::Bind(interface, error) => write!(f, "{interface}: {error}"),
+            WaylandError::MissingProtocol(interface) => {
+                write!(f, "the compositor doesn't support {interface}")
+            }
             WaylandError::Dispatch(error) => error.fmt(f),
             WaylandError::Calloop(error) => error.fmt(f),
             WaylandError::Wire(error) => error.fmt(f),
@@ -57,22 +123,71 @@ impl Display for WaylandError {
     }
 }
 
+impl WaylandError {
+    /// The cause of the error exposed to the users.
+    pub fn cause(&self) -> crate::platform::wayland::WaylandError {
+        use crate::platform::wayland::WaylandError as Cause;
+
+        fn wire_cause(error: &client::backend::WaylandError) -> Cause {
+            match error {
+                client::backend::WaylandError::Io(_) => Cause::ConnectionClosed,
+                client::backend::WaylandError::Protocol(_) => Cause::ProtocolError,
+            }
+        }
+
+        match self {
+            WaylandError::Connection(_) => Cause::ConnectionClosed,
+            WaylandError::Global(GlobalError::Backend(error)) => wire_cause(error),
+            WaylandError::Global(GlobalError::InvalidId(_)) => Cause::ProtocolError,
+            WaylandError::Bind(interface, _) | WaylandError::MissingProtocol(interface) => {
+                Cause::MissingProtocol(interface)
+            }
+            WaylandError::Dispatch(DispatchError::Backend(error)) => wire_cause(error),
+            WaylandError::Dispatch(DispatchError::BadMessage { .. }) => Cause::ProtocolError,
+            WaylandError::Calloop(_) => Cause::Other,
+            WaylandError::Wire(error) => wire_cause(error),
+        }
+    }
+}
+
 impl From<WaylandError> for OsError {
     fn from(value: WaylandError) -> Self {
         Self::WaylandError(Arc::new(value))
     }
 }
 
-/// Dummy device id, since Wayland doesn't have device events.
+/// The device id, which identifies the seat of the device, since Wayland doesn't expose
+/// individual devices.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct DeviceId;
+pub struct DeviceId {
+    /// The protocol id of the `wl_seat`.
+    seat_id: u32,
+}
 
 impl DeviceId {
     pub const unsafe fn dummy() -> Self {
-        DeviceId
+        DeviceId { seat_id: 0 }
+    }
+
+    #[inline]
+    pub(crate) fn from_seat(seat: &WlSeat) -> Self {
+        Self {
+            seat_id: seat.id().protocol_id(),
+        }
+    }
+
+    #[inline]
+    pub fn seat_id(&self) -> u32 {
+        self.seat_id
     }
 }
 
+/// Whether the protocol logging is enabled.
+#[inline]
+fn protocol_logging() -> bool {
+    PROTOCOL_LOGGING.load(Ordering::Relaxed)
+}
+
 /// Get the WindowId out of the surface.
 #[inline]
 fn make_wid(surface: &WlSurface) -> WindowId {
@@ -85,3 +200,22 @@ fn logical_to_physical_rounded(size: LogicalSize<u32>, scale_factor: f64) -> Phy
     let height = size.height as f64 * scale_factor;
     (width.round(), height.round()).into()
 }
+
+/// Create an anonymous file to share memory with the compositor.
+fn create_anonymous_file() -> io::Result<File> {
+    #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+    let fd = rustix::fs::memfd_create("winit", rustix::fs::MemfdFlags::CLOEXEC)?;
+
+    #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+    let fd = {
+        use rustix::shm::{Mode, ShmOFlags};
+
+        let name = format!("/winit-{}", std::process::id());
+        let flags = ShmOFlags::CREATE | ShmOFlags::EXCL | ShmOFlags::RDWR;
+        let fd = rustix::shm::shm_open(name.as_str(), flags, Mode::RUSR | Mode::WUSR)?;
+        rustix::shm::shm_unlink(name.as_str())?;
+        fd
+    };
+
+    Ok(File::from(fd))
+}
@@ -1,21 +1,28 @@
-use sctk::reexports::client::protocol::wl_output::WlOutput;
+use sctk::reexports::client::protocol::wl_output::{Subpixel, WlOutput};
 use sctk::reexports::client::Proxy;
 
 use sctk::output::OutputData;
 
-use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
+use crate::error::{ExternalError, NotSupportedError};
+use crate::platform::wayland::{GammaRampError, PowerMode, SubpixelLayout};
 use crate::platform_impl::platform::VideoModeHandle as PlatformVideoModeHandle;
+use crate::platform_impl::wayland::error;
 
 use super::event_loop::EventLoopWindowTarget;
+use super::types::wlr_screencopy::CaptureCallback;
 
 impl EventLoopWindowTarget {
     #[inline]
     pub fn available_monitors(&self) -> impl Iterator<Item = MonitorHandle> {
+        // NOTE: only the monitors with the complete info from the compositor are tracked.
         self.state
             .borrow()
-            .output_state
-            .outputs()
-            .map(MonitorHandle::new)
+            .monitors
+            .lock()
+            .unwrap()
+            .clone()
+            .into_iter()
     }
 
     #[inline]
@@ -23,6 +30,68 @@ impl EventLoopWindowTarget {
         // There's no primary monitor on Wayland.
         None
     }
+
+    pub fn set_monitor_power(
+        &self,
+        monitor: &MonitorHandle,
+        mode: PowerMode,
+    ) -> Result<(), NotSupportedError> {
+        let state = self.state.borrow();
+        let output_power_manager = state
+            .output_power_manager
+            .as_ref()
+            .ok_or_else(NotSupportedError::new)?;
+        output_power_manager.set_mode(&monitor.proxy, mode);
+        Ok(())
+    }
+
+    pub fn set_monitor_gamma_ramp(
+        &self,
+        monitor: &MonitorHandle,
+        red: &[u16],
+        green: &[u16],
+        blue: &[u16],
+    ) -> Result<(), GammaRampError> {
+        let mut state = self.state.borrow_mut();
+        let gamma_control_manager = state
+            .gamma_control_manager
+            .as_mut()
+            .ok_or(GammaRampError::NotSupported)?;
+
+        // Wait for the size of the ramps of the new control, to check the ramps against it.
+        if gamma_control_manager.create_control(&monitor.proxy) {
+            let mut wayland_source = self.wayland_dispatcher.as_source_mut();
+            if let Err(error) = wayland_source.queue().roundtrip(&mut state) {
+                error!("Error during the roundtrip with the compositor: {error}");
+            }
+
+            // Deliver the events dispatched during the roundtrip.
+            self.event_loop_awakener.ping();
+        }
+
+        state
+            .gamma_control_manager
+            .as_mut()
+            .ok_or(GammaRampError::NotSupported)?
+            .set_gamma_ramp(&monitor.proxy, red, green, blue)
+    }
+
+    pub fn capture_monitor(
+        &self,
+        monitor: &MonitorHandle,
+        region: Option<(LogicalPosition<i32>, LogicalSize<i32>)>,
+        callback: CaptureCallback,
+    ) {
+        let state = &mut *self.state.borrow_mut();
+        if let Some(screencopy_manager) = state.screencopy_manager.as_mut() {
+            screencopy_manager.capture(&monitor.proxy, region, callback);
+        } else {
+            // Call back on the next iteration, like when the capture completes.
+            let error = ExternalError::NotSupported(NotSupportedError::new());
+            state.captured_frames.push((callback, Err(error)));
+            self.event_loop_awakener.ping();
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -42,6 +111,27 @@ impl MonitorHandle {
         output_data.with_output_info(|info| info.name.clone())
     }
 
+    #[inline]
+    pub fn description(&self) -> Option<String> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        output_data.with_output_info(|info| info.description.clone())
+    }
+
+    #[inline]
+    pub fn stable_id(&self) -> Option<String> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        output_data.with_output_info(|info| {
+            // The connector tells apart the monitors of the same model.
+            let parts = [
+                info.make.as_str(),
+                info.model.as_str(),
+                info.name.as_deref().unwrap_or_default(),
+            ];
+            let parts: Vec<_> = parts.into_iter().filter(|part| !part.is_empty()).collect();
+            (!parts.is_empty()).then(|| parts.join("/"))
+        })
+    }
+
     #[inline]
     pub fn native_identifier(&self) -> u32 {
         let output_data = self.proxy.data::<OutputData>().unwrap();
@@ -64,6 +154,32 @@ impl MonitorHandle {
         .into()
     }
 
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        let (width, height) = output_data.with_output_info(|info| info.physical_size);
+
+        // Virtual outputs, such as projectors, may not have a physical size.
+        if width > 0 && height > 0 {
+            Some((width as u32, height as u32))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn subpixel_layout(&self) -> SubpixelLayout {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        match output_data.with_output_info(|info| info.subpixel) {
+            Subpixel::None => SubpixelLayout::None,
+            Subpixel::HorizontalRgb => SubpixelLayout::HorizontalRGB,
+            Subpixel::HorizontalBgr => SubpixelLayout::HorizontalBGR,
+            Subpixel::VerticalRgb => SubpixelLayout::VerticalRGB,
+            Subpixel::VerticalBgr => SubpixelLayout::VerticalBGR,
+            _ => SubpixelLayout::Unknown,
+        }
+    }
+
     #[inline]
     pub fn position(&self) -> PhysicalPosition<i32> {
         let output_data = self.proxy.data::<OutputData>().unwrap();
@@ -0,0 +1,544 @@
+//! The clipboard handling, with the selection of the data devices.
+
+use std::cell::Cell;
+use std::io::{self, ErrorKind, Read, Write};
+use std::os::fd::AsRawFd;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use rustix::fs::OFlags;
+
+use sctk::data_device_manager::data_device::{DataDeviceData, DataDeviceHandler};
+use sctk::data_device_manager::data_offer::{DataOfferHandler, DragOffer, SelectionOffer};
+use sctk::data_device_manager::data_source::{CopyPasteSource, DataSourceHandler};
+use sctk::data_device_manager::{ReadPipe, WritePipe};
+use sctk::reexports::calloop::PostAction;
+use sctk::reexports::client::protocol::wl_data_device::WlDataDevice;
+use sctk::reexports::client::protocol::wl_data_device_manager::DndAction;
+use sctk::reexports::client::protocol::wl_data_source::WlDataSource;
+use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+
+use crate::dpi::LogicalPosition;
+use crate::event::{DndAction as RootDndAction, Event, WindowEvent};
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{self, warn, WindowId};
+
+/// The text mime types, from the most preferred one.
+const TEXT_MIME_TYPES: [&str; 3] = ["text/plain;charset=utf-8", "UTF8_STRING", "text/plain"];
+
+/// How long the blocking clipboard reads wait for the owner of the selection to send it.
+const CLIPBOARD_READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The callback of a clipboard read, called with the data once it's read.
+pub type ClipboardCallback = Box<dyn FnOnce(Option<Vec<u8>>)>;
+
+/// The selection set by winit.
+#[derive(Debug)]
+pub struct ClipboardSource {
+    source: CopyPasteSource,
+    /// The data for each offered mime type.
+    offers: Vec<(String, Vec<u8>)>,
+}
+
+/// The drag and drop offer over a window.
+#[derive(Debug)]
+pub struct WindowDrag {
+    pub offer: DragOffer,
+    /// The mime type the offer is accepted as by the user.
+    pub accepted_mime_type: Option<String>,
+}
+
+impl WinitState {
+    /// The offers of the selection when it was set by winit itself, which can't be read from
+    /// the pipe, since sending them requires dispatching the request.
+    pub fn own_clipboard(&self) -> Option<&[(String, Vec<u8>)]> {
+        self.clipboard_source
+            .as_ref()
+            .map(|clipboard_source| clipboard_source.offers.as_slice())
+    }
+
+    /// The selection offer of the seat with the keyboard focus on one of the windows, or the
+    /// one of any seat otherwise.
+    fn selection_offer(&self) -> Option<SelectionOffer> {
+        let seats = || {
+            self.seats
+                .values()
+                .filter_map(|seat| Some((seat.data_device.as_ref()?, seat)))
+        };
+        let (data_device, _) = seats()
+            .find(|(_, seat)| seat.keyboard_focus_serial().is_some())
+            .or_else(|| seats().next())?;
+
+        data_device.data().selection_offer()
+    }
+
+    /// The mime types the selection is offered as.
+    pub fn clipboard_mime_types(&self) -> Vec<String> {
+        if let Some(offers) = self.own_clipboard() {
+            return offers
+                .iter()
+                .map(|(mime_type, _)| mime_type.clone())
+                .collect();
+        }
+
+        self.selection_offer()
+            .map(|offer| offer.with_mime_types(|mime_types| mime_types.to_vec()))
+            .unwrap_or_default()
+    }
+
+    /// Request the selection as the mime type, returning the pipe to read it from.
+    pub fn receive_clipboard(&self, mime_type: &str) -> Option<ReadPipe> {
+        let offer = self.selection_offer()?;
+        if !offer
+            .with_mime_types(|mime_types| mime_types.iter().any(|offered| offered == mime_type))
+        {
+            return None;
+        }
+
+        match offer.receive(mime_type.to_owned()) {
+            Ok(pipe) => Some(pipe),
+            Err(err) => {
+                warn!("failed to receive the selection: {err}");
+                None
+            }
+        }
+    }
+
+    /// Set the selection to the offers, on behalf of the seat with the keyboard focus.
+    pub fn set_clipboard(
+        &mut self,
+        offers: Vec<(String, Vec<u8>)>,
+        queue_handle: &QueueHandle<Self>,
+    ) {
+        let Some(data_device_manager) = self.data_device_manager.as_ref() else {
+            return;
+        };
+
+        // The compositor only accepts the selection set with the serial of a recent keyboard
+        // event of the focused client.
+        let Some((data_device, serial)) = self
+            .seats
+            .values()
+            .find_map(|seat| Some((seat.data_device.as_ref()?, seat.keyboard_focus_serial()?)))
+        else {
+            warn!("the clipboard can't be set without the keyboard focus");
+            return;
+        };
+
+        let mime_types = offers.iter().map(|(mime_type, _)| mime_type.clone());
+        let source = data_device_manager.create_copy_paste_source(queue_handle, mime_types);
+        source.set_selection(data_device, serial);
+        self.clipboard_source = Some(ClipboardSource { source, offers });
+    }
+}
+
+impl WinitState {
+    /// Read the pipe on the event loop as the owner of the selection sends the data, queuing the
+    /// callback in `clipboard_reads` once it closes the pipe.
+    pub fn read_clipboard_async(&mut self, pipe: ReadPipe, callback: ClipboardCallback) {
+        self.read_pipe_async(pipe, "selection", move |state, data| {
+            state.clipboard_reads.push((callback, data));
+        });
+    }
+
+    /// Read the pipe on the event loop until the other client closes it, calling `on_read` with
+    /// the data, or with `None` when reading the `what` failed.
+    fn read_pipe_async<F>(&mut self, pipe: ReadPipe, what: &'static str, on_read: F)
+    where
+        F: FnOnce(&mut WinitState, Option<Vec<u8>>) + 'static,
+    {
+        let on_read = Rc::new(Cell::new(Some(on_read)));
+        let pending_on_read = on_read.clone();
+        let mut data = Vec::new();
+        let result = self.loop_handle.insert_source(pipe, move |_, file, state| {
+            // NOTE: the pipe is only read once each time it's readable, so this doesn't block.
+            let mut buffer = [0; 4096];
+            let read = match (&**file).read(&mut buffer) {
+                Ok(0) => Some(std::mem::take(&mut data)),
+                Ok(len) => {
+                    data.extend_from_slice(&buffer[..len]);
+                    return PostAction::Continue;
+                }
+                Err(err) if err.kind() == ErrorKind::Interrupted => return PostAction::Continue,
+                Err(err) => {
+                    warn!("failed to read the {what}: {err}");
+                    None
+                }
+            };
+
+            if let Some(on_read) = pending_on_read.take() {
+                state.dispatched_events = true;
+                on_read(state, read);
+            }
+
+            PostAction::Remove
+        });
+
+        if let Err(err) = result {
+            warn!("failed to read the {what} on the event loop: {}", err.error);
+            if let Some(on_read) = on_read.take() {
+                on_read(self, None);
+            }
+        }
+    }
+
+    /// Write the data to the pipe on the event loop as the client pasting the selection reads
+    /// it, so a slow client doesn't stall the application.
+    fn write_clipboard_async(&mut self, pipe: WritePipe, data: Vec<u8>) {
+        let nonblocking = rustix::fs::fcntl_getfl(&pipe)
+            .and_then(|flags| rustix::fs::fcntl_setfl(&pipe, flags | OFlags::NONBLOCK));
+        if let Err(err) = nonblocking {
+            warn!("failed to make the selection pipe non-blocking: {err}");
+            return;
+        }
+
+        let mut written = 0;
+        let result = self.loop_handle.insert_source(pipe, move |_, file, _| {
+            // Write the chunks the pipe takes until it's full, and continue once it's writable.
+            while written < data.len() {
+                match (&**file).write(&data[written..]) {
+                    Ok(len) => written += len,
+                    Err(err) if err.kind() == ErrorKind::Interrupted => (),
+                    Err(err) if err.kind() == ErrorKind::WouldBlock => return PostAction::Continue,
+                    Err(err) => {
+                        warn!("failed to send the selection: {err}");
+                        break;
+                    }
+                }
+            }
+
+            // Close the pipe, so the client pasting the selection knows it has all the data.
+            PostAction::Remove
+        });
+
+        if let Err(err) = result {
+            warn!(
+                "failed to send the selection on the event loop: {}",
+                err.error
+            );
+        }
+    }
+}
+
+/// The most preferred text mime type among the offered ones.
+pub fn text_mime_type(mime_types: &[String]) -> Option<&'static str> {
+    TEXT_MIME_TYPES.into_iter().find(|text_mime_type| {
+        mime_types
+            .iter()
+            .any(|mime_type| mime_type == text_mime_type)
+    })
+}
+
+/// The offers of the text as all the text mime types, which are all sent as UTF-8.
+pub fn text_offers(text: String) -> Vec<(String, Vec<u8>)> {
+    let data = text.into_bytes();
+    TEXT_MIME_TYPES
+        .into_iter()
+        .map(|mime_type| (mime_type.to_owned(), data.clone()))
+        .collect()
+}
+
+/// Read the data from the pipe until the owner of the selection closes it, giving up when it
+/// doesn't send all of it within the `CLIPBOARD_READ_TIMEOUT`.
+pub fn read_clipboard(mut pipe: ReadPipe) -> Option<Vec<u8>> {
+    let deadline = Instant::now() + CLIPBOARD_READ_TIMEOUT;
+    let mut data = Vec::new();
+    let mut buffer = [0; 4096];
+    loop {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        let mut poll_fd = libc::pollfd {
+            fd: pipe.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: the pollfd is valid for the duration of the call.
+        match unsafe { libc::poll(&mut poll_fd, 1, timeout.as_millis() as libc::c_int) } {
+            0 => {
+                warn!("timed out reading the selection");
+                return None;
+            }
+            -1 => {
+                let err = io::Error::last_os_error();
+                if err.kind() == ErrorKind::Interrupted {
+                    continue;
+                }
+                warn!("failed to wait for the selection: {err}");
+                return None;
+            }
+            _ => (),
+        }
+
+        // NOTE: the pipe is readable, so reading it once doesn't block.
+        match pipe.read(&mut buffer) {
+            Ok(0) => return Some(data),
+            Ok(len) => data.extend_from_slice(&buffer[..len]),
+            Err(err) if err.kind() == ErrorKind::Interrupted => (),
+            Err(err) => {
+                warn!("failed to read the selection: {err}");
+                return None;
+            }
+        }
+    }
+}
+
+impl WinitState {
+    /// The window the drag and drop of the data device is over, with the scale factor.
+    fn drag_window(&self, data_device: &WlDataDevice) -> Option<(WindowId, f64)> {
+        let seat = data_device.data::<DataDeviceData>()?.seat();
+        let window_id = self.seats.get(&seat.id())?.drag_window?;
+        let scale_factor = self
+            .windows
+            .borrow()
+            .get(&window_id)?
+            .lock()
+            .unwrap()
+            .scale_factor();
+        Some((window_id, scale_factor))
+    }
+
+    /// Stop tracking the drag and drop of the data device, returning the window it was over with
+    /// the mime type it was accepted as.
+    fn end_drag(&mut self, data_device: &WlDataDevice) -> Option<(WindowId, Option<String>)> {
+        let seat = data_device.data::<DataDeviceData>()?.seat();
+        let window_id = self.seats.get_mut(&seat.id())?.drag_window.take()?;
+        let accepted_mime_type = self
+            .windows
+            .get_mut()
+            .get(&window_id)
+            .and_then(|window| window.lock().unwrap().take_drag())
+            .and_then(|drag| drag.accepted_mime_type);
+
+        Some((window_id, accepted_mime_type))
+    }
+}
+
+impl DataDeviceHandler for WinitState {
+    fn enter(&mut self, _: &Connection, _: &QueueHandle<Self>, data_device: &WlDataDevice) {
+        let Some(data) = data_device.data::<DataDeviceData>() else {
+            return;
+        };
+        let Some(offer) = data.drag_offer() else {
+            return;
+        };
+
+        // NOTE: the drags over the decorations aren't reported.
+        let window_id = wayland::make_wid(&offer.surface);
+        let mut window = match self.windows.get_mut().get(&window_id) {
+            Some(window) => window.lock().unwrap(),
+            None => return,
+        };
+
+        let position = LogicalPosition::new(offer.x, offer.y).to_physical(window.scale_factor());
+        let mime_types = offer.with_mime_types(|mime_types| mime_types.to_vec());
+        let source_actions = RootDndAction::from_bits_truncate(offer.source_actions.bits());
+        window.set_drag_offer(offer);
+        drop(window);
+
+        if let Some(seat) = self.seats.get_mut(&data.seat().id()) {
+            seat.drag_window = Some(window_id);
+        }
+
+        self.events_sink.push_window_event(
+            WindowEvent::DragEntered {
+                position,
+                mime_types,
+                source_actions,
+            },
+            window_id,
+        );
+    }
+
+    fn leave(&mut self, _: &Connection, _: &QueueHandle<Self>, data_device: &WlDataDevice) {
+        if let Some((window_id, _)) = self.end_drag(data_device) {
+            self.events_sink
+                .push_window_event(WindowEvent::DragLeft, window_id);
+        }
+    }
+
+    fn motion(&mut self, _: &Connection, _: &QueueHandle<Self>, data_device: &WlDataDevice) {
+        let Some((window_id, scale_factor)) = self.drag_window(data_device) else {
+            return;
+        };
+        let Some(offer) = data_device
+            .data::<DataDeviceData>()
+            .and_then(DataDeviceData::drag_offer)
+        else {
+            return;
+        };
+
+        let position = LogicalPosition::new(offer.x, offer.y).to_physical(scale_factor);
+        let source_actions = RootDndAction::from_bits_truncate(offer.source_actions.bits());
+        self.events_sink.push_window_event(
+            WindowEvent::DragMoved {
+                position,
+                source_actions,
+            },
+            window_id,
+        );
+    }
+
+    fn selection(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataDevice) {
+        // NOTE: the selection offer is kept by the data device until it's read.
+    }
+
+    fn drop_performed(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        data_device: &WlDataDevice,
+    ) {
+        let Some(offer) = data_device
+            .data::<DataDeviceData>()
+            .and_then(DataDeviceData::drag_offer)
+        else {
+            return;
+        };
+
+        // The drop ends the drag and drop, so the following leave isn't reported.
+        let Some((window_id, accepted_mime_type)) = self.end_drag(data_device) else {
+            offer.destroy();
+            return;
+        };
+
+        let action = RootDndAction::from_bits_truncate(offer.selected_action.bits());
+        let pipe = accepted_mime_type
+            .filter(|_| !action.is_empty())
+            .and_then(|mime_type| match offer.receive(mime_type.clone()) {
+                Ok(pipe) => Some((mime_type, pipe)),
+                Err(err) => {
+                    warn!("failed to receive the drop: {err}");
+                    None
+                }
+            });
+
+        let Some((mime_type, pipe)) = pipe else {
+            offer.destroy();
+            self.events_sink.push_window_event(
+                WindowEvent::DragDropped {
+                    action,
+                    mime_type: None,
+                    data: None,
+                },
+                window_id,
+            );
+            return;
+        };
+
+        self.read_pipe_async(pipe, "drop", move |state, data| {
+            // NOTE: the ask action isn't final, so the drop is cancelled by destroying the offer
+            // without finishing it.
+            if data.is_some() && action != RootDndAction::ASK {
+                offer.finish();
+            }
+            offer.destroy();
+
+            state.events_sink.push_window_event(
+                WindowEvent::DragDropped {
+                    action,
+                    mime_type: Some(mime_type),
+                    data,
+                },
+                window_id,
+            );
+        });
+    }
+}
+
+impl DataOfferHandler for WinitState {
+    fn source_actions(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        offer: &mut DragOffer,
+        actions: DndAction,
+    ) {
+        if offer.left || offer.dropped {
+            return;
+        }
+
+        // NOTE: the data offer is locked, so its mime types can't be read here.
+        let window_id = wayland::make_wid(&offer.surface);
+        let scale_factor = match self.windows.get_mut().get(&window_id) {
+            Some(window) => window.lock().unwrap().scale_factor(),
+            None => return,
+        };
+
+        let position = LogicalPosition::new(offer.x, offer.y).to_physical(scale_factor);
+        self.events_sink.push_window_event(
+            WindowEvent::DragMoved {
+                position,
+                source_actions: RootDndAction::from_bits_truncate(actions.bits()),
+            },
+            window_id,
+        );
+    }
+
+    fn selected_action(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &mut DragOffer,
+        _: DndAction,
+    ) {
+        // NOTE: the negotiated action is reported once it's dropped.
+    }
+}
+
+impl DataSourceHandler for WinitState {
+    fn accept_mime(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataSource,
+        _: Option<String>,
+    ) {
+    }
+
+    fn send_request(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &WlDataSource,
+        mime_type: String,
+        fd: WritePipe,
+    ) {
+        let Some(clipboard_source) = self
+            .clipboard_source
+            .as_ref()
+            .filter(|clipboard_source| clipboard_source.source.inner() == source)
+        else {
+            return;
+        };
+
+        let Some((_, data)) = clipboard_source
+            .offers
+            .iter()
+            .find(|(offered, _)| *offered == mime_type)
+        else {
+            return;
+        };
+
+        let data = data.clone();
+        self.write_clipboard_async(fd, data);
+    }
+
+    fn cancelled(&mut self, _: &Connection, _: &QueueHandle<Self>, source: &WlDataSource) {
+        // Another client set the selection.
+        if self
+            .clipboard_source
+            .as_ref()
+            .is_some_and(|clipboard_source| clipboard_source.source.inner() == source)
+        {
+            self.clipboard_source = None;
+            self.events_sink.push_event(Event::ClipboardLost);
+        }
+    }
+
+    fn dnd_dropped(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataSource) {}
+
+    fn dnd_finished(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataSource) {}
+
+    fn action(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataSource, _: DndAction) {}
+}
+
+sctk::delegate_data_device!(WinitState);
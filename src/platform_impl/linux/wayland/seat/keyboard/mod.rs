@@ -1,11 +1,12 @@
 //! The keyboard input handling.
 
+use std::os::fd::OwnedFd;
 use std::sync::Mutex;
 use std::time::Duration;
 
 use calloop::timer::{TimeoutAction, Timer};
 use calloop::{LoopHandle, RegistrationToken};
-use log::warn;
+use memmap2::MmapOptions;
 
 use sctk::reexports::client::protocol::wl_keyboard::WlKeyboard;
 use sctk::reexports::client::protocol::wl_keyboard::{
@@ -14,14 +15,14 @@ use sctk::reexports::client::protocol::wl_keyboard::{
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::{Connection, Dispatch, Proxy, QueueHandle, WEnum};
 
-use crate::event::{ElementState, WindowEvent};
+use crate::event::{DeviceEvent, ElementState, RawKeyEvent, WindowEvent};
 use crate::keyboard::ModifiersState;
 
-use crate::platform_impl::common::xkb::Context;
+use crate::platform_impl::common::xkb::{self, Context};
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
 use crate::platform_impl::wayland::seat::WinitSeatState;
 use crate::platform_impl::wayland::state::WinitState;
-use crate::platform_impl::wayland::{self, DeviceId, WindowId};
+use crate::platform_impl::wayland::{self, warn, DeviceId, WindowId};
 
 impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
     fn event(
@@ -37,15 +38,83 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
             None => return,
         };
 
+        if let WlKeyboardEvent::Enter { serial, .. } | WlKeyboardEvent::Key { serial, .. } = event {
+            if let Some(keyboard_state) = seat_state.keyboard_state.as_mut() {
+                keyboard_state.latest_serial = serial;
+            }
+        }
+
         match event {
+            WlKeyboardEvent::Key {
+                key,
+                state: WEnum::Value(key_state),
+                ..
+            } if state.raw_keyboard => {
+                // Deliver the keys as is, without the xkb processing and key repeat.
+                let key_state = match key_state {
+                    WlKeyState::Pressed => ElementState::Pressed,
+                    _ => ElementState::Released,
+                };
+                let event = DeviceEvent::Key(RawKeyEvent {
+                    physical_key: xkb::scancode_to_physicalkey(key),
+                    state: key_state,
+                });
+                state
+                    .events_sink
+                    .push_device_event(event, DeviceId::from_seat(&data.seat));
+            }
             WlKeyboardEvent::Keymap { format, fd, size } => match format {
                 WEnum::Value(format) => match format {
                     WlKeymapFormat::NoKeymap => {
                         warn!("non-xkb compatible keymap")
                     }
                     WlKeymapFormat::XkbV1 => {
-                        let context = &mut seat_state.keyboard_state.as_mut().unwrap().xkb_context;
-                        context.set_keymap_from_fd(fd, size as usize);
+                        let keyboard_state = seat_state.keyboard_state.as_mut().unwrap();
+
+                        // Keep the source of the keymap for the users doing their own key
+                        // handling.
+                        let map = unsafe {
+                            MmapOptions::new()
+                                .len(size as usize)
+                                .map_copy_read_only(&fd)
+                        };
+                        let keymap = match map {
+                            Ok(map) => {
+                                let len = map.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+                                Some(map[..len].to_vec())
+                            }
+                            Err(err) => {
+                                warn!("failed to read the keymap: {err}");
+                                None
+                            }
+                        };
+
+                        // The first keymap comes with the keyboard, only report the ones
+                        // replacing it, e.g. after a layout switch. Compositors also resend the
+                        // same keymap from time to time, so ignore those.
+                        let previous = std::mem::replace(&mut keyboard_state.keymap, keymap);
+                        let changed = previous.is_some() && previous != keyboard_state.keymap;
+
+                        keyboard_state.keymap_fd = match fd.try_clone() {
+                            Ok(fd) => Some((fd, size)),
+                            Err(err) => {
+                                warn!("failed to duplicate the keymap fd: {err}");
+                                None
+                            }
+                        };
+
+                        keyboard_state
+                            .xkb_context
+                            .set_keymap_from_fd(fd, size as usize);
+
+                        if changed {
+                            match *data.window_id.lock().unwrap() {
+                                Some(window_id) => state
+                                    .events_sink
+                                    .push_window_event(WindowEvent::KeymapChanged, window_id),
+                                None => keyboard_state.keymap_changed_pending = true,
+                            }
+                        }
                     }
                     _ => unreachable!(),
                 },
@@ -53,7 +122,10 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     warn!("unknown keymap format 0x{:x}", value)
                 }
             },
-            WlKeyboardEvent::Enter { surface, .. } => {
+            WlKeyboardEvent::Enter {
+                serial, surface, ..
+            } => {
+                trace_protocol!("<- {}.enter({serial}, {})", wl_keyboard.id(), surface.id());
                 let window_id = wayland::make_wid(&surface);
 
                 // Mark the window as focused.
@@ -83,6 +155,12 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                         .push_window_event(WindowEvent::Focused(true), window_id);
                 }
 
+                if std::mem::take(&mut keyboard_state.keymap_changed_pending) {
+                    state
+                        .events_sink
+                        .push_window_event(WindowEvent::KeymapChanged, window_id);
+                }
+
                 // HACK: this is just for GNOME not fixing their ordering issue of modifiers.
                 if std::mem::take(&mut seat_state.modifiers_pending) {
                     state.events_sink.push_window_event(
@@ -91,7 +169,8 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     );
                 }
             }
-            WlKeyboardEvent::Leave { surface, .. } => {
+            WlKeyboardEvent::Leave { serial, surface } => {
+                trace_protocol!("<- {}.leave({serial}, {})", wl_keyboard.id(), surface.id());
                 let window_id = wayland::make_wid(&surface);
 
                 // NOTE: we should drop the repeat regardless whethere it was for the present
@@ -297,6 +376,15 @@ pub struct KeyboardState {
     /// The state of the keyboard.
     pub xkb_context: Context,
 
+    /// The keymap sent by the compositor, without the trailing nul bytes.
+    pub keymap: Option<Vec<u8>>,
+
+    /// The file descriptor and size of the keymap sent by the compositor.
+    pub keymap_fd: Option<(OwnedFd, u32)>,
+
+    /// Whether the keymap changed while no window had the keyboard focus.
+    pub keymap_changed_pending: bool,
+
     /// The information about the repeat rate obtained from the compositor.
     pub repeat_info: RepeatInfo,
 
@@ -305,6 +393,9 @@ pub struct KeyboardState {
 
     /// The current repeat raw key.
     pub current_repeat: Option<u32>,
+
+    /// The serial of the latest enter or key event, which could be used for the clipboard.
+    pub latest_serial: u32,
 }
 
 impl KeyboardState {
@@ -313,9 +404,13 @@ impl KeyboardState {
             keyboard,
             loop_handle,
             xkb_context: Context::new().unwrap(),
+            keymap: None,
+            keymap_fd: None,
+            keymap_changed_pending: false,
             repeat_info: RepeatInfo::default(),
             repeat_token: None,
             current_repeat: None,
+            latest_serial: 0,
         }
     }
 }
@@ -377,6 +472,11 @@ impl KeyboardData {
             seat,
         }
     }
+
+    /// The window with the keyboard focus.
+    pub fn window_id(&self) -> Option<WindowId> {
+        *self.window_id.lock().unwrap()
+    }
 }
 
 fn key_input(
@@ -394,7 +494,9 @@ fn key_input(
 
     let keyboard_state = seat_state.keyboard_state.as_mut().unwrap();
 
-    let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(DeviceId));
+    let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
+        DeviceId::from_seat(&data.seat),
+    ));
     if let Some(mut key_context) = keyboard_state.xkb_context.key_context() {
         let event = key_context.process_key_event(keycode, state, repeat);
         let event = WindowEvent::KeyboardInput {
@@ -1,5 +1,6 @@
 //! Seat handling.
 
+use std::os::fd::OwnedFd;
 use std::sync::Arc;
 
 use ahash::AHashMap;
@@ -9,25 +10,36 @@ use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_touch::WlTouch;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle};
 use sctk::reexports::protocols::wp::relative_pointer::zv1::client::zwp_relative_pointer_v1::ZwpRelativePointerV1;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_seat_v2::ZwpTabletSeatV2;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::ZwpTextInputV3;
 
+use sctk::data_device_manager::data_device::DataDevice;
 use sctk::seat::pointer::{ThemeSpec, ThemedPointer};
 use sctk::seat::{Capability as SeatCapability, SeatHandler, SeatState};
 
 use crate::event::WindowEvent;
 use crate::keyboard::ModifiersState;
 use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::WindowId;
 
+mod data_device;
 mod keyboard;
 mod pointer;
+mod tablet;
 mod text_input;
 mod touch;
 
+pub use data_device::{
+    read_clipboard, text_mime_type, text_offers, ClipboardCallback, ClipboardSource, WindowDrag,
+};
+pub use pointer::pointer_gestures::PointerGesturesState;
 pub use pointer::relative_pointer::RelativePointerState;
 pub use pointer::{PointerConstraintsState, WinitPointerData, WinitPointerDataExt};
+pub use tablet::TabletManagerState;
 pub use text_input::{TextInputState, ZwpTextInputV3Ext};
 
 use keyboard::{KeyboardData, KeyboardState};
+use pointer::pointer_gestures::PointerGestures;
 use text_input::TextInputData;
 use touch::TouchPoint;
 
@@ -42,12 +54,27 @@ pub struct WinitSeatState {
     /// The mapping from touched points to the surfaces they're present.
     touch_map: AHashMap<i32, TouchPoint>,
 
+    /// The windows which got touch events in the current touch frame.
+    touch_frame_windows: Vec<WindowId>,
+
     /// The text input bound on the seat.
     text_input: Option<Arc<ZwpTextInputV3>>,
 
     /// The relative pointer bound on the seat.
     relative_pointer: Option<ZwpRelativePointerV1>,
 
+    /// The pointer gestures bound on the seat.
+    pointer_gestures: Option<PointerGestures>,
+
+    /// The tablets of the seat.
+    tablet_seat: Option<ZwpTabletSeatV2>,
+
+    /// The data device of the seat, used for the clipboard and drag and drop.
+    data_device: Option<DataDevice>,
+
+    /// The window the drag and drop of the seat is over.
+    drag_window: Option<WindowId>,
+
     /// The keyboard bound on the seat.
     keyboard_state: Option<KeyboardState>,
 
@@ -59,8 +86,60 @@ pub struct WinitSeatState {
 }
 
 impl WinitSeatState {
-    pub fn new() -> Self {
-        Default::default()
+    pub fn new(tablet_seat: Option<ZwpTabletSeatV2>, data_device: Option<DataDevice>) -> Self {
+        Self {
+            tablet_seat,
+            data_device,
+            ..Default::default()
+        }
+    }
+
+    /// The relative pointer bound on the seat.
+    pub fn relative_pointer(&self) -> Option<&ZwpRelativePointerV1> {
+        self.relative_pointer.as_ref()
+    }
+
+    /// The modifiers of the seat's keyboard, when it's focused on one of the windows.
+    pub fn focused_modifiers(&self) -> Option<ModifiersState> {
+        let keyboard_state = self.keyboard_state.as_ref()?;
+        keyboard_state
+            .keyboard
+            .data::<KeyboardData>()?
+            .window_id()
+            .map(|_| self.modifiers)
+    }
+
+    /// The serial of the latest keyboard event, when the keyboard is focused on one of the
+    /// windows.
+    pub fn keyboard_focus_serial(&self) -> Option<u32> {
+        let keyboard_state = self.keyboard_state.as_ref()?;
+        keyboard_state
+            .keyboard
+            .data::<KeyboardData>()?
+            .window_id()
+            .map(|_| keyboard_state.latest_serial)
+    }
+
+    /// The keymap of the seat's keyboard in the xkb text format.
+    pub fn xkb_keymap(&self) -> Option<&[u8]> {
+        self.keyboard_state.as_ref()?.keymap.as_deref()
+    }
+
+    /// The file descriptor and size of the keymap of the seat's keyboard.
+    pub fn xkb_keymap_fd(&self) -> Option<(&OwnedFd, u32)> {
+        let (fd, size) = self.keyboard_state.as_ref()?.keymap_fd.as_ref()?;
+        Some((fd, *size))
+    }
+
+    /// The seat and serial of the latest pointer button press on the window, which is the one
+    /// compositors accept for the popup grabs.
+    pub fn latest_press_serial(&self, window_id: WindowId) -> Option<(&WlSeat, u32)> {
+        let data = self.pointer.as_ref()?.pointer().winit_data();
+        if data.focused_window() != Some(window_id) {
+            return None;
+        }
+
+        Some((data.seat(), data.latest_press_serial()?))
     }
 }
 
@@ -111,6 +190,10 @@ impl SeatHandler for WinitState {
                     )
                 });
 
+                seat_state.pointer_gestures = self.pointer_gestures.as_ref().map(|manager| {
+                    manager.get_gestures(&seat, themed_pointer.pointer(), queue_handle)
+                });
+
                 let themed_pointer = Arc::new(themed_pointer);
 
                 // Register cursor surface.
@@ -162,6 +245,10 @@ impl SeatHandler for WinitState {
                     relative_pointer.destroy();
                 }
 
+                if let Some(pointer_gestures) = seat_state.pointer_gestures.take() {
+                    pointer_gestures.destroy();
+                }
+
                 if let Some(pointer) = seat_state.pointer.take() {
                     let pointer_data = pointer.pointer().winit_data();
 
@@ -189,10 +276,23 @@ impl SeatHandler for WinitState {
     fn new_seat(
         &mut self,
         _connection: &Connection,
-        _queue_handle: &QueueHandle<Self>,
+        queue_handle: &QueueHandle<Self>,
         seat: WlSeat,
     ) {
-        self.seats.insert(seat.id(), WinitSeatState::new());
+        let tablet_seat = self
+            .tablet_manager
+            .as_ref()
+            .map(|manager| manager.get_tablet_seat(&seat, queue_handle));
+        let data_device = self
+            .data_device_manager
+            .as_ref()
+            .map(|manager| manager.get_data_device(queue_handle, &seat));
+        self.seats
+            .insert(seat.id(), WinitSeatState::new(tablet_seat, data_device));
+
+        if let Some(idle_notifier) = self.idle_notifier.as_mut() {
+            idle_notifier.add_seat(&seat, queue_handle);
+        }
     }
 
     fn remove_seat(
@@ -201,7 +301,20 @@ impl SeatHandler for WinitState {
         _queue_handle: &QueueHandle<Self>,
         seat: WlSeat,
     ) {
-        let _ = self.seats.remove(&seat.id());
+        if let Some(tablet_seat) = self
+            .seats
+            .remove(&seat.id())
+            .and_then(|seat_state| seat_state.tablet_seat)
+        {
+            tablet_seat.destroy();
+        }
+        if let Some(event) = self
+            .idle_notifier
+            .as_mut()
+            .and_then(|idle_notifier| idle_notifier.remove_seat(&seat.id()))
+        {
+            self.events_sink.push_event(event);
+        }
         self.on_keyboard_destroy(&seat.id());
     }
 }
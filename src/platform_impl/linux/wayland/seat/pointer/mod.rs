@@ -6,6 +6,7 @@ use std::time::Duration;
 
 use sctk::reexports::client::delegate_dispatch;
 use sctk::reexports::client::protocol::wl_pointer::WlPointer;
+use sctk::reexports::client::protocol::wl_region::WlRegion;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle, Dispatch};
@@ -29,6 +30,7 @@ use crate::event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase, Wind
 use crate::platform_impl::wayland::state::WinitState;
 use crate::platform_impl::wayland::{self, DeviceId, WindowId};
 
+pub mod pointer_gestures;
 pub mod relative_pointer;
 
 impl PointerHandler for WinitState {
@@ -42,9 +44,27 @@ impl PointerHandler for WinitState {
         let seat = pointer.winit_data().seat();
         let seat_state = self.seats.get(&seat.id()).unwrap();
 
-        let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(DeviceId));
+        let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
+            DeviceId::from_seat(seat),
+        ));
 
         for event in events {
+            match event.kind {
+                PointerEventKind::Enter { serial } => trace_protocol!(
+                    "<- {}.enter({serial}, {}, {}, {})",
+                    pointer.id(),
+                    event.surface.id(),
+                    event.position.0,
+                    event.position.1
+                ),
+                PointerEventKind::Leave { serial } => trace_protocol!(
+                    "<- {}.leave({serial}, {})",
+                    pointer.id(),
+                    event.surface.id()
+                ),
+                _ => (),
+            }
+
             let surface = &event.surface;
 
             // The parent surface.
@@ -121,9 +141,15 @@ impl PointerHandler for WinitState {
                     if let Some(pointer) = seat_state.pointer.as_ref().map(Arc::downgrade) {
                         window.pointer_entered(pointer);
                     }
+                    window.resize_border_point_moved(event.position.0, event.position.1);
 
-                    // Set the currently focused surface.
-                    pointer.winit_data().inner.lock().unwrap().surface = Some(window_id);
+                    // Set the currently focused surface and reset the buttons, since we
+                    // don't get the releases while the pointer is outside, e.g. during the
+                    // interactive move.
+                    let mut pointer_data = pointer.winit_data().inner.lock().unwrap();
+                    pointer_data.surface = Some(window_id);
+                    pointer_data.pressed_buttons = 0;
+                    drop(pointer_data);
 
                     self.events_sink.push_window_event(
                         WindowEvent::CursorMoved {
@@ -137,14 +163,22 @@ impl PointerHandler for WinitState {
                     if let Some(pointer) = seat_state.pointer.as_ref().map(Arc::downgrade) {
                         window.pointer_left(pointer);
                     }
+                    window.title_bar_point_left();
+                    window.resize_border_point_left();
 
                     // Remove the active surface.
-                    pointer.winit_data().inner.lock().unwrap().surface = None;
+                    let mut pointer_data = pointer.winit_data().inner.lock().unwrap();
+                    pointer_data.surface = None;
+                    pointer_data.pressed_buttons = 0;
+                    drop(pointer_data);
 
                     self.events_sink
                         .push_window_event(WindowEvent::CursorLeft { device_id }, window_id);
                 }
                 PointerEventKind::Motion { .. } => {
+                    window.title_bar_point_moved(seat);
+                    window.resize_border_point_moved(event.position.0, event.position.1);
+
                     self.events_sink.push_window_event(
                         WindowEvent::CursorMoved {
                             device_id,
@@ -153,29 +187,61 @@ impl PointerHandler for WinitState {
                         window_id,
                     );
                 }
-                ref kind @ PointerEventKind::Press { button, serial, .. }
-                | ref kind @ PointerEventKind::Release { button, serial, .. } => {
-                    // Update the last button serial.
-                    pointer
-                        .winit_data()
-                        .inner
-                        .lock()
-                        .unwrap()
-                        .latest_button_serial = serial;
+                ref kind @ PointerEventKind::Press {
+                    button,
+                    serial,
+                    time,
+                }
+                | ref kind @ PointerEventKind::Release {
+                    button,
+                    serial,
+                    time,
+                } => {
+                    let pressed = matches!(kind, PointerEventKind::Press { .. });
+
+                    // Update the last button serial and the pressed buttons.
+                    let mut pointer_data = pointer.winit_data().inner.lock().unwrap();
+                    pointer_data.latest_button_serial = serial;
+                    if pressed {
+                        pointer_data.latest_press_serial = serial;
+                    }
+                    pointer_data.pressed_buttons = if pressed {
+                        pointer_data.pressed_buttons + 1
+                    } else {
+                        pointer_data.pressed_buttons.saturating_sub(1)
+                    };
+                    drop(pointer_data);
 
                     let button = wayland_button_to_winit(button);
-                    let state = if matches!(kind, PointerEventKind::Press { .. }) {
+
+                    // Resize the window when clicking on the resize border, and move it when
+                    // clicking on the title bar region.
+                    if button == MouseButton::Left
+                        && (window.resize_border_click(pressed, seat, serial)
+                            || window.title_bar_click(
+                                pressed,
+                                serial,
+                                Duration::from_millis(time as u64),
+                                event.position.0,
+                                event.position.1,
+                            ))
+                    {
+                        continue;
+                    }
+
+                    let state = if pressed {
                         ElementState::Pressed
                     } else {
                         ElementState::Released
                     };
-                    self.events_sink.push_window_event(
+                    self.events_sink.push_timed_window_event(
                         WindowEvent::MouseInput {
                             device_id,
                             state,
                             button,
                         },
                         window_id,
+                        time,
                     );
                 }
                 PointerEventKind::Axis {
@@ -285,18 +351,27 @@ impl WinitPointerData {
         pointer_constraints: &PointerConstraintsState,
         surface: &WlSurface,
         pointer: &WlPointer,
+        region: Option<&WlRegion>,
         queue_handle: &QueueHandle<WinitState>,
     ) {
         self.inner.lock().unwrap().confined_pointer = Some(pointer_constraints.confine_pointer(
             surface,
             pointer,
-            None,
+            region,
             Lifetime::Persistent,
             queue_handle,
             GlobalData,
         ));
     }
 
+    /// Update the region of the active confinement, applied on the next surface commit.
+    pub fn set_confine_region(&self, region: Option<&WlRegion>) {
+        let inner = self.inner.lock().unwrap();
+        if let Some(confined_pointer) = inner.confined_pointer.as_ref() {
+            confined_pointer.set_region(region);
+        }
+    }
+
     pub fn unconfine_pointer(&self) {
         let inner = self.inner.lock().unwrap();
         if let Some(confined_pointer) = inner.confined_pointer.as_ref() {
@@ -319,15 +394,32 @@ impl WinitPointerData {
         self.sctk_data.latest_button_serial().unwrap_or_default()
     }
 
+    /// Serial of the last button press, if any button is still held down.
+    pub fn latest_button_press_serial(&self) -> Option<u32> {
+        let inner = self.inner.lock().unwrap();
+        (inner.pressed_buttons > 0).then_some(inner.latest_button_serial)
+    }
+
+    /// Serial of the last button press, even if the button was released since.
+    pub fn latest_press_serial(&self) -> Option<u32> {
+        let serial = self.inner.lock().unwrap().latest_press_serial;
+        (serial != 0).then_some(serial)
+    }
+
     /// Last enter serial.
     pub fn latest_enter_serial(&self) -> u32 {
         self.sctk_data.latest_enter_serial().unwrap_or_default()
     }
 
-    pub fn set_locked_cursor_position(&self, surface_x: f64, surface_y: f64) {
+    /// Set the cursor position hint for the locked pointer, returning whether the pointer was
+    /// locked.
+    pub fn set_locked_cursor_position(&self, surface_x: f64, surface_y: f64) -> bool {
         let inner = self.inner.lock().unwrap();
         if let Some(locked_pointer) = inner.locked_pointer.as_ref() {
             locked_pointer.set_cursor_position_hint(surface_x, surface_y);
+            true
+        } else {
+            false
         }
     }
 }
@@ -349,6 +441,12 @@ pub struct WinitPointerDataInner {
     /// Serial of the last button event.
     latest_button_serial: u32,
 
+    /// Serial of the last button press.
+    latest_press_serial: u32,
+
+    /// The number of buttons held down on the surface.
+    pressed_buttons: u32,
+
     /// Currently focused window.
     surface: Option<WindowId>,
 
@@ -375,6 +473,8 @@ impl Default for WinitPointerDataInner {
             locked_pointer: None,
             confined_pointer: None,
             latest_button_serial: 0,
+            latest_press_serial: 0,
+            pressed_buttons: 0,
             phase: TouchPhase::Ended,
         }
     }
@@ -0,0 +1,291 @@
+//! Pointer gestures.
+
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_pointer::WlPointer;
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::{delegate_dispatch, Dispatch};
+use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::pointer_gestures::zv1::{
+    client::zwp_pointer_gesture_hold_v1::{self, ZwpPointerGestureHoldV1},
+    client::zwp_pointer_gesture_pinch_v1::{self, ZwpPointerGesturePinchV1},
+    client::zwp_pointer_gesture_swipe_v1::{self, ZwpPointerGestureSwipeV1},
+    client::zwp_pointer_gestures_v1::ZwpPointerGesturesV1,
+};
+
+use sctk::globals::GlobalData;
+
+use crate::dpi::LogicalPosition;
+use crate::event::{TouchPhase, WindowEvent};
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{self, WindowId};
+
+/// Wrapper around the pointer gestures.
+pub struct PointerGesturesState {
+    manager: ZwpPointerGesturesV1,
+}
+
+impl PointerGesturesState {
+    /// Create new pointer gestures manager.
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=3, GlobalData)?;
+        Ok(Self { manager })
+    }
+
+    /// Create the gestures for the pointer of the seat.
+    pub fn get_gestures(
+        &self,
+        seat: &WlSeat,
+        pointer: &WlPointer,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> PointerGestures {
+        let data = || PointerGestureData::new(seat.clone());
+        PointerGestures {
+            swipe: self.get_swipe_gesture(pointer, queue_handle, data()),
+            pinch: self.get_pinch_gesture(pointer, queue_handle, data()),
+            // The hold gesture is available since version 3.
+            hold: (self.version() >= 3)
+                .then(|| self.get_hold_gesture(pointer, queue_handle, data())),
+        }
+    }
+}
+
+impl Deref for PointerGesturesState {
+    type Target = ZwpPointerGesturesV1;
+
+    fn deref(&self) -> &Self::Target {
+        &self.manager
+    }
+}
+
+/// The gestures of the pointer of a seat.
+#[derive(Debug)]
+pub struct PointerGestures {
+    swipe: ZwpPointerGestureSwipeV1,
+    pinch: ZwpPointerGesturePinchV1,
+    hold: Option<ZwpPointerGestureHoldV1>,
+}
+
+impl PointerGestures {
+    pub fn destroy(self) {
+        self.swipe.destroy();
+        self.pinch.destroy();
+        if let Some(hold) = self.hold {
+            hold.destroy();
+        }
+    }
+}
+
+/// The data of the gesture objects.
+#[derive(Debug)]
+pub struct PointerGestureData {
+    /// The seat the gesture belongs to.
+    seat: WlSeat,
+
+    inner: Mutex<PointerGestureDataInner>,
+}
+
+impl PointerGestureData {
+    pub fn new(seat: WlSeat) -> Self {
+        Self {
+            seat,
+            inner: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PointerGestureDataInner {
+    /// The window the ongoing gesture happens on.
+    window_id: Option<WindowId>,
+
+    /// The number of fingers of the ongoing swipe or hold.
+    fingers: u32,
+
+    /// The scale of the ongoing pinch, relative to its beginning.
+    scale: f64,
+}
+
+impl Dispatch<ZwpPointerGesturesV1, GlobalData, WinitState> for PointerGesturesState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpPointerGesturesV1,
+        _event: <ZwpPointerGesturesV1 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwpPointerGestureSwipeV1, PointerGestureData, WinitState> for PointerGesturesState {
+    fn event(
+        state: &mut WinitState,
+        _proxy: &ZwpPointerGestureSwipeV1,
+        event: <ZwpPointerGestureSwipeV1 as Proxy>::Event,
+        data: &PointerGestureData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        let mut inner = data.inner.lock().unwrap();
+        let (window_id, phase, delta) = match event {
+            zwp_pointer_gesture_swipe_v1::Event::Begin {
+                surface, fingers, ..
+            } => {
+                let window_id = wayland::make_wid(&surface);
+                inner.window_id = Some(window_id);
+                inner.fingers = fingers;
+                (window_id, TouchPhase::Started, (0., 0.))
+            }
+            zwp_pointer_gesture_swipe_v1::Event::Update { dx, dy, .. } => match inner.window_id {
+                Some(window_id) => (window_id, TouchPhase::Moved, (dx, dy)),
+                None => return,
+            },
+            zwp_pointer_gesture_swipe_v1::Event::End { cancelled, .. } => {
+                match inner.window_id.take() {
+                    Some(window_id) => (window_id, end_phase(cancelled), (0., 0.)),
+                    None => return,
+                }
+            }
+            _ => return,
+        };
+
+        let scale_factor = match state.windows.get_mut().get(&window_id) {
+            Some(window) => window.lock().unwrap().scale_factor(),
+            None => return,
+        };
+
+        let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
+            wayland::DeviceId::from_seat(&data.seat),
+        ));
+        state.events_sink.push_window_event(
+            WindowEvent::SwipeGesture {
+                device_id,
+                fingers: inner.fingers,
+                delta: LogicalPosition::<f64>::from(delta).to_physical(scale_factor),
+                phase,
+            },
+            window_id,
+        );
+    }
+}
+
+impl Dispatch<ZwpPointerGesturePinchV1, PointerGestureData, WinitState> for PointerGesturesState {
+    fn event(
+        state: &mut WinitState,
+        _proxy: &ZwpPointerGesturePinchV1,
+        event: <ZwpPointerGesturePinchV1 as Proxy>::Event,
+        data: &PointerGestureData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        let mut inner = data.inner.lock().unwrap();
+        let (window_id, phase, scale_delta, rotation_delta) = match event {
+            zwp_pointer_gesture_pinch_v1::Event::Begin { surface, .. } => {
+                let window_id = wayland::make_wid(&surface);
+                inner.window_id = Some(window_id);
+                inner.scale = 1.;
+                (window_id, TouchPhase::Started, 0., 0.)
+            }
+            zwp_pointer_gesture_pinch_v1::Event::Update {
+                scale, rotation, ..
+            } => match inner.window_id {
+                Some(window_id) => {
+                    let scale_delta = scale - inner.scale;
+                    inner.scale = scale;
+                    // The rotation is clockwise, while winit's is counterclockwise.
+                    (window_id, TouchPhase::Moved, scale_delta, -rotation as f32)
+                }
+                None => return,
+            },
+            zwp_pointer_gesture_pinch_v1::Event::End { cancelled, .. } => {
+                match inner.window_id.take() {
+                    Some(window_id) => (window_id, end_phase(cancelled), 0., 0.),
+                    None => return,
+                }
+            }
+            _ => return,
+        };
+
+        let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
+            wayland::DeviceId::from_seat(&data.seat),
+        ));
+        state.events_sink.push_window_event(
+            WindowEvent::PinchGesture {
+                device_id,
+                delta: scale_delta,
+                phase,
+            },
+            window_id,
+        );
+        state.events_sink.push_window_event(
+            WindowEvent::RotationGesture {
+                device_id,
+                delta: rotation_delta,
+                phase,
+            },
+            window_id,
+        );
+    }
+}
+
+impl Dispatch<ZwpPointerGestureHoldV1, PointerGestureData, WinitState> for PointerGesturesState {
+    fn event(
+        state: &mut WinitState,
+        _proxy: &ZwpPointerGestureHoldV1,
+        event: <ZwpPointerGestureHoldV1 as Proxy>::Event,
+        data: &PointerGestureData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        let mut inner = data.inner.lock().unwrap();
+        let (window_id, phase) = match event {
+            zwp_pointer_gesture_hold_v1::Event::Begin {
+                surface, fingers, ..
+            } => {
+                let window_id = wayland::make_wid(&surface);
+                inner.window_id = Some(window_id);
+                inner.fingers = fingers;
+                (window_id, TouchPhase::Started)
+            }
+            zwp_pointer_gesture_hold_v1::Event::End { cancelled, .. } => {
+                match inner.window_id.take() {
+                    Some(window_id) => (window_id, end_phase(cancelled)),
+                    None => return,
+                }
+            }
+            _ => return,
+        };
+
+        let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
+            wayland::DeviceId::from_seat(&data.seat),
+        ));
+        state.events_sink.push_window_event(
+            WindowEvent::TouchpadHold {
+                device_id,
+                fingers: inner.fingers,
+                phase,
+            },
+            window_id,
+        );
+    }
+}
+
+/// The phase of the `end` event of the gesture.
+fn end_phase(cancelled: i32) -> TouchPhase {
+    if cancelled == 0 {
+        TouchPhase::Ended
+    } else {
+        TouchPhase::Cancelled
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwpPointerGesturesV1: GlobalData] => PointerGesturesState);
+delegate_dispatch!(WinitState: [ZwpPointerGestureSwipeV1: PointerGestureData] => PointerGesturesState);
+delegate_dispatch!(WinitState: [ZwpPointerGesturePinchV1: PointerGestureData] => PointerGesturesState);
+delegate_dispatch!(WinitState: [ZwpPointerGestureHoldV1: PointerGestureData] => PointerGesturesState);
@@ -0,0 +1,414 @@
+//! Tablet pads.
+
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::{delegate_dispatch, event_created_child, Dispatch};
+use sctk::reexports::client::{Connection, Proxy, QueueHandle, WEnum};
+use sctk::reexports::protocols::wp::tablet::zv2::client::{
+    zwp_tablet_manager_v2::ZwpTabletManagerV2,
+    zwp_tablet_pad_group_v2::{self, ZwpTabletPadGroupV2},
+    zwp_tablet_pad_ring_v2::{self, ZwpTabletPadRingV2},
+    zwp_tablet_pad_strip_v2::{self, ZwpTabletPadStripV2},
+    zwp_tablet_pad_v2::{self, ButtonState, ZwpTabletPadV2},
+    zwp_tablet_seat_v2::{self, ZwpTabletSeatV2},
+    zwp_tablet_tool_v2::{self, ZwpTabletToolV2},
+    zwp_tablet_v2::{self, ZwpTabletV2},
+};
+
+use sctk::globals::GlobalData;
+
+use crate::event::{ElementState, TabletPadEvent, WindowEvent};
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{self, WindowId};
+
+/// The maximum position of a strip.
+const STRIP_POSITION_MAX: f64 = 65535.;
+
+/// Wrapper around the tablet manager.
+pub struct TabletManagerState {
+    manager: ZwpTabletManagerV2,
+}
+
+impl TabletManagerState {
+    /// Create new tablet manager.
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+
+    /// Get the tablets of the seat.
+    pub fn get_tablet_seat(
+        &self,
+        seat: &WlSeat,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> ZwpTabletSeatV2 {
+        let data = TabletSeatData { seat: seat.clone() };
+        self.manager.get_tablet_seat(seat, queue_handle, data)
+    }
+}
+
+impl Deref for TabletManagerState {
+    type Target = ZwpTabletManagerV2;
+
+    fn deref(&self) -> &Self::Target {
+        &self.manager
+    }
+}
+
+/// The data of the tablet seat.
+#[derive(Debug)]
+pub struct TabletSeatData {
+    /// The seat the tablets belong to.
+    seat: WlSeat,
+}
+
+/// The data of the tablet pad.
+#[derive(Debug, Default)]
+pub struct TabletPadData {
+    inner: Mutex<TabletPadDataInner>,
+}
+
+#[derive(Debug, Default)]
+struct TabletPadDataInner {
+    /// The seat the pad belongs to, set once the pad is announced.
+    seat: Option<WlSeat>,
+
+    /// The window the pad is focused on.
+    window_id: Option<WindowId>,
+
+    /// The groups of the pad, destroyed together with it.
+    groups: Vec<ZwpTabletPadGroupV2>,
+
+    /// The rings of the pad, indexed in the order they're announced.
+    rings: Vec<ZwpTabletPadRingV2>,
+
+    /// The strips of the pad, indexed in the order they're announced.
+    strips: Vec<ZwpTabletPadStripV2>,
+}
+
+/// The data of the pad group.
+#[derive(Debug, Default)]
+pub struct TabletPadGroupData {
+    /// The pad the group belongs to.
+    pad: Mutex<Option<ZwpTabletPadV2>>,
+}
+
+/// The data of the pad rings and strips.
+#[derive(Debug, Default)]
+pub struct TabletPadAxisData {
+    inner: Mutex<TabletPadAxisDataInner>,
+}
+
+#[derive(Debug, Default)]
+struct TabletPadAxisDataInner {
+    /// The pad the axis belongs to.
+    pad: Option<ZwpTabletPadV2>,
+
+    /// The index of the ring or strip on the pad.
+    index: u32,
+
+    /// The value received in the current frame.
+    pending: Option<f64>,
+
+    /// The angle of the ring in the previous frame.
+    last_angle: Option<f64>,
+}
+
+impl Dispatch<ZwpTabletManagerV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpTabletManagerV2,
+        _event: <ZwpTabletManagerV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwpTabletSeatV2, TabletSeatData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpTabletSeatV2,
+        event: <ZwpTabletSeatV2 as Proxy>::Event,
+        data: &TabletSeatData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        if let zwp_tablet_seat_v2::Event::PadAdded { id } = event {
+            if let Some(pad_data) = id.data::<TabletPadData>() {
+                pad_data.inner.lock().unwrap().seat = Some(data.seat.clone());
+            }
+        }
+    }
+
+    event_created_child!(WinitState, ZwpTabletSeatV2, [
+        zwp_tablet_seat_v2::EVT_TABLET_ADDED_OPCODE => (ZwpTabletV2, GlobalData),
+        zwp_tablet_seat_v2::EVT_TOOL_ADDED_OPCODE => (ZwpTabletToolV2, GlobalData),
+        zwp_tablet_seat_v2::EVT_PAD_ADDED_OPCODE => (ZwpTabletPadV2, Default::default()),
+    ]);
+}
+
+impl Dispatch<ZwpTabletV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        proxy: &ZwpTabletV2,
+        event: <ZwpTabletV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        if let zwp_tablet_v2::Event::Removed = event {
+            proxy.destroy();
+        }
+    }
+}
+
+impl Dispatch<ZwpTabletToolV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        proxy: &ZwpTabletToolV2,
+        event: <ZwpTabletToolV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        // NOTE: the tools aren't handled, only the pads.
+        if let zwp_tablet_tool_v2::Event::Removed = event {
+            proxy.destroy();
+        }
+    }
+}
+
+impl Dispatch<ZwpTabletPadV2, TabletPadData, WinitState> for TabletManagerState {
+    fn event(
+        state: &mut WinitState,
+        proxy: &ZwpTabletPadV2,
+        event: <ZwpTabletPadV2 as Proxy>::Event,
+        data: &TabletPadData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        let mut inner = data.inner.lock().unwrap();
+        match event {
+            zwp_tablet_pad_v2::Event::Group { pad_group } => {
+                if let Some(group_data) = pad_group.data::<TabletPadGroupData>() {
+                    *group_data.pad.lock().unwrap() = Some(proxy.clone());
+                }
+                inner.groups.push(pad_group);
+            }
+            zwp_tablet_pad_v2::Event::Enter { surface, .. } => {
+                inner.window_id = Some(wayland::make_wid(&surface));
+            }
+            zwp_tablet_pad_v2::Event::Leave { .. } => {
+                inner.window_id = None;
+            }
+            zwp_tablet_pad_v2::Event::Button {
+                button,
+                state: WEnum::Value(button_state),
+                ..
+            } => {
+                let button_state = match button_state {
+                    ButtonState::Pressed => ElementState::Pressed,
+                    _ => ElementState::Released,
+                };
+                drop(inner);
+                push_pad_event(
+                    state,
+                    data,
+                    TabletPadEvent::Button {
+                        button,
+                        state: button_state,
+                    },
+                );
+            }
+            zwp_tablet_pad_v2::Event::Removed => {
+                for group in inner.groups.drain(..) {
+                    group.destroy();
+                }
+                for ring in inner.rings.drain(..) {
+                    ring.destroy();
+                }
+                for strip in inner.strips.drain(..) {
+                    strip.destroy();
+                }
+                proxy.destroy();
+            }
+            _ => (),
+        }
+    }
+
+    event_created_child!(WinitState, ZwpTabletPadV2, [
+        zwp_tablet_pad_v2::EVT_GROUP_OPCODE => (ZwpTabletPadGroupV2, Default::default()),
+    ]);
+}
+
+impl Dispatch<ZwpTabletPadGroupV2, TabletPadGroupData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpTabletPadGroupV2,
+        event: <ZwpTabletPadGroupV2 as Proxy>::Event,
+        data: &TabletPadGroupData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        let pad = data.pad.lock().unwrap();
+        let Some(pad_data) = pad.as_ref().and_then(|pad| pad.data::<TabletPadData>()) else {
+            return;
+        };
+
+        let mut pad_inner = pad_data.inner.lock().unwrap();
+        let (axis_data, index) = match &event {
+            zwp_tablet_pad_group_v2::Event::Ring { ring } => {
+                pad_inner.rings.push(ring.clone());
+                (ring.data::<TabletPadAxisData>(), pad_inner.rings.len() - 1)
+            }
+            zwp_tablet_pad_group_v2::Event::Strip { strip } => {
+                pad_inner.strips.push(strip.clone());
+                (
+                    strip.data::<TabletPadAxisData>(),
+                    pad_inner.strips.len() - 1,
+                )
+            }
+            _ => return,
+        };
+
+        if let Some(axis_data) = axis_data {
+            let mut axis_inner = axis_data.inner.lock().unwrap();
+            axis_inner.pad = pad.clone();
+            axis_inner.index = index as u32;
+        }
+    }
+
+    event_created_child!(WinitState, ZwpTabletPadGroupV2, [
+        zwp_tablet_pad_group_v2::EVT_RING_OPCODE => (ZwpTabletPadRingV2, Default::default()),
+        zwp_tablet_pad_group_v2::EVT_STRIP_OPCODE => (ZwpTabletPadStripV2, Default::default()),
+    ]);
+}
+
+impl Dispatch<ZwpTabletPadRingV2, TabletPadAxisData, WinitState> for TabletManagerState {
+    fn event(
+        state: &mut WinitState,
+        _proxy: &ZwpTabletPadRingV2,
+        event: <ZwpTabletPadRingV2 as Proxy>::Event,
+        data: &TabletPadAxisData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        let mut inner = data.inner.lock().unwrap();
+        match event {
+            zwp_tablet_pad_ring_v2::Event::Angle { degrees } => inner.pending = Some(degrees),
+            zwp_tablet_pad_ring_v2::Event::Stop => {
+                inner.pending = None;
+                inner.last_angle = None;
+            }
+            zwp_tablet_pad_ring_v2::Event::Frame { .. } => {
+                let Some(angle) = inner.pending.take() else {
+                    return;
+                };
+
+                // The first angle after the finger touched the ring only sets the origin.
+                let last_angle = inner.last_angle.replace(angle);
+                if let (Some(last_angle), Some(pad_data)) = (
+                    last_angle,
+                    inner
+                        .pad
+                        .as_ref()
+                        .and_then(|pad| pad.data::<TabletPadData>()),
+                ) {
+                    let event = TabletPadEvent::Ring {
+                        ring: inner.index,
+                        delta: ring_delta(last_angle, angle),
+                    };
+                    push_pad_event(state, pad_data, event);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+impl Dispatch<ZwpTabletPadStripV2, TabletPadAxisData, WinitState> for TabletManagerState {
+    fn event(
+        state: &mut WinitState,
+        _proxy: &ZwpTabletPadStripV2,
+        event: <ZwpTabletPadStripV2 as Proxy>::Event,
+        data: &TabletPadAxisData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        let mut inner = data.inner.lock().unwrap();
+        match event {
+            zwp_tablet_pad_strip_v2::Event::Position { position } => {
+                inner.pending = Some(position as f64 / STRIP_POSITION_MAX);
+            }
+            zwp_tablet_pad_strip_v2::Event::Stop => inner.pending = None,
+            zwp_tablet_pad_strip_v2::Event::Frame { .. } => {
+                let (Some(position), Some(pad_data)) = (
+                    inner.pending.take(),
+                    inner
+                        .pad
+                        .as_ref()
+                        .and_then(|pad| pad.data::<TabletPadData>()),
+                ) else {
+                    return;
+                };
+
+                let event = TabletPadEvent::Strip {
+                    strip: inner.index,
+                    position,
+                };
+                push_pad_event(state, pad_data, event);
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Send the event to the window the pad is focused on.
+fn push_pad_event(state: &mut WinitState, data: &TabletPadData, event: TabletPadEvent) {
+    let inner = data.inner.lock().unwrap();
+    let (Some(seat), Some(window_id)) = (inner.seat.as_ref(), inner.window_id) else {
+        return;
+    };
+
+    let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
+        wayland::DeviceId::from_seat(seat),
+    ));
+    state
+        .events_sink
+        .push_window_event(WindowEvent::TabletPad { device_id, event }, window_id);
+}
+
+/// The clockwise rotation of the ring between the angles, taking the shortest way around.
+fn ring_delta(last_angle: f64, angle: f64) -> f64 {
+    (angle - last_angle + 180.).rem_euclid(360.) - 180.
+}
+
+delegate_dispatch!(WinitState: [ZwpTabletManagerV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletSeatV2: TabletSeatData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletToolV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletPadV2: TabletPadData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletPadGroupV2: TabletPadGroupData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletPadRingV2: TabletPadAxisData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletPadStripV2: TabletPadAxisData] => TabletManagerState);
+
+#[cfg(test)]
+mod tests {
+    use super::ring_delta;
+
+    #[test]
+    fn ring_delta_wraps_around() {
+        assert_eq!(ring_delta(10., 30.), 20.);
+        assert_eq!(ring_delta(30., 10.), -20.);
+        assert_eq!(ring_delta(350., 10.), 20.);
+        assert_eq!(ring_delta(10., 350.), -20.);
+    }
+}
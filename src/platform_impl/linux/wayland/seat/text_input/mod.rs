@@ -74,8 +74,7 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                 };
 
                 if window.ime_allowed() {
-                    text_input.enable();
-                    text_input.set_content_type_by_purpose(window.ime_purpose());
+                    window.enable_text_input(text_input);
                     text_input.commit();
                     state
                         .events_sink
@@ -135,6 +134,17 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                     None => return,
                 };
 
+                // The compositor could still send the state pending before the text input was
+                // disabled, which the users don't expect after disallowing the IME.
+                let ime_allowed = windows
+                    .get(&window_id)
+                    .is_some_and(|window| window.lock().unwrap().ime_allowed());
+                if !ime_allowed {
+                    text_input_data.pending_preedit = None;
+                    text_input_data.pending_commit = None;
+                    return;
+                }
+
                 // Clear preedit at the start of `Done`.
                 state.events_sink.push_window_event(
                     WindowEvent::Ime(Ime::Preedit(String::new(), None)),
@@ -176,8 +186,19 @@ impl ZwpTextInputV3Ext for ZwpTextInputV3 {
     fn set_content_type_by_purpose(&self, purpose: ImePurpose) {
         let (hint, purpose) = match purpose {
             ImePurpose::Normal => (ContentHint::None, ContentPurpose::Normal),
-            ImePurpose::Password => (ContentHint::SensitiveData, ContentPurpose::Password),
+            ImePurpose::Password => (
+                ContentHint::SensitiveData | ContentHint::HiddenText,
+                ContentPurpose::Password,
+            ),
             ImePurpose::Terminal => (ContentHint::None, ContentPurpose::Terminal),
+            ImePurpose::Number => (ContentHint::None, ContentPurpose::Number),
+            ImePurpose::Pin => (
+                ContentHint::SensitiveData | ContentHint::HiddenText,
+                ContentPurpose::Pin,
+            ),
+            ImePurpose::Phone => (ContentHint::None, ContentPurpose::Phone),
+            ImePurpose::Url => (ContentHint::None, ContentPurpose::Url),
+            ImePurpose::Email => (ContentHint::None, ContentPurpose::Email),
         };
         self.set_content_type(hint, purpose);
     }
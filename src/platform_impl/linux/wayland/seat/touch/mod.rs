@@ -2,16 +2,18 @@
 
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
-use sctk::reexports::client::protocol::wl_touch::WlTouch;
-use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+use sctk::reexports::client::protocol::wl_touch::{Event as TouchEvent, WlTouch};
+use sctk::reexports::client::{Connection, Dispatch, Proxy, QueueHandle};
 
 use sctk::seat::touch::{TouchData, TouchHandler};
+use sctk::seat::SeatState;
 
-use crate::dpi::LogicalPosition;
+use crate::dpi::{LogicalPosition, PhysicalPosition};
 use crate::event::{Touch, TouchPhase, WindowEvent};
 
+use crate::platform_impl::wayland::seat::WinitSeatState;
 use crate::platform_impl::wayland::state::WinitState;
-use crate::platform_impl::wayland::{self, DeviceId};
+use crate::platform_impl::wayland::{self, DeviceId, WindowId};
 
 impl TouchHandler for WinitState {
     fn down(
@@ -43,7 +45,7 @@ impl TouchHandler for WinitState {
         self.events_sink.push_window_event(
             WindowEvent::Touch(Touch {
                 device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                    DeviceId,
+                    DeviceId::from_seat(touch.seat()),
                 )),
                 phase: TouchPhase::Started,
                 location: location.to_physical(scale_factor),
@@ -52,6 +54,8 @@ impl TouchHandler for WinitState {
             }),
             window_id,
         );
+
+        touch_frame_window(seat_state, window_id);
     }
 
     fn up(
@@ -80,7 +84,7 @@ impl TouchHandler for WinitState {
         self.events_sink.push_window_event(
             WindowEvent::Touch(Touch {
                 device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                    DeviceId,
+                    DeviceId::from_seat(touch.seat()),
                 )),
                 phase: TouchPhase::Ended,
                 location: touch_point.location.to_physical(scale_factor),
@@ -89,6 +93,8 @@ impl TouchHandler for WinitState {
             }),
             window_id,
         );
+
+        touch_frame_window(seat_state, window_id);
     }
 
     fn motion(
@@ -119,7 +125,7 @@ impl TouchHandler for WinitState {
         self.events_sink.push_window_event(
             WindowEvent::Touch(Touch {
                 device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                    DeviceId,
+                    DeviceId::from_seat(touch.seat()),
                 )),
                 phase: TouchPhase::Moved,
                 location: touch_point.location.to_physical(scale_factor),
@@ -128,10 +134,13 @@ impl TouchHandler for WinitState {
             }),
             window_id,
         );
+
+        touch_frame_window(seat_state, window_id);
     }
 
     fn cancel(&mut self, _: &Connection, _: &QueueHandle<Self>, touch: &WlTouch) {
         let seat_state = self.seats.get_mut(&touch.seat().id()).unwrap();
+        seat_state.touch_frame_windows.clear();
 
         for (id, touch_point) in seat_state.touch_map.drain() {
             let window_id = wayland::make_wid(&touch_point.surface);
@@ -145,7 +154,7 @@ impl TouchHandler for WinitState {
             self.events_sink.push_window_event(
                 WindowEvent::Touch(Touch {
                     device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
+                        DeviceId::from_seat(touch.seat()),
                     )),
                     phase: TouchPhase::Cancelled,
                     location,
@@ -174,6 +183,55 @@ impl TouchHandler for WinitState {
     }
 }
 
+impl Dispatch<WlTouch, TouchData, WinitState> for WinitState {
+    fn event(
+        state: &mut WinitState,
+        touch: &WlTouch,
+        event: TouchEvent,
+        data: &TouchData,
+        conn: &Connection,
+        qh: &QueueHandle<WinitState>,
+    ) {
+        let is_frame = matches!(event, TouchEvent::Frame);
+
+        // SCTK buffers the events until the frame, and dispatches them all on it.
+        <SeatState as Dispatch<WlTouch, TouchData, WinitState>>::event(
+            state, touch, event, data, conn, qh,
+        );
+
+        if !is_frame {
+            return;
+        }
+
+        // Mark the end of the frame on the windows which got its events.
+        let seat_state = match state.seats.get_mut(&touch.seat().id()) {
+            Some(seat_state) => seat_state,
+            None => return,
+        };
+        for window_id in seat_state.touch_frame_windows.drain(..) {
+            state.events_sink.push_window_event(
+                WindowEvent::Touch(Touch {
+                    device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
+                        DeviceId::from_seat(touch.seat()),
+                    )),
+                    phase: TouchPhase::Frame,
+                    location: PhysicalPosition::default(),
+                    force: None,
+                    id: 0,
+                }),
+                window_id,
+            );
+        }
+    }
+}
+
+/// Add the window to the ones which got events in the current touch frame.
+fn touch_frame_window(seat_state: &mut WinitSeatState, window_id: WindowId) {
+    if !seat_state.touch_frame_windows.contains(&window_id) {
+        seat_state.touch_frame_windows.push(window_id);
+    }
+}
+
 /// The state of the touch point.
 #[derive(Debug)]
 pub struct TouchPoint {
@@ -195,5 +253,3 @@ impl TouchDataExt for WlTouch {
             .seat()
     }
 }
-
-sctk::delegate_touch!(WinitState);
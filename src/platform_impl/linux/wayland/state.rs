@@ -1,41 +1,68 @@
 use std::cell::RefCell;
-use std::sync::atomic::Ordering;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
 
 use ahash::AHashMap;
 
 use sctk::reexports::calloop::LoopHandle;
 use sctk::reexports::client::backend::ObjectId;
 use sctk::reexports::client::globals::GlobalList;
+use sctk::reexports::client::protocol::wl_buffer::WlBuffer;
 use sctk::reexports::client::protocol::wl_output::WlOutput;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+use sctk::reexports::csd_frame::WindowState as XdgWindowState;
+
+use sctk::reexports::protocols::wp::cursor_shape::v1::client::wp_cursor_shape_manager_v1::WpCursorShapeManagerV1;
+use sctk::reexports::protocols::wp::linux_dmabuf::zv1::client::zwp_linux_buffer_params_v1::ZwpLinuxBufferParamsV1;
+use sctk::reexports::protocols::wp::linux_dmabuf::zv1::client::zwp_linux_dmabuf_feedback_v1::ZwpLinuxDmabufFeedbackV1;
+use sctk::reexports::protocols::wp::linux_dmabuf::zv1::client::zwp_linux_dmabuf_v1::ZwpLinuxDmabufV1;
+use sctk::reexports::protocols_wlr::layer_shell::v1::client::zwlr_layer_shell_v1::ZwlrLayerShellV1;
 
 use sctk::compositor::{CompositorHandler, CompositorState};
+use sctk::data_device_manager::DataDeviceManagerState;
+use sctk::dmabuf::{DmabufFeedback, DmabufHandler, DmabufState};
+use sctk::globals::ProvidesBoundGlobal;
 use sctk::output::{OutputHandler, OutputState};
 use sctk::registry::{ProvidesRegistryState, RegistryState};
 use sctk::seat::pointer::ThemedPointer;
 use sctk::seat::SeatState;
 use sctk::shell::wlr_layer::{LayerShell, LayerShellHandler, LayerSurface, LayerSurfaceConfigure};
+use sctk::shell::xdg::popup::{Popup, PopupConfigure, PopupHandler};
 use sctk::shell::xdg::window::{Window, WindowConfigure, WindowHandler};
-use sctk::shell::xdg::XdgShell;
+use sctk::shell::xdg::{XdgShell, XdgSurface};
 use sctk::shell::WaylandSurface;
 use sctk::shm::slot::SlotPool;
 use sctk::shm::{Shm, ShmHandler};
 use sctk::subcompositor::SubcompositorState;
 
+use crate::dpi::LogicalSize;
+use crate::error::ExternalError;
+use crate::event::WindowEvent;
+use crate::platform::wayland::CapturedFrame;
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
 use crate::platform_impl::wayland::output::MonitorHandle;
 use crate::platform_impl::wayland::seat::{
-    PointerConstraintsState, RelativePointerState, TextInputState, WinitPointerData,
+    ClipboardCallback, ClipboardSource, PointerConstraintsState, PointerGesturesState,
+    RelativePointerState, TabletManagerState, TextInputState, WinitPointerData,
     WinitPointerDataExt, WinitSeatState,
 };
+use crate::platform_impl::wayland::types::ext_foreign_toplevel_list::ForeignToplevelListState;
+use crate::platform_impl::wayland::types::ext_idle_notify::IdleNotifierState;
 use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
+use crate::platform_impl::wayland::types::wlr_foreign_toplevel_management::ForeignToplevelManagerState;
+use crate::platform_impl::wayland::types::wlr_gamma_control::GammaControlManagerState;
+use crate::platform_impl::wayland::types::wlr_output_power_management::OutputPowerManagerState;
+use crate::platform_impl::wayland::types::wlr_screencopy::{
+    CaptureCallback, ScreencopyManagerState,
+};
 use crate::platform_impl::wayland::types::wp_fractional_scaling::FractionalScalingManager;
+use crate::platform_impl::wayland::types::wp_presentation::PresentationManager;
+use crate::platform_impl::wayland::types::wp_single_pixel_buffer::SinglePixelBufferState;
 use crate::platform_impl::wayland::types::wp_viewporter::ViewporterState;
 use crate::platform_impl::wayland::types::xdg_activation::XdgActivationState;
 use crate::platform_impl::wayland::window::{WindowRequests, WindowState};
-use crate::platform_impl::wayland::{WaylandError, WindowId};
+use crate::platform_impl::wayland::{warn, WaylandError, WindowId};
 use crate::platform_impl::OsError;
 
 /// Winit's Wayland state.
@@ -65,7 +92,7 @@ pub struct WinitState {
     pub xdg_shell: XdgShell,
 
     /// The layer shell for layer surfaces
-    pub layer_shell: LayerShell,
+    pub layer_shell: Option<LayerShell>,
 
     /// The currently present windows.
     pub windows: RefCell<AHashMap<WindowId, Arc<Mutex<WindowState>>>>,
@@ -85,6 +112,9 @@ pub struct WinitState {
     /// Currently present cursor surfaces.
     pub pointer_surfaces: AHashMap<ObjectId, Arc<ThemedPointer<WinitPointerData>>>,
 
+    /// The bare surfaces created by the user, with their frame callback flags.
+    pub bare_surfaces: AHashMap<ObjectId, Weak<AtomicBool>>,
+
     /// The state of the text input on the client.
     pub text_input_state: Option<TextInputState>,
 
@@ -101,6 +131,42 @@ pub struct WinitState {
     /// Relative pointer.
     pub relative_pointer: Option<RelativePointerState>,
 
+    /// Pointer gestures to handle the touchpad swipes and pinches.
+    pub pointer_gestures: Option<PointerGesturesState>,
+
+    /// Tablet manager to handle the tablet pads.
+    pub tablet_manager: Option<TabletManagerState>,
+
+    /// Data device manager to handle the clipboard.
+    pub data_device_manager: Option<DataDeviceManagerState>,
+
+    /// The selection set by winit.
+    pub clipboard_source: Option<ClipboardSource>,
+
+    /// The clipboard reads completed on the event loop, to call back on the next iteration.
+    pub clipboard_reads: Vec<(ClipboardCallback, Option<Vec<u8>>)>,
+
+    /// Idle notifier to tell whether the user is idle.
+    pub idle_notifier: Option<IdleNotifierState>,
+
+    /// Foreign toplevel list to list the toplevels of all the clients.
+    pub foreign_toplevel_list: Option<ForeignToplevelListState>,
+
+    /// Foreign toplevel manager to list, activate and minimize the toplevels of all the clients.
+    pub foreign_toplevel_manager: Option<ForeignToplevelManagerState>,
+
+    /// Output power manager to turn the monitors on and off.
+    pub output_power_manager: Option<OutputPowerManagerState>,
+
+    /// Gamma control manager to set the gamma ramps of the monitors.
+    pub gamma_control_manager: Option<GammaControlManagerState>,
+
+    /// Screencopy manager to capture the contents of the monitors.
+    pub screencopy_manager: Option<ScreencopyManagerState>,
+
+    /// The monitor captures completed on the event loop, to call back on the next iteration.
+    pub captured_frames: Vec<(CaptureCallback, Result<CapturedFrame, ExternalError>)>,
+
     /// Pointer constraints to handle pointer locking and confining.
     pub pointer_constraints: Option<Arc<PointerConstraintsState>>,
 
@@ -110,15 +176,36 @@ pub struct WinitState {
     /// Fractional scaling manager.
     pub fractional_scaling_manager: Option<FractionalScalingManager>,
 
+    /// Single pixel buffer manager for the input only windows.
+    pub single_pixel_buffer: Option<SinglePixelBufferState>,
+
     /// KWin blur manager.
     pub kwin_blur_manager: Option<KWinBlurManager>,
 
+    /// Presentation time manager to get the timing of the presented frames.
+    pub presentation: Option<PresentationManager>,
+
+    /// Whether the compositor draws the named cursors with the cursor shape protocol.
+    pub cursor_shape: bool,
+
+    /// The dmabuf state to get the buffer allocation feedback.
+    pub dmabuf_state: DmabufState,
+
     /// Loop handle to re-register event sources, such as keyboard repeat.
     pub loop_handle: LoopHandle<'static, Self>,
 
     /// Whether we have dispatched events to the user thus we want to
     /// send `AboutToWait` and normally wakeup the user.
     pub dispatched_events: bool,
+
+    /// Whether the keys are delivered raw, without the xkb processing.
+    pub raw_keyboard: bool,
+
+    /// Whether every configure is delivered as its own resize.
+    pub raw_configures: bool,
+
+    /// The namespace of the layer shell windows created without one.
+    pub default_layer_namespace: Option<String>,
 }
 
 impl WinitState {
@@ -126,10 +213,12 @@ impl WinitState {
         globals: &GlobalList,
         queue_handle: &QueueHandle<Self>,
         loop_handle: LoopHandle<'static, WinitState>,
+        tablets: bool,
+        foreign_toplevels: bool,
     ) -> Result<Self, OsError> {
         let registry_state = RegistryState::new(globals);
-        let compositor_state =
-            CompositorState::bind(globals, queue_handle).map_err(WaylandError::Bind)?;
+        let compositor_state = CompositorState::bind(globals, queue_handle)
+            .map_err(|error| WaylandError::Bind("wl_compositor", error))?;
         let subcompositor_state = match SubcompositorState::bind(
             compositor_state.wl_compositor().clone(),
             globals,
@@ -137,30 +226,41 @@ impl WinitState {
         ) {
             Ok(c) => Some(c),
             Err(e) => {
-                log::warn!("Subcompositor protocol not available, ignoring CSD: {e:?}");
+                warn!("Subcompositor protocol not available, ignoring CSD: {e:?}");
                 None
             }
         };
 
         let output_state = OutputState::new(globals, queue_handle);
-        let monitors = output_state.outputs().map(MonitorHandle::new).collect();
+        // NOTE: the monitors are added from `new_output` once the compositor sent their info.
 
         let seat_state = SeatState::new(globals, queue_handle);
 
+        // NOTE: the compositors stop emulating the pointer for the tablet tools once the tablet
+        // seat is created, so only bind the tablets when the user asked for them.
+        let tablet_manager = tablets
+            .then(|| TabletManagerState::new(globals, queue_handle).ok())
+            .flatten();
+        let data_device_manager = DataDeviceManagerState::bind(globals, queue_handle).ok();
         let mut seats = AHashMap::default();
         for seat in seat_state.seats() {
-            seats.insert(seat.id(), WinitSeatState::new());
+            let tablet_seat = tablet_manager
+                .as_ref()
+                .map(|manager| manager.get_tablet_seat(&seat, queue_handle));
+            let data_device = data_device_manager
+                .as_ref()
+                .map(|manager| manager.get_data_device(queue_handle, &seat));
+            seats.insert(seat.id(), WinitSeatState::new(tablet_seat, data_device));
         }
 
-        let (viewporter_state, fractional_scaling_manager) =
-            if let Ok(fsm) = FractionalScalingManager::new(globals, queue_handle) {
-                (ViewporterState::new(globals, queue_handle).ok(), Some(fsm))
-            } else {
-                (None, None)
-            };
+        // The viewport is only used with fractional scaling and for the input only windows.
+        let fractional_scaling_manager = FractionalScalingManager::new(globals, queue_handle).ok();
+        let viewporter_state = ViewporterState::new(globals, queue_handle).ok();
 
-        let shm = Shm::bind(globals, queue_handle).map_err(WaylandError::Bind)?;
+        let shm = Shm::bind(globals, queue_handle)
+            .map_err(|error| WaylandError::Bind("wl_shm", error))?;
         let custom_cursor_pool = Arc::new(Mutex::new(SlotPool::new(2, &shm).unwrap()));
+        let screencopy_manager = ScreencopyManagerState::new(globals, queue_handle, shm.wl_shm());
 
         Ok(Self {
             registry_state,
@@ -171,10 +271,11 @@ impl WinitState {
             shm,
             custom_cursor_pool,
 
-            xdg_shell: XdgShell::bind(globals, queue_handle).map_err(WaylandError::Bind)?,
+            xdg_shell: XdgShell::bind(globals, queue_handle)
+                .map_err(|error| WaylandError::Bind("xdg_wm_base", error))?,
             xdg_activation: XdgActivationState::bind(globals, queue_handle).ok(),
 
-            layer_shell: LayerShell::bind(globals, queue_handle).map_err(WaylandError::Bind)?,
+            layer_shell: LayerShell::bind(globals, queue_handle).ok(),
 
             windows: Default::default(),
             window_requests: Default::default(),
@@ -182,31 +283,146 @@ impl WinitState {
             window_events_sink: Default::default(),
             viewporter_state,
             fractional_scaling_manager,
+            single_pixel_buffer: SinglePixelBufferState::new(globals, queue_handle).ok(),
             kwin_blur_manager: KWinBlurManager::new(globals, queue_handle).ok(),
+            presentation: PresentationManager::new(globals, queue_handle).ok(),
+            cursor_shape: globals.contents().with_list(|list| {
+                list.iter()
+                    .any(|global| global.interface == WpCursorShapeManagerV1::interface().name)
+            }),
+            dmabuf_state: DmabufState::new(globals, queue_handle),
 
             seats,
             text_input_state: TextInputState::new(globals, queue_handle).ok(),
 
             relative_pointer: RelativePointerState::new(globals, queue_handle).ok(),
+            pointer_gestures: PointerGesturesState::new(globals, queue_handle).ok(),
+            tablet_manager,
+            data_device_manager,
+            clipboard_source: None,
+            clipboard_reads: Vec::new(),
+            idle_notifier: IdleNotifierState::new(globals, queue_handle).ok(),
+            foreign_toplevel_list: foreign_toplevels
+                .then(|| ForeignToplevelListState::new(globals, queue_handle).ok())
+                .flatten(),
+            foreign_toplevel_manager: foreign_toplevels
+                .then(|| ForeignToplevelManagerState::new(globals, queue_handle).ok())
+                .flatten(),
+            output_power_manager: OutputPowerManagerState::new(globals, queue_handle).ok(),
+            gamma_control_manager: GammaControlManagerState::new(globals, queue_handle).ok(),
+            screencopy_manager: screencopy_manager.ok(),
+            captured_frames: Vec::new(),
             pointer_constraints: PointerConstraintsState::new(globals, queue_handle)
                 .map(Arc::new)
                 .ok(),
             pointer_surfaces: Default::default(),
+            bare_surfaces: Default::default(),
 
-            monitors: Arc::new(Mutex::new(monitors)),
+            monitors: Default::default(),
             events_sink: EventSink::new(),
             loop_handle,
             // Make it true by default.
             dispatched_events: true,
+            raw_keyboard: false,
+            raw_configures: false,
+            default_layer_namespace: None,
         })
     }
 
+    /// Whether the compositor sent the info of all the bound outputs.
+    pub fn outputs_ready(&self) -> bool {
+        let monitors = self.monitors.lock().unwrap();
+        self.output_state
+            .outputs()
+            .all(|output| monitors.iter().any(|monitor| monitor.proxy == output))
+    }
+
+    /// The version of the bound global with the given interface name.
+    pub fn protocol_version(&self, interface: &str) -> Option<u32> {
+        fn version<I: Proxy>(proxy: &I) -> (&'static str, u32) {
+            (I::interface().name, proxy.version())
+        }
+
+        let layer_shell = self.layer_shell.as_ref().and_then(|layer_shell| {
+            ProvidesBoundGlobal::<ZwlrLayerShellV1, 4>::bound_global(layer_shell).ok()
+        });
+        let bound = [
+            Some(version(self.compositor_state.wl_compositor())),
+            Some(version(self.shm.wl_shm())),
+            Some(version(self.xdg_shell.xdg_wm_base())),
+            layer_shell.as_ref().map(version),
+            self.seat_state.seats().next().as_ref().map(version),
+            self.output_state.outputs().next().as_ref().map(version),
+            self.xdg_activation
+                .as_ref()
+                .map(|state| version(state.global())),
+            self.relative_pointer.as_deref().map(version),
+            self.pointer_gestures.as_deref().map(version),
+            self.tablet_manager.as_deref().map(version),
+            self.data_device_manager
+                .as_ref()
+                .map(|state| version(state.data_device_manager())),
+            self.idle_notifier
+                .as_ref()
+                .map(|state| version(state.global())),
+            self.foreign_toplevel_list
+                .as_ref()
+                .map(|state| version(state.global())),
+            self.foreign_toplevel_manager
+                .as_ref()
+                .map(|state| version(state.global())),
+            self.output_power_manager
+                .as_ref()
+                .map(|state| version(state.global())),
+            self.gamma_control_manager
+                .as_ref()
+                .map(|state| version(state.global())),
+            self.screencopy_manager
+                .as_ref()
+                .map(|state| version(state.global())),
+            self.pointer_constraints
+                .as_deref()
+                .map(|state| version(&**state)),
+            self.text_input_state.as_deref().map(version),
+            self.viewporter_state
+                .as_ref()
+                .map(|state| version(state.global())),
+            self.fractional_scaling_manager
+                .as_ref()
+                .map(|state| version(state.global())),
+            self.single_pixel_buffer
+                .as_ref()
+                .map(|state| version(state.global())),
+            self.kwin_blur_manager
+                .as_ref()
+                .map(|state| version(state.global())),
+            self.presentation
+                .as_ref()
+                .map(|state| version(state.global())),
+            self.dmabuf_state
+                .version()
+                .map(|version| (ZwpLinuxDmabufV1::interface().name, version)),
+        ];
+
+        bound
+            .into_iter()
+            .flatten()
+            .find(|(name, _)| *name == interface)
+            .map(|(_, version)| version)
+    }
+
     pub fn scale_factor_changed(
         &mut self,
         surface: &WlSurface,
         scale_factor: f64,
         is_legacy: bool,
     ) {
+        // Guard against the broken compositors, since zero scale breaks all the size math.
+        if !scale_factor.is_normal() || scale_factor < 0. {
+            warn!("Ignoring invalid scale factor {scale_factor}");
+            return;
+        }
+
         // Check if the cursor surface.
         let window_id = super::make_wid(surface);
 
@@ -216,6 +432,13 @@ impl WinitState {
                 return;
             }
 
+            // The scale forced by the user is kept regardless of the compositor.
+            let mut window = window.lock().unwrap();
+            if window.has_forced_scale() || !window.compositor_scale_changed(scale_factor) {
+                return;
+            }
+            drop(window);
+
             // The scale factor change is for the window.
             let pos = if let Some(pos) = self
                 .window_compositor_updates
@@ -229,8 +452,6 @@ impl WinitState {
                 self.window_compositor_updates.len() - 1
             };
 
-            // Update the scale factor right away.
-            window.lock().unwrap().set_scale_factor(scale_factor);
             self.window_compositor_updates[pos].scale_changed = true;
         } else if let Some(pointer) = self.pointer_surfaces.get(&surface.id()) {
             // Get the window, where the pointer resides right now.
@@ -246,6 +467,40 @@ impl WinitState {
     }
 
     pub fn queue_close(updates: &mut Vec<WindowCompositorUpdate>, window_id: WindowId) {
+        Self::compositor_update(updates, window_id).close_window = true;
+    }
+
+    /// Update the scale of the windows following the user's scale preference.
+    pub fn apply_scale_preferences(&mut self) {
+        for (window_id, window) in self.windows.get_mut() {
+            if window.lock().unwrap().apply_scale_preference() {
+                Self::compositor_update(&mut self.window_compositor_updates, *window_id)
+                    .scale_changed = true;
+            }
+        }
+    }
+
+    /// Record the configure of the window, which changed its size from `previous_size` when
+    /// `resized`.
+    fn record_configure(&mut self, pos: usize, resized: bool, previous_size: LogicalSize<u32>) {
+        let update = &mut self.window_compositor_updates[pos];
+
+        // Keep the size set by the previous configure of this dispatch instead of coalescing it.
+        if resized && update.resized && self.raw_configures {
+            update.coalesced_sizes.push(previous_size);
+        }
+        update.resized |= resized;
+    }
+
+    /// Queue the notification that the compositor destroyed the surface of the window.
+    pub fn queue_surface_destroyed(updates: &mut Vec<WindowCompositorUpdate>, window_id: WindowId) {
+        Self::compositor_update(updates, window_id).surface_destroyed = true;
+    }
+
+    fn compositor_update(
+        updates: &mut Vec<WindowCompositorUpdate>,
+        window_id: WindowId,
+    ) -> &mut WindowCompositorUpdate {
         let pos = if let Some(pos) = updates
             .iter()
             .position(|update| update.window_id == window_id)
@@ -256,7 +511,7 @@ impl WinitState {
             updates.len() - 1
         };
 
-        updates[pos].close_window = true;
+        &mut updates[pos]
     }
 }
 
@@ -278,8 +533,12 @@ impl WindowHandler for WinitState {
         _: &QueueHandle<Self>,
         window: &Window,
         configure: WindowConfigure,
-        _serial: u32,
+        serial: u32,
     ) {
+        let xdg_surface = window.xdg_surface().id();
+        trace_protocol!("<- {xdg_surface}.configure({serial}) {configure:?}");
+        trace_protocol!("-> {xdg_surface}.ack_configure({serial})");
+
         let window_id = super::make_wid(window.wl_surface());
 
         let pos = if let Some(pos) = self
@@ -294,18 +553,43 @@ impl WindowHandler for WinitState {
             self.window_compositor_updates.len() - 1
         };
 
-        // Populate the configure to the window.
-        self.window_compositor_updates[pos].resized |= self
+        let mut window = self
             .windows
             .get_mut()
             .get_mut(&window_id)
             .expect("got configure for dead window.")
             .lock()
-            .unwrap()
-            .configure_xdg(
-                configure,
-                &self.shm,
-                &self.subcompositor_state);
+            .unwrap();
+
+        // Populate the configure to the window.
+        let was_suspended = window.is_suspended();
+        let was_configured = window.is_configured();
+        let previous_state = window.toplevel_state() & STATE_CHANGE_EVENT_STATES;
+        let previous_size = window.inner_size();
+        let resized = window.configure_xdg(configure, &self.shm, &self.subcompositor_state);
+        window.commit_input_buffer();
+        let suspended = window.is_suspended();
+        let new_state = window.toplevel_state() & STATE_CHANGE_EVENT_STATES;
+        let (maximized, fullscreen) = (window.is_maximized(), window.is_fullscreen());
+        drop(window);
+        self.record_configure(pos, resized, previous_size);
+
+        // The compositor suspends the windows which aren't visible, e.g. minimized ones.
+        if was_suspended != suspended {
+            self.events_sink
+                .push_window_event(WindowEvent::Occluded(suspended), window_id);
+        }
+
+        // The compositor could maximize or fullscreen the window on its own.
+        if was_configured && previous_state != new_state {
+            self.events_sink.push_window_event(
+                WindowEvent::WindowStateChanged {
+                    maximized,
+                    fullscreen,
+                },
+                window_id,
+            );
+        }
 
         // NOTE: configure demands wl_surface::commit, however winit doesn't commit on behalf of the
         // users, since it can break a lot of things, thus it'll ask users to redraw instead.
@@ -325,6 +609,7 @@ impl LayerShellHandler for WinitState {
     fn closed(&mut self, _: &Connection, _: &QueueHandle<Self>, layer: &LayerSurface) {
         let window_id = super::make_wid(layer.wl_surface());
         Self::queue_close(&mut self.window_compositor_updates, window_id);
+        Self::queue_surface_destroyed(&mut self.window_compositor_updates, window_id);
     }
 
     fn configure(
@@ -333,8 +618,12 @@ impl LayerShellHandler for WinitState {
         _: &QueueHandle<Self>,
         layer: &LayerSurface,
         configure: LayerSurfaceConfigure,
-        _serial: u32,
+        serial: u32,
     ) {
+        let surface = layer.wl_surface().id();
+        trace_protocol!("<- zwlr_layer_surface_v1.configure({serial}) of {surface} {configure:?}");
+        trace_protocol!("-> zwlr_layer_surface_v1.ack_configure({serial}) of {surface}");
+
         let window_id = super::make_wid(layer.wl_surface());
 
         let pos = if let Some(pos) = self
@@ -350,17 +639,127 @@ impl LayerShellHandler for WinitState {
         };
 
         // Populate the configure to the window.
-        self.window_compositor_updates[pos].resized |= self
+        let mut window = self
             .windows
             .get_mut()
             .get_mut(&window_id)
             .expect("got configure for dead window.")
             .lock()
+            .unwrap();
+        let previous_size = window.inner_size();
+        let resized = window.configure_layer(configure);
+        window.commit_input_buffer();
+        drop(window);
+        self.record_configure(pos, resized, previous_size);
+    }
+}
+
+impl PopupHandler for WinitState {
+    fn configure(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        popup: &Popup,
+        configure: PopupConfigure,
+    ) {
+        let (xdg_surface, serial) = (popup.xdg_surface().id(), configure.serial);
+        trace_protocol!("<- {xdg_surface}.configure({serial}) {configure:?}");
+        trace_protocol!("-> {xdg_surface}.ack_configure({serial})");
+
+        let window_id = super::make_wid(popup.wl_surface());
+
+        let pos = if let Some(pos) = self
+            .window_compositor_updates
+            .iter()
+            .position(|update| update.window_id == window_id)
+        {
+            pos
+        } else {
+            self.window_compositor_updates
+                .push(WindowCompositorUpdate::new(window_id));
+            self.window_compositor_updates.len() - 1
+        };
+
+        // Populate the configure to the window.
+        let mut window = self
+            .windows
+            .get_mut()
+            .get_mut(&window_id)
+            .expect("got configure for dead window.")
+            .lock()
+            .unwrap();
+        let previous_size = window.inner_size();
+        let resized = window.configure_popup(configure);
+        window.commit_input_buffer();
+        drop(window);
+        self.record_configure(pos, resized, previous_size);
+
+        // The popup is mapped only once the user draws into it.
+        self.window_requests
+            .get_mut()
+            .get(&window_id)
             .unwrap()
-            .configure_layer(configure);
+            .redraw_requested
+            .store(true, Ordering::Relaxed);
+
+        self.dispatched_events = true;
+    }
+
+    fn done(&mut self, _: &Connection, _: &QueueHandle<Self>, popup: &Popup) {
+        trace_protocol!("<- {}.popup_done()", popup.xdg_popup().id());
+
+        // The popup was dismissed, e.g. by clicking outside of it while it had a grab.
+        let window_id = super::make_wid(popup.wl_surface());
+        Self::queue_close(&mut self.window_compositor_updates, window_id);
+        Self::queue_surface_destroyed(&mut self.window_compositor_updates, window_id);
     }
 }
 
+impl DmabufHandler for WinitState {
+    fn dmabuf_state(&mut self) -> &mut DmabufState {
+        &mut self.dmabuf_state
+    }
+
+    fn dmabuf_feedback(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        proxy: &ZwpLinuxDmabufFeedbackV1,
+        feedback: DmabufFeedback,
+    ) {
+        // The tranches are sorted by preference, with the main device as a fallback.
+        let device = feedback
+            .tranches()
+            .first()
+            .map(|tranche| tranche.device)
+            .unwrap_or(feedback.main_device());
+
+        if let Some(window) = self
+            .windows
+            .get_mut()
+            .values()
+            .find(|window| window.lock().unwrap().has_dmabuf_feedback(proxy))
+        {
+            window.lock().unwrap().dmabuf_device = Some(device);
+        }
+    }
+
+    // NOTE: winit doesn't create dmabuf buffers on its own.
+
+    fn created(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &ZwpLinuxBufferParamsV1,
+        _: WlBuffer,
+    ) {
+    }
+
+    fn failed(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &ZwpLinuxBufferParamsV1) {}
+
+    fn released(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlBuffer) {}
+}
+
 impl OutputHandler for WinitState {
     fn output_state(&mut self) -> &mut OutputState {
         &mut self.output_state
@@ -385,6 +784,10 @@ impl OutputHandler for WinitState {
 
     fn output_destroyed(&mut self, _: &Connection, _: &QueueHandle<Self>, removed: WlOutput) {
         let mut monitors = self.monitors.lock().unwrap();
+        if let Some(gamma_control_manager) = self.gamma_control_manager.as_mut() {
+            gamma_control_manager.remove_output(&removed.id());
+        }
+
         let removed = MonitorHandle::new(removed);
         if let Some(pos) = monitors.iter().position(|output| output == &removed) {
             monitors.remove(pos);
@@ -400,7 +803,8 @@ impl CompositorHandler for WinitState {
         _: &wayland_client::protocol::wl_surface::WlSurface,
         _: wayland_client::protocol::wl_output::Transform,
     ) {
-        // TODO(kchibisov) we need to expose it somehow in winit.
+        // NOTE: the transform is stored on the surface data, and queried by the user when
+        // rendering a frame.
     }
 
     fn scale_factor_changed(
@@ -410,14 +814,30 @@ impl CompositorHandler for WinitState {
         surface: &WlSurface,
         scale_factor: i32,
     ) {
+        // NOTE: with `wl_compositor` v6 this is the `preferred_buffer_scale` sent by the
+        // compositor as soon as the surface moves, otherwise it's guessed from the scale of the
+        // outputs the surface entered.
         self.scale_factor_changed(surface, scale_factor as f64, true)
     }
 
-    fn frame(&mut self, _: &Connection, _: &QueueHandle<Self>, surface: &WlSurface, _: u32) {
+    fn frame(&mut self, _: &Connection, _: &QueueHandle<Self>, surface: &WlSurface, time: u32) {
+        trace_protocol!("<- wl_callback.done({time}) of {}", surface.id());
+
         let window_id = super::make_wid(surface);
         let window = match self.windows.get_mut().get(&window_id) {
             Some(window) => window,
-            None => return,
+            None => {
+                // The frame callback could be for a bare surface of the user.
+                if let Some(frame_done) = self
+                    .bare_surfaces
+                    .get(&surface.id())
+                    .and_then(Weak::upgrade)
+                {
+                    frame_done.store(true, Ordering::Relaxed);
+                    self.dispatched_events = true;
+                }
+                return;
+            }
         };
 
         // In case we have a redraw requested we must indicate the wake up.
@@ -444,8 +864,12 @@ impl ProvidesRegistryState for WinitState {
     sctk::registry_handlers![OutputState, SeatState];
 }
 
+/// The states of the toplevel sending `WindowEvent::WindowStateChanged` when they change.
+const STATE_CHANGE_EVENT_STATES: XdgWindowState =
+    XdgWindowState::MAXIMIZED.union(XdgWindowState::FULLSCREEN);
+
 // The window update coming from the compositor.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct WindowCompositorUpdate {
     /// The id of the window this updates belongs to.
     pub window_id: WindowId,
@@ -456,8 +880,14 @@ pub struct WindowCompositorUpdate {
     /// New scale factor.
     pub scale_changed: bool,
 
+    /// The sizes of the configures coalesced into this update, when the configures are raw.
+    pub coalesced_sizes: Vec<LogicalSize<u32>>,
+
     /// Close the window.
     pub close_window: bool,
+
+    /// The compositor destroyed the surface of the window.
+    pub surface_destroyed: bool,
 }
 
 impl WindowCompositorUpdate {
@@ -466,7 +896,9 @@ impl WindowCompositorUpdate {
             window_id,
             resized: false,
             scale_changed: false,
+            coalesced_sizes: Vec::new(),
             close_window: false,
+            surface_destroyed: false,
         }
     }
 }
@@ -478,4 +910,6 @@ sctk::delegate_registry!(WinitState);
 sctk::delegate_shm!(WinitState);
 sctk::delegate_xdg_shell!(WinitState);
 sctk::delegate_xdg_window!(WinitState);
+sctk::delegate_xdg_popup!(WinitState);
+sctk::delegate_dmabuf!(WinitState);
 sctk::delegate_layer!(WinitState);
@@ -0,0 +1,88 @@
+//! Bare surfaces created on behalf of the user.
+
+use std::ffi::c_void;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use sctk::compositor::SurfaceData;
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{Proxy, QueueHandle};
+
+use super::output::MonitorHandle;
+use super::state::WinitState;
+
+/// A `wl_surface` which isn't a window, but still uses winit's connection and event loop.
+#[derive(Debug)]
+pub struct BareSurface {
+    surface: WlSurface,
+
+    queue_handle: QueueHandle<WinitState>,
+
+    /// Whether the frame callback was received, set by the event loop.
+    frame_done: Arc<AtomicBool>,
+
+    /// Wake the event loop up to forget the surface once it's dropped.
+    event_loop_awakener: calloop::ping::Ping,
+}
+
+impl BareSurface {
+    /// Create the surface, registering it on the state to get the frame callbacks.
+    pub fn new(
+        state: &mut WinitState,
+        queue_handle: &QueueHandle<WinitState>,
+        event_loop_awakener: calloop::ping::Ping,
+    ) -> Self {
+        let surface = state.compositor_state.create_surface(queue_handle);
+        let frame_done = Arc::new(AtomicBool::new(false));
+
+        state
+            .bare_surfaces
+            .insert(surface.id(), Arc::downgrade(&frame_done));
+
+        Self {
+            surface,
+            queue_handle: queue_handle.clone(),
+            frame_done,
+            event_loop_awakener,
+        }
+    }
+
+    #[inline]
+    pub fn wl_surface_ptr(&self) -> NonNull<c_void> {
+        NonNull::new(self.surface.id().as_ptr() as *mut _).expect("wl_surface will never be null")
+    }
+
+    #[inline]
+    pub fn request_frame(&self) {
+        trace_protocol!("-> {}.frame()", self.surface.id());
+        self.surface.frame(&self.queue_handle, self.surface.clone());
+    }
+
+    #[inline]
+    pub fn take_frame_done(&self) -> bool {
+        self.frame_done.swap(false, Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub fn monitors(&self) -> Vec<MonitorHandle> {
+        self.surface
+            .data::<SurfaceData>()
+            .map(|data| data.outputs().map(MonitorHandle::new).collect())
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn scale_factor(&self) -> i32 {
+        self.surface
+            .data::<SurfaceData>()
+            .map_or(1, |data| data.scale_factor())
+    }
+}
+
+impl Drop for BareSurface {
+    fn drop(&mut self) {
+        self.surface.destroy();
+        self.event_loop_awakener.ping();
+    }
+}
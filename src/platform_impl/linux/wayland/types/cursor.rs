@@ -1,9 +1,16 @@
+use std::env;
+use std::sync::Arc;
+
+use ahash::AHashMap;
 use cursor_icon::CursorIcon;
 
-use sctk::reexports::client::protocol::wl_shm::Format;
+use sctk::reexports::client::protocol::wl_shm::{Format, WlShm};
+use sctk::reexports::client::Connection;
 use sctk::shm::slot::{Buffer, SlotPool};
+use wayland_cursor::{CursorImageBuffer, CursorTheme as XCursorTheme};
 
 use crate::cursor::CursorImage;
+use crate::platform_impl::wayland::warn;
 
 #[derive(Debug)]
 pub enum SelectedCursor {
@@ -17,27 +24,59 @@ impl Default for SelectedCursor {
     }
 }
 
+/// The closest icon available with the cursor shape protocol.
+///
+/// The protocol lacks some of the icons, which would otherwise fall back to the default arrow.
+pub fn closest_cursor_shape(icon: CursorIcon) -> CursorIcon {
+    match icon {
+        CursorIcon::DndAsk => CursorIcon::Copy,
+        CursorIcon::AllResize => CursorIcon::Move,
+        icon => icon,
+    }
+}
+
 #[derive(Debug)]
 pub struct CustomCursor {
-    pub buffer: Buffer,
-    pub w: i32,
-    pub h: i32,
-    pub hotspot_x: i32,
-    pub hotspot_y: i32,
+    image: Arc<CursorImage>,
+    /// The buffers of the image upscaled for each scale of the cursor surfaces.
+    buffers: AHashMap<i32, Buffer>,
 }
 
 impl CustomCursor {
-    pub(crate) fn new(pool: &mut SlotPool, image: &CursorImage) -> Self {
-        let (buffer, canvas) = pool
-            .create_buffer(
-                image.width as i32,
-                image.height as i32,
-                4 * (image.width as i32),
-                Format::Argb8888,
-            )
-            .unwrap();
+    pub(crate) fn new(image: Arc<CursorImage>) -> Self {
+        Self {
+            image,
+            buffers: AHashMap::new(),
+        }
+    }
+
+    /// The buffer of the image for the scale, with its size and hotspot in buffer pixels.
+    pub fn buffer(&mut self, pool: &mut SlotPool, scale: i32) -> (&Buffer, (i32, i32), (i32, i32)) {
+        let image = &self.image;
+        let (w, h) = (image.width as i32 * scale, image.height as i32 * scale);
+        let buffer = self.buffers.entry(scale).or_insert_with(|| {
+            let (buffer, canvas) = pool.create_buffer(w, h, 4 * w, Format::Argb8888).unwrap();
+            write_scaled_argb(canvas, image, scale as usize);
+            buffer
+        });
+
+        let hotspot = (
+            image.hotspot_x as i32 * scale,
+            image.hotspot_y as i32 * scale,
+        );
+        (buffer, (w, h), hotspot)
+    }
+}
+
+/// Write the image upscaled by `scale` into the canvas, in the premultiplied ARGB8888 format.
+fn write_scaled_argb(canvas: &mut [u8], image: &CursorImage, scale: usize) {
+    let row_len = 4 * image.width as usize * scale;
+    for (y, row) in canvas.chunks_exact_mut(row_len).enumerate() {
+        let image_row = y / scale * image.width as usize;
+        for (x, canvas_chunk) in row.chunks_exact_mut(4).enumerate() {
+            let i = 4 * (image_row + x / scale);
+            let rgba = &image.rgba[i..i + 4];
 
-        for (canvas_chunk, rgba) in canvas.chunks_exact_mut(4).zip(image.rgba.chunks_exact(4)) {
             // Alpha in buffer is premultiplied.
             let alpha = rgba[3] as f32 / 255.;
             let r = (rgba[0] as f32 * alpha) as u32;
@@ -47,13 +86,80 @@ impl CustomCursor {
             let array: &mut [u8; 4] = canvas_chunk.try_into().unwrap();
             *array = color.to_le_bytes();
         }
+    }
+}
+
+/// The cursor theme requested by the user instead of the system one.
+#[derive(Debug)]
+pub struct CursorTheme {
+    name: String,
+    size: u32,
+    /// The theme loaded for each scale.
+    themes: AHashMap<u32, XCursorTheme>,
+}
+
+impl CursorTheme {
+    /// Create the theme, using the system theme name when `name` is `None`.
+    pub fn new(name: Option<String>, size: u32) -> Self {
+        let name = name
+            .or_else(|| env::var("XCURSOR_THEME").ok())
+            .unwrap_or_else(|| "default".into());
+        Self {
+            name,
+            size: size.max(1),
+            themes: AHashMap::new(),
+        }
+    }
 
-        CustomCursor {
-            buffer,
-            w: image.width as i32,
-            h: image.height as i32,
-            hotspot_x: image.hotspot_x as i32,
-            hotspot_y: image.hotspot_y as i32,
+    /// Get the first image of the cursor for the given scale, loading the theme if needed.
+    pub fn get_cursor(
+        &mut self,
+        connection: &Connection,
+        shm: &WlShm,
+        icon: CursorIcon,
+        scale: u32,
+    ) -> Option<&CursorImageBuffer> {
+        if !self.themes.contains_key(&scale) {
+            let theme = XCursorTheme::load_from_name(
+                connection,
+                shm.clone(),
+                &self.name,
+                self.size * scale,
+            )
+            .map_err(|err| warn!("failed to load the cursor theme {}: {err}", self.name))
+            .ok()?;
+            self.themes.insert(scale, theme);
         }
+
+        let theme = self.themes.get_mut(&scale).unwrap();
+        // Look the alternative names up as well, since the themes don't agree on the names.
+        let name = std::iter::once(icon.name())
+            .chain(icon.alt_names().iter().copied())
+            .find(|name| theme.get_cursor(name).is_some())?;
+        theme.get_cursor(name).map(|cursor| &cursor[0])
+    }
+
+    /// Whether the theme has the cursor, under its name or one of its alternative names.
+    pub fn has_cursor(&mut self, connection: &Connection, shm: &WlShm, icon: CursorIcon) -> bool {
+        self.get_cursor(connection, shm, icon, 1).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upscales_custom_cursors() {
+        let rgba = vec![255, 0, 0, 255, 0, 0, 255, 0];
+        let image = CursorImage::from_rgba(rgba, 2, 1, 1, 0).unwrap();
+
+        let mut canvas = vec![0; 4 * 4 * 2];
+        write_scaled_argb(&mut canvas, &image, 2);
+
+        let red = 0xffff0000u32.to_le_bytes();
+        let transparent = [0; 4];
+        let row = [red, red, transparent, transparent].concat();
+        assert_eq!(canvas, [row.clone(), row].concat());
     }
 }
@@ -0,0 +1,169 @@
+//! Handling of the foreign toplevel list, which lists the toplevels of all the clients.
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{
+    delegate_dispatch, event_created_child, Connection, Proxy, QueueHandle,
+};
+use sctk::reexports::protocols::ext::foreign_toplevel_list::v1::client::ext_foreign_toplevel_handle_v1::{
+    Event as HandleEvent, ExtForeignToplevelHandleV1,
+};
+use sctk::reexports::protocols::ext::foreign_toplevel_list::v1::client::ext_foreign_toplevel_list_v1::{
+    self, Event as ListEvent, ExtForeignToplevelListV1,
+};
+
+use sctk::globals::GlobalData;
+
+use crate::event::Event;
+use crate::platform::wayland::ForeignToplevel;
+use crate::platform_impl::wayland::state::WinitState;
+
+/// Foreign toplevel list, tracking the toplevels of all the clients.
+#[derive(Debug)]
+pub struct ForeignToplevelListState {
+    list: ExtForeignToplevelListV1,
+
+    /// The toplevels in the order the compositor announced them.
+    toplevels: Vec<Toplevel>,
+}
+
+#[derive(Debug)]
+struct Toplevel {
+    handle: ExtForeignToplevelHandleV1,
+
+    /// The state as of the latest `done`, or `None` before the first one.
+    current: Option<ForeignToplevel>,
+
+    /// The state being sent by the compositor until the next `done`.
+    pending: ForeignToplevel,
+}
+
+impl ForeignToplevelListState {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let list = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self {
+            list,
+            toplevels: Vec::new(),
+        })
+    }
+
+    pub fn global(&self) -> &ExtForeignToplevelListV1 {
+        &self.list
+    }
+
+    /// The toplevels which received their initial state.
+    pub fn toplevels(&self) -> Vec<ForeignToplevel> {
+        self.toplevels
+            .iter()
+            .filter_map(|toplevel| toplevel.current.clone())
+            .collect()
+    }
+
+    fn toplevel_mut(&mut self, handle: &ExtForeignToplevelHandleV1) -> Option<&mut Toplevel> {
+        self.toplevels
+            .iter_mut()
+            .find(|toplevel| &toplevel.handle == handle)
+    }
+}
+
+impl Drop for ForeignToplevelListState {
+    fn drop(&mut self) {
+        for toplevel in self.toplevels.drain(..) {
+            toplevel.handle.destroy();
+        }
+
+        self.list.destroy();
+    }
+}
+
+impl Dispatch<ExtForeignToplevelListV1, GlobalData, WinitState> for ForeignToplevelListState {
+    fn event(
+        state: &mut WinitState,
+        _: &ExtForeignToplevelListV1,
+        event: <ExtForeignToplevelListV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        match event {
+            ListEvent::Toplevel { toplevel: handle } => {
+                let Some(foreign_toplevel_list) = state.foreign_toplevel_list.as_mut() else {
+                    handle.destroy();
+                    return;
+                };
+
+                foreign_toplevel_list.toplevels.push(Toplevel {
+                    handle,
+                    current: None,
+                    pending: ForeignToplevel::default(),
+                });
+            }
+            ListEvent::Finished => {
+                // The compositor stopped sending the toplevels, so the list is useless.
+                let foreign_toplevel_list = state.foreign_toplevel_list.take();
+                if foreign_toplevel_list.is_some_and(|list| !list.toplevels().is_empty()) {
+                    state.events_sink.push_event(Event::ForeignToplevelsChanged);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    event_created_child!(WinitState, ExtForeignToplevelListV1, [
+        ext_foreign_toplevel_list_v1::EVT_TOPLEVEL_OPCODE => (ExtForeignToplevelHandleV1, GlobalData)
+    ]);
+}
+
+impl Dispatch<ExtForeignToplevelHandleV1, GlobalData, WinitState> for ForeignToplevelListState {
+    fn event(
+        state: &mut WinitState,
+        handle: &ExtForeignToplevelHandleV1,
+        event: <ExtForeignToplevelHandleV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let Some(foreign_toplevel_list) = state.foreign_toplevel_list.as_mut() else {
+            return;
+        };
+
+        if let HandleEvent::Closed = event {
+            let Some(index) = foreign_toplevel_list
+                .toplevels
+                .iter()
+                .position(|toplevel| &toplevel.handle == handle)
+            else {
+                return;
+            };
+
+            let toplevel = foreign_toplevel_list.toplevels.remove(index);
+            toplevel.handle.destroy();
+            if toplevel.current.is_some() {
+                state.events_sink.push_event(Event::ForeignToplevelsChanged);
+            }
+
+            return;
+        }
+
+        let Some(toplevel) = foreign_toplevel_list.toplevel_mut(handle) else {
+            return;
+        };
+
+        match event {
+            HandleEvent::Title { title } => toplevel.pending.title = title,
+            HandleEvent::AppId { app_id } => toplevel.pending.app_id = app_id,
+            HandleEvent::Identifier { identifier } => toplevel.pending.identifier = identifier,
+            HandleEvent::Done if toplevel.current.as_ref() != Some(&toplevel.pending) => {
+                toplevel.current = Some(toplevel.pending.clone());
+                state.events_sink.push_event(Event::ForeignToplevelsChanged);
+            }
+            _ => (),
+        }
+    }
+}
+
+delegate_dispatch!(WinitState: [ExtForeignToplevelListV1: GlobalData] => ForeignToplevelListState);
+delegate_dispatch!(WinitState: [ExtForeignToplevelHandleV1: GlobalData] => ForeignToplevelListState);
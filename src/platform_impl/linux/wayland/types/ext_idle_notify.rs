@@ -0,0 +1,166 @@
+//! Handling of the idle notifications, which tell whether the user is idle.
+
+use std::time::Duration;
+
+use ahash::AHashMap;
+
+use sctk::reexports::client::backend::ObjectId;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{delegate_dispatch, Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::ext::idle_notify::v1::client::ext_idle_notification_v1::{
+    Event as IdleNotificationEvent, ExtIdleNotificationV1,
+};
+use sctk::reexports::protocols::ext::idle_notify::v1::client::ext_idle_notifier_v1::ExtIdleNotifierV1;
+
+use sctk::globals::GlobalData;
+
+use crate::event::Event;
+use crate::platform_impl::wayland::state::WinitState;
+
+/// Idle notifier, tracking whether the user is idle on all the seats.
+#[derive(Debug)]
+pub struct IdleNotifierState {
+    notifier: ExtIdleNotifierV1,
+
+    /// The time without input after which the user is idle, when the notifications are enabled.
+    timeout: Option<Duration>,
+
+    /// The notifications of the seats, along with whether the seat is idle.
+    notifications: AHashMap<ObjectId, (ExtIdleNotificationV1, bool)>,
+
+    /// Whether `Event::UserIdle` was sent last.
+    user_idle: bool,
+}
+
+impl IdleNotifierState {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let notifier = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self {
+            notifier,
+            timeout: None,
+            notifications: Default::default(),
+            user_idle: false,
+        })
+    }
+
+    pub fn global(&self) -> &ExtIdleNotifierV1 {
+        &self.notifier
+    }
+
+    /// Set the idle timeout, replacing the notifications of the `seats`, or disable the
+    /// notifications with `None`.
+    ///
+    /// Returns `Event::UserActive` when the user was idle, since the new notifications start
+    /// with the user being active.
+    pub fn set_timeout(
+        &mut self,
+        timeout: Option<Duration>,
+        seats: impl Iterator<Item = WlSeat>,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Option<Event<()>> {
+        for (_, (notification, _)) in self.notifications.drain() {
+            notification.destroy();
+        }
+
+        self.timeout = timeout;
+        for seat in seats {
+            self.add_seat(&seat, queue_handle);
+        }
+
+        std::mem::take(&mut self.user_idle).then_some(Event::UserActive)
+    }
+
+    /// Create the notification for the new seat.
+    pub fn add_seat(&mut self, seat: &WlSeat, queue_handle: &QueueHandle<WinitState>) {
+        let Some(timeout) = self.timeout else {
+            return;
+        };
+
+        let timeout = u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX);
+        let data = IdleNotificationData { seat: seat.id() };
+        let notification = self
+            .notifier
+            .get_idle_notification(timeout, seat, queue_handle, data);
+        self.notifications.insert(seat.id(), (notification, false));
+    }
+
+    /// Destroy the notification of the removed seat, returning the event if the user is idle on
+    /// the remaining seats.
+    pub fn remove_seat(&mut self, seat: &ObjectId) -> Option<Event<()>> {
+        let (notification, _) = self.notifications.remove(seat)?;
+        notification.destroy();
+        self.update_user_idle()
+    }
+
+    /// Record whether the user is idle on the seat, returning the event if it changed whether
+    /// the user is idle on all the seats.
+    fn set_seat_idle(&mut self, seat: &ObjectId, idle: bool) -> Option<Event<()>> {
+        self.notifications.get_mut(seat)?.1 = idle;
+        self.update_user_idle()
+    }
+
+    fn update_user_idle(&mut self) -> Option<Event<()>> {
+        let user_idle =
+            !self.notifications.is_empty() && self.notifications.values().all(|(_, idle)| *idle);
+        if user_idle == self.user_idle {
+            return None;
+        }
+
+        self.user_idle = user_idle;
+        Some(if user_idle {
+            Event::UserIdle
+        } else {
+            Event::UserActive
+        })
+    }
+}
+
+pub struct IdleNotificationData {
+    /// The seat the notification was created for.
+    seat: ObjectId,
+}
+
+impl Dispatch<ExtIdleNotifierV1, GlobalData, WinitState> for IdleNotifierState {
+    fn event(
+        _: &mut WinitState,
+        _: &ExtIdleNotifierV1,
+        _: <ExtIdleNotifierV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+    }
+}
+
+impl Dispatch<ExtIdleNotificationV1, IdleNotificationData, WinitState> for IdleNotifierState {
+    fn event(
+        state: &mut WinitState,
+        _: &ExtIdleNotificationV1,
+        event: <ExtIdleNotificationV1 as Proxy>::Event,
+        data: &IdleNotificationData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let idle = match event {
+            IdleNotificationEvent::Idled => true,
+            IdleNotificationEvent::Resumed => false,
+            _ => return,
+        };
+
+        let Some(idle_notifier) = state.idle_notifier.as_mut() else {
+            return;
+        };
+
+        if let Some(event) = idle_notifier.set_seat_idle(&data.seat, idle) {
+            state.events_sink.push_event(event);
+        }
+    }
+}
+
+delegate_dispatch!(WinitState: [ExtIdleNotifierV1: GlobalData] => IdleNotifierState);
+delegate_dispatch!(WinitState: [ExtIdleNotificationV1: IdleNotificationData] => IdleNotifierState);
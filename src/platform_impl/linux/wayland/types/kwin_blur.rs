@@ -27,6 +27,10 @@ impl KWinBlurManager {
         Ok(Self { manager })
     }
 
+    pub fn global(&self) -> &OrgKdeKwinBlurManager {
+        &self.manager
+    }
+
     pub fn blur(
         &self,
         surface: &WlSurface,
@@ -1,7 +1,15 @@
 //! Wayland protocol implementation boilerplate.
 
 pub mod cursor;
+pub mod ext_foreign_toplevel_list;
+pub mod ext_idle_notify;
 pub mod kwin_blur;
+pub mod wlr_foreign_toplevel_management;
+pub mod wlr_gamma_control;
+pub mod wlr_output_power_management;
+pub mod wlr_screencopy;
 pub mod wp_fractional_scaling;
+pub mod wp_presentation;
+pub mod wp_single_pixel_buffer;
 pub mod wp_viewporter;
 pub mod xdg_activation;
@@ -0,0 +1,227 @@
+//! Handling of the wlr foreign toplevel management, which lists the toplevels of all the clients
+//! with their state, and lets them be activated and minimized.
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{
+    delegate_dispatch, event_created_child, Connection, Proxy, QueueHandle,
+};
+use sctk::reexports::protocols_wlr::foreign_toplevel::v1::client::zwlr_foreign_toplevel_handle_v1::{
+    Event as HandleEvent, State as ToplevelState, ZwlrForeignToplevelHandleV1,
+};
+use sctk::reexports::protocols_wlr::foreign_toplevel::v1::client::zwlr_foreign_toplevel_manager_v1::{
+    self, Event as ManagerEvent, ZwlrForeignToplevelManagerV1,
+};
+
+use sctk::globals::GlobalData;
+
+use crate::event::Event;
+use crate::platform::wayland::ManagedToplevel;
+use crate::platform_impl::wayland::state::WinitState;
+
+/// Foreign toplevel manager, to control the toplevels of all the clients.
+#[derive(Debug)]
+pub struct ForeignToplevelManagerState {
+    manager: ZwlrForeignToplevelManagerV1,
+
+    /// The identifier of the next toplevel announced by the compositor.
+    next_id: u64,
+
+    /// The toplevels in the order the compositor announced them.
+    toplevels: Vec<Toplevel>,
+}
+
+#[derive(Debug)]
+struct Toplevel {
+    handle: ZwlrForeignToplevelHandleV1,
+
+    /// The state as of the latest `done`, or `None` before the first one.
+    current: Option<ManagedToplevel>,
+
+    /// The state being sent by the compositor until the next `done`.
+    pending: ManagedToplevel,
+}
+
+impl ForeignToplevelManagerState {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=3, GlobalData)?;
+        Ok(Self {
+            manager,
+            next_id: 0,
+            toplevels: Vec::new(),
+        })
+    }
+
+    pub fn global(&self) -> &ZwlrForeignToplevelManagerV1 {
+        &self.manager
+    }
+
+    /// The toplevels which received their initial state.
+    pub fn toplevels(&self) -> Vec<ManagedToplevel> {
+        self.toplevels
+            .iter()
+            .filter_map(|toplevel| toplevel.current.clone())
+            .collect()
+    }
+
+    /// The handle of the toplevel, or `None` when it's closed.
+    fn handle(&self, toplevel: &ManagedToplevel) -> Option<&ZwlrForeignToplevelHandleV1> {
+        self.toplevels
+            .iter()
+            .find(|candidate| candidate.pending.id == toplevel.id)
+            .map(|toplevel| &toplevel.handle)
+    }
+}
+
+impl WinitState {
+    /// Activate the toplevel on the seat with the keyboard focus on one of the windows, or any
+    /// seat otherwise.
+    pub fn activate_managed_toplevel(&self, toplevel: &ManagedToplevel) {
+        let Some(handle) = self
+            .foreign_toplevel_manager
+            .as_ref()
+            .and_then(|manager| manager.handle(toplevel))
+        else {
+            return;
+        };
+
+        let focused = |seat: &WlSeat| {
+            self.seats
+                .get(&seat.id())
+                .is_some_and(|seat| seat.keyboard_focus_serial().is_some())
+        };
+        let seat = self
+            .seat_state
+            .seats()
+            .find(focused)
+            .or_else(|| self.seat_state.seats().next());
+        if let Some(seat) = seat {
+            handle.activate(&seat);
+        }
+    }
+
+    pub fn minimize_managed_toplevel(&self, toplevel: &ManagedToplevel) {
+        if let Some(handle) = self
+            .foreign_toplevel_manager
+            .as_ref()
+            .and_then(|manager| manager.handle(toplevel))
+        {
+            handle.set_minimized();
+        }
+    }
+}
+
+impl Dispatch<ZwlrForeignToplevelManagerV1, GlobalData, WinitState>
+    for ForeignToplevelManagerState
+{
+    fn event(
+        state: &mut WinitState,
+        _: &ZwlrForeignToplevelManagerV1,
+        event: <ZwlrForeignToplevelManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        match event {
+            ManagerEvent::Toplevel { toplevel: handle } => {
+                let Some(foreign_toplevel_manager) = state.foreign_toplevel_manager.as_mut() else {
+                    handle.destroy();
+                    return;
+                };
+
+                let id = foreign_toplevel_manager.next_id;
+                foreign_toplevel_manager.next_id += 1;
+                foreign_toplevel_manager.toplevels.push(Toplevel {
+                    handle,
+                    current: None,
+                    pending: ManagedToplevel {
+                        id,
+                        ..Default::default()
+                    },
+                });
+            }
+            ManagerEvent::Finished => {
+                // The manager is destroyed by the compositor.
+                let Some(foreign_toplevel_manager) = state.foreign_toplevel_manager.take() else {
+                    return;
+                };
+
+                let changed = !foreign_toplevel_manager.toplevels().is_empty();
+                for toplevel in foreign_toplevel_manager.toplevels {
+                    toplevel.handle.destroy();
+                }
+
+                if changed {
+                    state.events_sink.push_event(Event::ForeignToplevelsChanged);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    event_created_child!(WinitState, ZwlrForeignToplevelManagerV1, [
+        zwlr_foreign_toplevel_manager_v1::EVT_TOPLEVEL_OPCODE => (ZwlrForeignToplevelHandleV1, GlobalData)
+    ]);
+}
+
+impl Dispatch<ZwlrForeignToplevelHandleV1, GlobalData, WinitState> for ForeignToplevelManagerState {
+    fn event(
+        state: &mut WinitState,
+        handle: &ZwlrForeignToplevelHandleV1,
+        event: <ZwlrForeignToplevelHandleV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let Some(foreign_toplevel_manager) = state.foreign_toplevel_manager.as_mut() else {
+            return;
+        };
+        let Some(index) = foreign_toplevel_manager
+            .toplevels
+            .iter()
+            .position(|toplevel| &toplevel.handle == handle)
+        else {
+            return;
+        };
+
+        if let HandleEvent::Closed = event {
+            let toplevel = foreign_toplevel_manager.toplevels.remove(index);
+            toplevel.handle.destroy();
+            if toplevel.current.is_some() {
+                state.events_sink.push_event(Event::ForeignToplevelsChanged);
+            }
+
+            return;
+        }
+
+        let toplevel = &mut foreign_toplevel_manager.toplevels[index];
+        match event {
+            HandleEvent::Title { title } => toplevel.pending.title = title,
+            HandleEvent::AppId { app_id } => toplevel.pending.app_id = app_id,
+            HandleEvent::State { state: states } => {
+                // The states are an array of native endian `u32`.
+                let states: Vec<ToplevelState> = states
+                    .chunks_exact(4)
+                    .map(|state| u32::from_ne_bytes(state.try_into().unwrap()))
+                    .filter_map(|state| ToplevelState::try_from(state).ok())
+                    .collect();
+                toplevel.pending.maximized = states.contains(&ToplevelState::Maximized);
+                toplevel.pending.minimized = states.contains(&ToplevelState::Minimized);
+                toplevel.pending.activated = states.contains(&ToplevelState::Activated);
+                toplevel.pending.fullscreen = states.contains(&ToplevelState::Fullscreen);
+            }
+            HandleEvent::Done if toplevel.current.as_ref() != Some(&toplevel.pending) => {
+                toplevel.current = Some(toplevel.pending.clone());
+                state.events_sink.push_event(Event::ForeignToplevelsChanged);
+            }
+            _ => (),
+        }
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwlrForeignToplevelManagerV1: GlobalData] => ForeignToplevelManagerState);
+delegate_dispatch!(WinitState: [ZwlrForeignToplevelHandleV1: GlobalData] => ForeignToplevelManagerState);
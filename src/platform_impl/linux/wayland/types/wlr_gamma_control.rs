@@ -0,0 +1,197 @@
+//! Handling of the wlr gamma control, which sets the gamma ramps of the monitors.
+
+use std::io::{Seek, Write};
+use std::os::fd::AsFd;
+
+use ahash::AHashMap;
+
+use sctk::reexports::client::backend::ObjectId;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_output::WlOutput;
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{delegate_dispatch, Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols_wlr::gamma_control::v1::client::zwlr_gamma_control_manager_v1::ZwlrGammaControlManagerV1;
+use sctk::reexports::protocols_wlr::gamma_control::v1::client::zwlr_gamma_control_v1::{
+    Event as GammaControlEvent, ZwlrGammaControlV1,
+};
+
+use sctk::globals::GlobalData;
+
+use crate::platform::wayland::GammaRampError;
+use crate::platform_impl::wayland::create_anonymous_file;
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::warn;
+
+/// Gamma control manager, to set the gamma ramps of the monitors.
+#[derive(Debug)]
+pub struct GammaControlManagerState {
+    manager: ZwlrGammaControlManagerV1,
+    queue_handle: QueueHandle<WinitState>,
+
+    /// The gamma controls of the outputs, which are kept until the output is removed, since
+    /// destroying them restores the original gamma.
+    controls: AHashMap<ObjectId, GammaControl>,
+}
+
+#[derive(Debug)]
+struct GammaControl {
+    control: ZwlrGammaControlV1,
+
+    /// The size of the ramps, once the compositor advertised it.
+    size: Option<usize>,
+
+    /// Whether the compositor refused the control.
+    failed: bool,
+}
+
+impl GammaControlManagerState {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self {
+            manager,
+            queue_handle: queue_handle.clone(),
+            controls: Default::default(),
+        })
+    }
+
+    pub fn global(&self) -> &ZwlrGammaControlManagerV1 {
+        &self.manager
+    }
+
+    /// Create the gamma control of the output, unless it already has one.
+    ///
+    /// Returns `true` when the control is new, in which case the size of the ramps is only known
+    /// once the compositor replied to it.
+    pub fn create_control(&mut self, output: &WlOutput) -> bool {
+        if self.controls.contains_key(&output.id()) {
+            return false;
+        }
+
+        let control = self.manager.get_gamma_control(
+            output,
+            &self.queue_handle,
+            GammaControlData {
+                output: output.id(),
+            },
+        );
+        self.controls.insert(
+            output.id(),
+            GammaControl {
+                control,
+                size: None,
+                failed: false,
+            },
+        );
+        true
+    }
+
+    /// Set the gamma ramps of the output, whose control must be created and replied to first.
+    pub fn set_gamma_ramp(
+        &mut self,
+        output: &WlOutput,
+        red: &[u16],
+        green: &[u16],
+        blue: &[u16],
+    ) -> Result<(), GammaRampError> {
+        if red.len() != green.len() || red.len() != blue.len() {
+            return Err(GammaRampError::InvalidSize {
+                expected: red.len(),
+            });
+        }
+
+        let control = self
+            .controls
+            .get(&output.id())
+            .ok_or(GammaRampError::Failed)?;
+        if control.failed {
+            return Err(GammaRampError::Failed);
+        }
+
+        // NOTE: the compositor replies with the size or the failure, so it's only missing when
+        // the roundtrip failed.
+        let size = control.size.ok_or(GammaRampError::Failed)?;
+        if size != red.len() {
+            return Err(GammaRampError::InvalidSize { expected: size });
+        }
+
+        control.upload(&[red, green, blue].concat());
+        Ok(())
+    }
+
+    /// Destroy the gamma control of the removed output.
+    pub fn remove_output(&mut self, output: &ObjectId) {
+        match self.controls.remove(output) {
+            Some(control) if !control.failed => control.control.destroy(),
+            _ => (),
+        }
+    }
+}
+
+impl GammaControl {
+    fn upload(&self, table: &[u16]) {
+        let bytes: Vec<u8> = table.iter().flat_map(|value| value.to_ne_bytes()).collect();
+        // The compositor reads the table from the offset of the file, which is shared.
+        let file = create_anonymous_file().and_then(|mut file| {
+            file.write_all(&bytes)?;
+            file.rewind()?;
+            Ok(file)
+        });
+        match file {
+            Ok(file) => self.control.set_gamma(file.as_fd()),
+            Err(error) => warn!("Failed to create the gamma table: {error}"),
+        }
+    }
+}
+
+pub struct GammaControlData {
+    /// The output the gamma control was created for.
+    output: ObjectId,
+}
+
+impl Dispatch<ZwlrGammaControlManagerV1, GlobalData, WinitState> for GammaControlManagerState {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwlrGammaControlManagerV1,
+        _: <ZwlrGammaControlManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<ZwlrGammaControlV1, GammaControlData, WinitState> for GammaControlManagerState {
+    fn event(
+        state: &mut WinitState,
+        _: &ZwlrGammaControlV1,
+        event: <ZwlrGammaControlV1 as Proxy>::Event,
+        data: &GammaControlData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let Some(gamma_control_manager) = state.gamma_control_manager.as_mut() else {
+            return;
+        };
+
+        let Some(control) = gamma_control_manager.controls.get_mut(&data.output) else {
+            return;
+        };
+
+        match event {
+            GammaControlEvent::GammaSize { size } => control.size = Some(size as usize),
+            GammaControlEvent::Failed => {
+                // NOTE: the control is kept, so the next ramps report the failure.
+                control.failed = true;
+                control.control.destroy();
+            }
+            _ => (),
+        }
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwlrGammaControlManagerV1: GlobalData] => GammaControlManagerState);
+delegate_dispatch!(WinitState: [ZwlrGammaControlV1: GammaControlData] => GammaControlManagerState);
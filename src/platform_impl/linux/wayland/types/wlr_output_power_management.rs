@@ -0,0 +1,80 @@
+//! Handling of the wlr output power management, which turns the monitors on and off.
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_output::WlOutput;
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{delegate_dispatch, Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols_wlr::output_power_management::v1::client::zwlr_output_power_manager_v1::ZwlrOutputPowerManagerV1;
+use sctk::reexports::protocols_wlr::output_power_management::v1::client::zwlr_output_power_v1::{
+    Mode, ZwlrOutputPowerV1,
+};
+
+use sctk::globals::GlobalData;
+
+use crate::platform::wayland::PowerMode;
+use crate::platform_impl::wayland::state::WinitState;
+
+/// Output power manager, to turn the monitors on and off.
+#[derive(Debug)]
+pub struct OutputPowerManagerState {
+    manager: ZwlrOutputPowerManagerV1,
+    queue_handle: QueueHandle<WinitState>,
+}
+
+impl OutputPowerManagerState {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self {
+            manager,
+            queue_handle: queue_handle.clone(),
+        })
+    }
+
+    pub fn global(&self) -> &ZwlrOutputPowerManagerV1 {
+        &self.manager
+    }
+
+    /// Set the power mode of the output, releasing the power object right away.
+    pub fn set_mode(&self, output: &WlOutput, mode: PowerMode) {
+        let power = self
+            .manager
+            .get_output_power(output, &self.queue_handle, GlobalData);
+        power.set_mode(match mode {
+            PowerMode::On => Mode::On,
+            PowerMode::Off => Mode::Off,
+        });
+        power.destroy();
+    }
+}
+
+impl Dispatch<ZwlrOutputPowerManagerV1, GlobalData, WinitState> for OutputPowerManagerState {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwlrOutputPowerManagerV1,
+        _: <ZwlrOutputPowerManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<ZwlrOutputPowerV1, GlobalData, WinitState> for OutputPowerManagerState {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwlrOutputPowerV1,
+        _: <ZwlrOutputPowerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // NOTE: the power object is destroyed right after setting the mode.
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwlrOutputPowerManagerV1: GlobalData] => OutputPowerManagerState);
+delegate_dispatch!(WinitState: [ZwlrOutputPowerV1: GlobalData] => OutputPowerManagerState);
@@ -0,0 +1,289 @@
+//! Handling of the wlr screencopy, which captures the contents of the monitors.
+
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::os::fd::AsFd;
+
+use ahash::AHashMap;
+
+use sctk::reexports::client::backend::ObjectId;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_buffer::WlBuffer;
+use sctk::reexports::client::protocol::wl_output::WlOutput;
+use sctk::reexports::client::protocol::wl_shm::{Format, WlShm};
+use sctk::reexports::client::protocol::wl_shm_pool::WlShmPool;
+use sctk::reexports::client::WEnum;
+use sctk::reexports::client::{delegate_dispatch, Connection, Dispatch, Proxy, QueueHandle};
+use sctk::reexports::protocols_wlr::screencopy::v1::client::zwlr_screencopy_frame_v1::{
+    Event as FrameEvent, Flags, ZwlrScreencopyFrameV1,
+};
+use sctk::reexports::protocols_wlr::screencopy::v1::client::zwlr_screencopy_manager_v1::ZwlrScreencopyManagerV1;
+
+use sctk::globals::GlobalData;
+
+use crate::dpi::{LogicalPosition, LogicalSize};
+use crate::error::ExternalError;
+use crate::platform::wayland::CapturedFrame;
+use crate::platform_impl::wayland::create_anonymous_file;
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::warn;
+use crate::platform_impl::OsError;
+
+/// The callback of a capture, called on the event loop once the compositor copied the frame.
+pub type CaptureCallback = Box<dyn FnOnce(Result<CapturedFrame, ExternalError>)>;
+
+/// Screencopy manager, to capture the contents of the monitors.
+pub struct ScreencopyManagerState {
+    manager: ZwlrScreencopyManagerV1,
+    queue_handle: QueueHandle<WinitState>,
+
+    /// The shm to create the buffers the frames are copied into.
+    shm: WlShm,
+
+    /// The frames being captured.
+    captures: AHashMap<ObjectId, FrameCapture>,
+}
+
+impl ScreencopyManagerState {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+        shm: &WlShm,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self {
+            manager,
+            queue_handle: queue_handle.clone(),
+            shm: shm.clone(),
+            captures: Default::default(),
+        })
+    }
+
+    pub fn global(&self) -> &ZwlrScreencopyManagerV1 {
+        &self.manager
+    }
+
+    /// Capture the output, or the region of it in logical coordinates.
+    ///
+    /// The frame is dispatched on the event loop, which queues the callback in `captured_frames`
+    /// once the compositor copied it.
+    pub fn capture(
+        &mut self,
+        output: &WlOutput,
+        region: Option<(LogicalPosition<i32>, LogicalSize<i32>)>,
+        callback: CaptureCallback,
+    ) {
+        let frame = match region {
+            Some((position, size)) => self.manager.capture_output_region(
+                0,
+                output,
+                position.x,
+                position.y,
+                size.width,
+                size.height,
+                &self.queue_handle,
+                FrameData,
+            ),
+            None => self
+                .manager
+                .capture_output(0, output, &self.queue_handle, FrameData),
+        };
+
+        self.captures.insert(
+            frame.id(),
+            FrameCapture {
+                callback,
+                buffer: None,
+                y_invert: false,
+            },
+        );
+    }
+}
+
+/// The state of a frame being captured.
+struct FrameCapture {
+    callback: CaptureCallback,
+
+    /// The buffer the frame is copied into, once the compositor described it.
+    buffer: Option<FrameBuffer>,
+
+    /// Whether the frame is upside down.
+    y_invert: bool,
+}
+
+struct FrameBuffer {
+    buffer: WlBuffer,
+    file: File,
+    format: Format,
+    width: u32,
+    height: u32,
+    stride: u32,
+}
+
+impl FrameCapture {
+    /// Create the buffer described by the compositor and copy the frame into it.
+    #[allow(clippy::too_many_arguments)]
+    fn copy(
+        &mut self,
+        frame: &ZwlrScreencopyFrameV1,
+        shm: &WlShm,
+        format: Format,
+        width: u32,
+        height: u32,
+        stride: u32,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> std::io::Result<()> {
+        let len = stride as usize * height as usize;
+        let file = create_anonymous_file()?;
+        file.set_len(len as u64)?;
+
+        let pool = shm.create_pool(file.as_fd(), len as i32, queue_handle, FrameData);
+        let buffer = pool.create_buffer(
+            0,
+            width as i32,
+            height as i32,
+            stride as i32,
+            format,
+            queue_handle,
+            FrameData,
+        );
+        pool.destroy();
+
+        frame.copy(&buffer);
+        self.buffer = Some(FrameBuffer {
+            buffer,
+            file,
+            format,
+            width,
+            height,
+            stride,
+        });
+        Ok(())
+    }
+
+    /// Read the copied frame out of the buffer.
+    fn read(&mut self) -> Result<CapturedFrame, &'static str> {
+        let buffer = self
+            .buffer
+            .as_mut()
+            .ok_or("the compositor didn't describe the buffer of the captured frame")?;
+
+        let mut pixels = vec![0; buffer.stride as usize * buffer.height as usize];
+        let file = &mut buffer.file;
+        if let Err(error) = file.rewind().and_then(|_| file.read_exact(&mut pixels)) {
+            warn!("Failed to read the captured frame: {error}");
+            return Err("failed to read the captured frame");
+        }
+
+        Ok(CapturedFrame {
+            size: (buffer.width, buffer.height).into(),
+            stride: buffer.stride,
+            format: buffer.format,
+            y_invert: self.y_invert,
+            pixels,
+        })
+    }
+}
+
+/// The data of the frames and of the buffers they are copied into.
+pub struct FrameData;
+
+impl Dispatch<ZwlrScreencopyManagerV1, GlobalData, WinitState> for ScreencopyManagerState {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwlrScreencopyManagerV1,
+        _: <ZwlrScreencopyManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<ZwlrScreencopyFrameV1, FrameData, WinitState> for ScreencopyManagerState {
+    fn event(
+        state: &mut WinitState,
+        frame: &ZwlrScreencopyFrameV1,
+        event: <ZwlrScreencopyFrameV1 as Proxy>::Event,
+        _: &FrameData,
+        _: &Connection,
+        queue_handle: &QueueHandle<WinitState>,
+    ) {
+        let Some(screencopy_manager) = state.screencopy_manager.as_mut() else {
+            return;
+        };
+
+        let Some(capture) = screencopy_manager.captures.get_mut(&frame.id()) else {
+            return;
+        };
+
+        let result = match event {
+            FrameEvent::Buffer {
+                format: WEnum::Value(format),
+                width,
+                height,
+                stride,
+            } => {
+                let shm = &screencopy_manager.shm;
+                match capture.copy(frame, shm, format, width, height, stride, queue_handle) {
+                    Ok(()) => return,
+                    Err(error) => {
+                        warn!("Failed to create the buffer of the captured frame: {error}");
+                        Err("failed to create the buffer of the captured frame")
+                    }
+                }
+            }
+            FrameEvent::Buffer { .. } => Err("unknown format of the captured frame"),
+            FrameEvent::Flags { flags } => {
+                capture.y_invert =
+                    matches!(flags, WEnum::Value(flags) if flags.contains(Flags::YInvert));
+                return;
+            }
+            FrameEvent::Ready { .. } => capture.read(),
+            FrameEvent::Failed => Err("the compositor failed to capture the output"),
+            _ => return,
+        };
+
+        let capture = screencopy_manager.captures.remove(&frame.id()).unwrap();
+        frame.destroy();
+        if let Some(buffer) = capture.buffer {
+            buffer.buffer.destroy();
+        }
+
+        let result = result.map_err(|error| ExternalError::Os(os_error!(OsError::Misc(error))));
+        state.dispatched_events = true;
+        state.captured_frames.push((capture.callback, result));
+    }
+}
+
+impl Dispatch<WlShmPool, FrameData, WinitState> for ScreencopyManagerState {
+    fn event(
+        _: &mut WinitState,
+        _: &WlShmPool,
+        _: <WlShmPool as Proxy>::Event,
+        _: &FrameData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<WlBuffer, FrameData, WinitState> for ScreencopyManagerState {
+    fn event(
+        _: &mut WinitState,
+        _: &WlBuffer,
+        _: <WlBuffer as Proxy>::Event,
+        _: &FrameData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // NOTE: the buffer is destroyed once the frame is copied.
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwlrScreencopyManagerV1: GlobalData] => ScreencopyManagerState);
+delegate_dispatch!(WinitState: [ZwlrScreencopyFrameV1: FrameData] => ScreencopyManagerState);
+delegate_dispatch!(WinitState: [WlShmPool: FrameData] => ScreencopyManagerState);
+delegate_dispatch!(WinitState: [WlBuffer: FrameData] => ScreencopyManagerState);
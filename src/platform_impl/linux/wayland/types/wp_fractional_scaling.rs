@@ -36,6 +36,10 @@ impl FractionalScalingManager {
         Ok(Self { manager })
     }
 
+    pub fn global(&self) -> &WpFractionalScaleManagerV1 {
+        &self.manager
+    }
+
     pub fn fractional_scaling(
         &self,
         surface: &WlSurface,
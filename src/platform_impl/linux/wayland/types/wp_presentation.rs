@@ -0,0 +1,190 @@
+//! Handling of the presentation time feedback.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{delegate_dispatch, Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::presentation_time::client::wp_presentation::WpPresentation;
+use sctk::reexports::protocols::wp::presentation_time::client::wp_presentation_feedback::{
+    Event as PresentationFeedbackEvent, WpPresentationFeedback,
+};
+
+use sctk::globals::GlobalData;
+
+use crate::platform::wayland::FrameTiming;
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{self, WindowId};
+
+/// The number of the latest frame intervals averaged in the frame timing.
+const FRAME_INTERVALS: usize = 8;
+
+/// Presentation time manager.
+#[derive(Debug, Clone)]
+pub struct PresentationManager {
+    manager: WpPresentation,
+}
+
+impl PresentationManager {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+
+    pub fn global(&self) -> &WpPresentation {
+        &self.manager
+    }
+
+    /// Request the feedback for the next commit of the surface.
+    pub fn feedback(&self, surface: &WlSurface, queue_handle: &QueueHandle<WinitState>) {
+        let data = PresentationFeedbackData {
+            window_id: wayland::make_wid(surface),
+        };
+        self.manager.feedback(surface, queue_handle, data);
+    }
+}
+
+pub struct PresentationFeedbackData {
+    /// The window the feedback was requested for.
+    window_id: WindowId,
+}
+
+/// The timing of the latest frames presented by the compositor for a window.
+#[derive(Debug, Default)]
+pub struct FrameTimingTracker {
+    last_presented: Option<Duration>,
+    refresh_interval: Option<Duration>,
+    intervals: VecDeque<Duration>,
+}
+
+impl FrameTimingTracker {
+    /// Record the frame presented at the given time, with the refresh interval of the output.
+    pub fn presented(&mut self, presented_at: Duration, refresh_interval: Duration) {
+        if let Some(interval) = self
+            .last_presented
+            .and_then(|last_presented| presented_at.checked_sub(last_presented))
+        {
+            if self.intervals.len() == FRAME_INTERVALS {
+                self.intervals.pop_front();
+            }
+            self.intervals.push_back(interval);
+        }
+
+        self.last_presented = Some(presented_at);
+        // Zero means that the output doesn't have a constant refresh rate.
+        self.refresh_interval = Some(refresh_interval).filter(|interval| !interval.is_zero());
+    }
+
+    /// The timing, available once at least two frames were presented.
+    pub fn timing(&self) -> Option<FrameTiming> {
+        let presented_at = self.last_presented?;
+        if self.intervals.is_empty() {
+            return None;
+        }
+
+        let count = self.intervals.len() as u32;
+        let average_frame_interval = self.intervals.iter().sum::<Duration>() / count;
+        let frame_interval_jitter = self
+            .intervals
+            .iter()
+            .map(|&interval| {
+                if interval > average_frame_interval {
+                    interval - average_frame_interval
+                } else {
+                    average_frame_interval - interval
+                }
+            })
+            .sum::<Duration>()
+            / count;
+
+        Some(FrameTiming {
+            presented_at,
+            refresh_interval: self.refresh_interval,
+            average_frame_interval,
+            frame_interval_jitter,
+        })
+    }
+}
+
+impl Dispatch<WpPresentation, GlobalData, WinitState> for PresentationManager {
+    fn event(
+        _: &mut WinitState,
+        _: &WpPresentation,
+        _: <WpPresentation as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // NOTE: the clock is only documented for the users, so ignore it.
+    }
+}
+
+impl Dispatch<WpPresentationFeedback, PresentationFeedbackData, WinitState>
+    for PresentationManager
+{
+    fn event(
+        state: &mut WinitState,
+        _: &WpPresentationFeedback,
+        event: <WpPresentationFeedback as Proxy>::Event,
+        data: &PresentationFeedbackData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        if let PresentationFeedbackEvent::Presented {
+            tv_sec_hi,
+            tv_sec_lo,
+            tv_nsec,
+            refresh,
+            ..
+        } = event
+        {
+            let seconds = ((tv_sec_hi as u64) << 32) | tv_sec_lo as u64;
+            let presented_at = Duration::new(seconds, tv_nsec);
+            if let Some(window) = state.windows.get_mut().get(&data.window_id) {
+                window
+                    .lock()
+                    .unwrap()
+                    .frame_presented(presented_at, Duration::from_nanos(refresh as u64));
+            }
+        }
+    }
+}
+
+delegate_dispatch!(WinitState: [WpPresentation: GlobalData] => PresentationManager);
+delegate_dispatch!(WinitState: [WpPresentationFeedback: PresentationFeedbackData] => PresentationManager);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_frame_timing() {
+        let ms = Duration::from_millis;
+        let mut tracker = FrameTimingTracker::default();
+
+        tracker.presented(ms(100), ms(16));
+        assert_eq!(tracker.timing(), None);
+
+        tracker.presented(ms(116), ms(16));
+        tracker.presented(ms(136), ms(16));
+        let timing = tracker.timing().unwrap();
+        assert_eq!(timing.presented_at, ms(136));
+        assert_eq!(timing.refresh_interval, Some(ms(16)));
+        assert_eq!(timing.average_frame_interval, ms(18));
+        assert_eq!(timing.frame_interval_jitter, ms(2));
+
+        // Only the latest intervals are averaged.
+        for i in 1..=FRAME_INTERVALS as u64 {
+            tracker.presented(ms(136 + 10 * i), Duration::ZERO);
+        }
+        let timing = tracker.timing().unwrap();
+        assert_eq!(timing.refresh_interval, None);
+        assert_eq!(timing.average_frame_interval, ms(10));
+        assert_eq!(timing.frame_interval_jitter, Duration::ZERO);
+    }
+}
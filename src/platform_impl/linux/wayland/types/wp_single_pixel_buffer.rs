@@ -0,0 +1,68 @@
+//! Handling of the wp-single-pixel-buffer.
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_buffer::WlBuffer;
+use sctk::reexports::client::Dispatch;
+use sctk::reexports::client::{delegate_dispatch, Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::single_pixel_buffer::v1::client::wp_single_pixel_buffer_manager_v1::WpSinglePixelBufferManagerV1;
+
+use sctk::globals::GlobalData;
+
+use crate::platform_impl::wayland::state::WinitState;
+
+/// Single pixel buffer manager.
+#[derive(Debug)]
+pub struct SinglePixelBufferState {
+    manager: WpSinglePixelBufferManagerV1,
+}
+
+impl SinglePixelBufferState {
+    /// Create new single pixel buffer manager.
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+
+    pub fn global(&self) -> &WpSinglePixelBufferManagerV1 {
+        &self.manager
+    }
+
+    /// Create a transparent 1x1 buffer, which is scaled to the surface with the viewport.
+    pub fn create_transparent_buffer(&self, queue_handle: &QueueHandle<WinitState>) -> WlBuffer {
+        self.manager
+            .create_u32_rgba_buffer(0, 0, 0, 0, queue_handle, GlobalData)
+    }
+}
+
+impl Dispatch<WpSinglePixelBufferManagerV1, GlobalData, WinitState> for SinglePixelBufferState {
+    fn event(
+        _: &mut WinitState,
+        _: &WpSinglePixelBufferManagerV1,
+        _: <WpSinglePixelBufferManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<WlBuffer, GlobalData, WinitState> for SinglePixelBufferState {
+    fn event(
+        _: &mut WinitState,
+        _: &WlBuffer,
+        _: <WlBuffer as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // NOTE: the buffer stays attached for the lifetime of the surface, so the release is
+        // irrelevant.
+    }
+}
+
+delegate_dispatch!(WinitState: [WpSinglePixelBufferManagerV1: GlobalData] => SinglePixelBufferState);
+delegate_dispatch!(WinitState: [WlBuffer: GlobalData] => SinglePixelBufferState);
@@ -27,6 +27,10 @@ impl ViewporterState {
         Ok(Self { viewporter })
     }
 
+    pub fn global(&self) -> &WpViewporter {
+        &self.viewporter
+    }
+
     /// Get the viewport for the given object.
     pub fn get_viewport(
         &self,
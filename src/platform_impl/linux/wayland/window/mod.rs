@@ -2,31 +2,36 @@
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use sctk::reexports::client::protocol::wl_data_device_manager::DndAction as SctkDndAction;
 use sctk::reexports::client::protocol::wl_display::WlDisplay;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::Proxy;
 use sctk::reexports::client::QueueHandle;
 
 use sctk::compositor::{CompositorState, Region, SurfaceData};
+use sctk::reexports::csd_frame::{WindowManagerCapabilities, WindowState as XdgWindowState};
 use sctk::reexports::protocols::xdg::activation::v1::client::xdg_activation_v1::XdgActivationV1;
+use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::XdgToplevel;
 use sctk::shell::wlr_layer::{Anchor, KeyboardInteractivity, Layer, LayerSurface};
+use sctk::shell::xdg::popup::Popup;
 use sctk::shell::xdg::window::Window as SctkWindow;
 use sctk::shell::xdg::window::WindowDecorations;
+use sctk::shell::xdg::XdgPositioner;
 use sctk::shell::WaylandSurface;
-use wayland_client::protocol::wl_output::WlOutput;
+use wayland_client::protocol::wl_output::{Transform, WlOutput};
 
-use log::warn;
-
-use crate::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
-use crate::event::{Ime, WindowEvent};
+use crate::event::{DndAction, Ime, WindowEvent};
 use crate::event_loop::AsyncRequestSerial;
+use crate::platform::wayland::{DecorationsMode, FrameTiming, OutputTransform, ScalePreference};
 use crate::platform_impl::{
     Fullscreen, MonitorHandle as PlatformMonitorHandle, OsError, PlatformIcon,
 };
 use crate::window::{
-    Cursor, CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType,
+    Cursor, CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme, UserAttentionType,
     WindowAttributes, WindowButtons, WindowLevel,
 };
 
@@ -34,7 +39,7 @@ use super::event_loop::sink::EventSink;
 use super::output::MonitorHandle;
 use super::state::WinitState;
 use super::types::xdg_activation::XdgActivationTokenData;
-use super::{EventLoopWindowTarget, WaylandError, WindowId};
+use super::{warn, EventLoopWindowTarget, WaylandError, WindowId};
 
 pub(crate) mod state;
 
@@ -78,6 +83,9 @@ pub struct Window {
 
     /// The event sink to deliver sythetic events.
     window_events_sink: Arc<Mutex<EventSink>>,
+
+    /// The instance name given with the application ID.
+    instance_name: Option<String>,
 }
 
 impl Window {
@@ -90,6 +98,13 @@ impl Window {
 
         let monitors = state.monitors.clone();
 
+        // Wayland has no instance names, so only keep it for the user.
+        let instance_name = attributes
+            .platform_specific
+            .name
+            .as_ref()
+            .map(|name| name.instance.clone());
+
         let surface = state.compositor_state.create_surface(&queue_handle);
         let compositor = state.compositor_state.clone();
         let xdg_activation = state
@@ -102,9 +117,15 @@ impl Window {
             .inner_size
             .unwrap_or(LogicalSize::new(800., 600.).into());
 
+        let tooltip = attributes.platform_specific.wayland.tooltip;
+
         let (window, mut window_state) = if let Some(layer) =
             attributes.platform_specific.wayland.layer_shell
         {
+            let layer_shell = state.layer_shell.as_ref().ok_or_else(|| {
+                os_error!(WaylandError::MissingProtocol("zwlr_layer_shell_v1").into())
+            })?;
+
             let handles = monitors.lock().unwrap();
             let output = attributes.platform_specific.wayland.output.and_then(|name| {
                 for handle in handles.iter() {
@@ -118,25 +139,60 @@ impl Window {
                 None
             });
 
-            let layer_surface = state.layer_shell.create_layer_surface(
+            // Tooltips stay above everything else and never take the keyboard focus.
+            let layer = if tooltip { Layer::Overlay } else { layer };
+            let keyboard_interactivity = if tooltip {
+                Some(KeyboardInteractivity::None)
+            } else {
+                attributes
+                    .platform_specific
+                    .wayland
+                    .keyboard_interactivity
+                    // Don't rely on the protocol default for the inactive windows.
+                    .or((!attributes.active).then_some(KeyboardInteractivity::None))
+            };
+
+            let namespace = attributes
+                .platform_specific
+                .wayland
+                .layer_namespace
+                .clone()
+                .or_else(|| state.default_layer_namespace.clone());
+            let layer_surface = layer_shell.create_layer_surface(
                 &queue_handle,
                 surface.clone(),
                 layer,
-                // TODO(theonlymrcat): Is this where app id should go?
-                None::<String>,
+                namespace,
                 output,
             );
 
-            let window_state = WindowState::new_layer(
+            // The compositor picks the size only along the axes the surface is stretched on, by
+            // anchoring to both of the opposite edges, otherwise the zero size is a protocol error.
+            let anchor = attributes
+                .platform_specific
+                .wayland
+                .anchor
+                .unwrap_or(Anchor::empty());
+            let mut size: LogicalSize<u32> = size.to_logical(1.);
+            if size.width == 0 && !anchor.contains(Anchor::LEFT | Anchor::RIGHT) {
+                warn!("Zero width requires anchoring the layer surface to the left and right");
+                size.width = 800;
+            }
+            if size.height == 0 && !anchor.contains(Anchor::TOP | Anchor::BOTTOM) {
+                warn!("Zero height requires anchoring the layer surface to the top and bottom");
+                size.height = 600;
+            }
+
+            let mut window_state = WindowState::new_layer(
                 event_loop_window_target.connection.clone(),
                 &event_loop_window_target.queue_handle,
                 &state,
-                size,
+                size.into(),
                 layer_surface.clone(),
                 attributes.preferred_theme,
             );
 
-            let size = size.to_logical(1.);
+            // Request the initial size before the initial commit.
             layer_surface.set_size(size.width, size.height);
 
             let window_shell = WindowShell::WlrLayer {
@@ -152,15 +208,68 @@ impl Window {
             if let Some((top, right, bottom, left)) = attributes.platform_specific.wayland.margin {
                 window_state.set_margin(top, right, bottom, left);
             }
-            if let Some(keyboard_interactivity) = attributes.platform_specific.wayland.keyboard_interactivity
-            {
+            if let Some(keyboard_interactivity) = keyboard_interactivity {
                 window_state.set_keyboard_interactivity(keyboard_interactivity);
             }
             (window_shell, window_state)
+        } else if let Some((parent, positioner)) = attributes.platform_specific.wayland.layer_popup
+        {
+            // NOTE: the `xdg_wm_base` is always bound, so these shouldn't fail.
+            let popup_error = |_| os_error!(OsError::Misc("failed to create xdg_popup."));
+
+            let xdg_positioner = XdgPositioner::new(&state.xdg_shell).map_err(popup_error)?;
+            let logical_size: LogicalSize<i32> = size.to_logical(1.);
+            let (x, y, width, height) = positioner.anchor_rect;
+            xdg_positioner.set_size(logical_size.width.max(1), logical_size.height.max(1));
+            xdg_positioner.set_anchor_rect(x, y, width, height);
+            xdg_positioner.set_anchor(positioner.anchor);
+            xdg_positioner.set_gravity(positioner.gravity);
+            xdg_positioner.set_offset(positioner.offset.0, positioner.offset.1);
+            xdg_positioner.set_constraint_adjustment(positioner.constraint_adjustment.bits());
+
+            let popup = Popup::from_surface(
+                None,
+                &xdg_positioner,
+                &queue_handle,
+                surface.clone(),
+                &state.xdg_shell,
+            )
+            .map_err(popup_error)?;
+            parent.get_popup(popup.xdg_popup());
+
+            // Grab the input, so the compositor dismisses the popup on the outside click, with the
+            // seat that last clicked the parent. Tooltips don't grab, so they don't take the focus
+            // from the parent.
+            let parent_id = super::make_wid(parent.wl_surface());
+            if let Some((seat, serial)) = state
+                .seats
+                .values()
+                .filter_map(|seat_state| seat_state.latest_press_serial(parent_id))
+                .max_by_key(|(_, serial)| *serial)
+                .filter(|_| !tooltip)
+            {
+                popup.xdg_popup().grab(seat, serial);
+            }
+
+            let window_state = WindowState::new_popup(
+                event_loop_window_target.connection.clone(),
+                &event_loop_window_target.queue_handle,
+                &state,
+                size,
+                popup.clone(),
+                attributes.preferred_theme,
+            );
+
+            (WindowShell::Popup { popup }, window_state)
         } else {
+            // Only the resize border is handled without the decorations.
+            let resize_only = attributes.platform_specific.wayland.decorations_mode
+                == DecorationsMode::ResizeOnly;
+            let decorations = attributes.decorations && !resize_only;
+
             // We prefer server side decorations, however to not have decorations we ask for client
             // side decorations instead.
-            let default_decorations = if attributes.decorations {
+            let default_decorations = if decorations {
                 WindowDecorations::RequestServer
             } else {
                 WindowDecorations::RequestClient
@@ -186,22 +295,27 @@ impl Window {
             window_state.set_blur(attributes.blur);
 
             // Set the decorations hint.
-            window_state.set_decorate(attributes.decorations);
+            window_state.set_decorate(decorations);
+            window_state.set_resize_border(resize_only);
 
             // Set the app_id.
             if let Some(name) = attributes.platform_specific.name.map(|name| name.general) {
                 window.set_app_id(name);
             }
 
+            // Set the parent window.
+            if let Some(parent) = attributes.platform_specific.wayland.parent.as_ref() {
+                window.xdg_toplevel().set_parent(Some(parent));
+            }
+
             // Set the window title.
             window_state.set_title(attributes.title);
 
             // Set the min and max sizes. We must set the hints upon creating a window, so
-            // we use the default `1.` scaling...
-            let min_size = attributes.min_inner_size.map(|size| size.to_logical(1.));
-            let max_size = attributes.max_inner_size.map(|size| size.to_logical(1.));
-            window_state.set_min_inner_size(min_size);
-            window_state.set_max_inner_size(max_size);
+            // the default `1.` scaling is used until the scale factor is known...
+            window_state.set_min_inner_size(attributes.min_inner_size);
+            window_state.set_max_inner_size(attributes.max_inner_size);
+            window_state.set_resize_increments(attributes.resize_increments);
 
             // Non-resizable implies that the min and max sizes are set to the same value.
             window_state.set_resizable(attributes.resizable);
@@ -214,13 +328,7 @@ impl Window {
                 warn!("`Fullscreen::Exclusive` is ignored on Wayland");
             }
             Some(Fullscreen::Borderless(monitor)) => {
-                let output = monitor.and_then(|monitor| match monitor {
-                    PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
-                    #[cfg(x11_platform)]
-                    PlatformMonitorHandle::X(_) => None,
-                });
-
-                window.set_fullscreen(output.as_ref())
+                window.set_fullscreen(fullscreen_output(monitor).as_ref())
             }
             _ if attributes.maximized => window.set_maximized(true),
             _ => (),
@@ -231,16 +339,43 @@ impl Window {
             Cursor::Custom(cursor) => window_state.set_custom_cursor(cursor),
         }
 
-        // Activate the window when the token is passed.
+        // Activate the window when the token is passed, unless the user doesn't want the window
+        // to take the focus.
         if let (Some(xdg_activation), Some(token)) = (
-            xdg_activation.as_ref(),
+            xdg_activation.as_ref().filter(|_| attributes.active),
             attributes.platform_specific.activation_token,
         ) {
             xdg_activation.activate(token._token, &surface);
         }
 
-        // XXX Do initial commit.
-        window.wl_surface().commit();
+        window_state.redraw_throttle = attributes.platform_specific.wayland.redraw_throttle;
+        window_state.initial_min_size = attributes.platform_specific.wayland.initial_min_size;
+
+        if let Some(scale) = attributes.platform_specific.wayland.preferred_integer_scale {
+            window_state.force_integer_scale(scale);
+        }
+
+        if attributes.platform_specific.wayland.input_only
+            && !window_state.set_input_only(&state, &queue_handle)
+        {
+            warn!("Input only windows require wp_single_pixel_buffer_manager_v1 and wp_viewporter");
+        }
+
+        // The empty input region lets the input through to the windows below.
+        if attributes.platform_specific.wayland.input_passthrough {
+            match Region::new(&*compositor) {
+                Ok(region) => surface.set_input_region(Some(region.wl_region())),
+                Err(_) => warn!("Failed to clear the input region."),
+            }
+        }
+
+        // XXX Do initial commit, unless the user wants to map the window later.
+        if attributes.visible {
+            trace_protocol!("-> {}.commit()", window.wl_surface().id());
+            window.wl_surface().commit();
+        } else {
+            window_state.visible = false;
+        }
 
         // Add the window and window requests into the state.
         let window_state = Arc::new(Mutex::new(window_state));
@@ -274,7 +409,7 @@ impl Window {
         })?;
 
         // XXX Wait for the initial configure to arrive.
-        while !window_state.lock().unwrap().is_configured() {
+        while attributes.visible && !window_state.lock().unwrap().is_configured() {
             event_queue.blocking_dispatch(&mut state).map_err(|error| {
                 os_error!(OsError::WaylandError(Arc::new(WaylandError::Dispatch(
                     error
@@ -299,6 +434,7 @@ impl Window {
             event_loop_awakener,
             window_requests,
             window_events_sink,
+            instance_name,
         })
     }
 }
@@ -316,13 +452,14 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_visible(&self, _visible: bool) {
-        // Not possible on Wayland.
+    pub fn set_visible(&self, visible: bool) {
+        self.window_state.lock().unwrap().set_visible(visible);
+        self.request_redraw();
     }
 
     #[inline]
     pub fn is_visible(&self) -> Option<bool> {
-        None
+        Some(self.window_state.lock().unwrap().visible)
     }
 
     #[inline]
@@ -365,7 +502,9 @@ impl Window {
 
     #[inline]
     pub fn pre_present_notify(&self) {
-        self.window_state.lock().unwrap().request_frame_callback();
+        let mut window_state = self.window_state.lock().unwrap();
+        window_state.request_frame_callback();
+        window_state.request_presentation_feedback();
     }
 
     #[inline]
@@ -375,6 +514,21 @@ impl Window {
         super::logical_to_physical_rounded(window_state.outer_size(), scale_factor)
     }
 
+    #[inline]
+    pub fn decoration_insets(&self) -> (u32, u32, u32, u32) {
+        let window_state = self.window_state.lock().unwrap();
+        let scale_factor = window_state.scale_factor();
+        let (top, right, bottom, left) = window_state.decoration_insets();
+        let top_right = super::logical_to_physical_rounded((top, right).into(), scale_factor);
+        let bottom_left = super::logical_to_physical_rounded((bottom, left).into(), scale_factor);
+        (
+            top_right.width,
+            top_right.height,
+            bottom_left.width,
+            bottom_left.height,
+        )
+    }
+
     #[inline]
     pub fn request_inner_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
         let mut window_state = self.window_state.lock().unwrap();
@@ -386,8 +540,6 @@ impl Window {
     /// Set the minimum inner size for the window.
     #[inline]
     pub fn set_min_inner_size(&self, min_size: Option<Size>) {
-        let scale_factor = self.scale_factor();
-        let min_size = min_size.map(|size| size.to_logical(scale_factor));
         self.window_state
             .lock()
             .unwrap()
@@ -399,8 +551,6 @@ impl Window {
     /// Set the maximum inner size for the window.
     #[inline]
     pub fn set_max_inner_size(&self, max_size: Option<Size>) {
-        let scale_factor = self.scale_factor();
-        let max_size = max_size.map(|size| size.to_logical(scale_factor));
         self.window_state
             .lock()
             .unwrap()
@@ -411,12 +561,20 @@ impl Window {
 
     #[inline]
     pub fn resize_increments(&self) -> Option<PhysicalSize<u32>> {
-        None
+        let window_state = self.window_state.lock().unwrap();
+        let scale_factor = window_state.scale_factor();
+        window_state
+            .resize_increments()
+            .map(|increments| increments.to_physical(scale_factor))
     }
 
     #[inline]
-    pub fn set_resize_increments(&self, _increments: Option<Size>) {
-        warn!("`set_resize_increments` is not implemented for Wayland");
+    pub fn set_resize_increments(&self, increments: Option<Size>) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_resize_increments(increments);
+        // NOTE: Requires a configure to be applied.
     }
 
     #[inline]
@@ -432,6 +590,11 @@ impl Window {
         self.window_state.lock().unwrap().has_focus()
     }
 
+    #[inline]
+    pub fn has_pointer_focus(&self) -> bool {
+        self.window_state.lock().unwrap().has_pointer_focus()
+    }
+
     #[inline]
     pub fn is_minimized(&self) -> Option<bool> {
         // XXX clients don't know whether they are minimized or not.
@@ -543,12 +706,7 @@ impl Window {
                 warn!("`Fullscreen::Exclusive` is ignored on Wayland");
             }
             Some(Fullscreen::Borderless(monitor)) => {
-                let output = monitor.and_then(|monitor| match monitor {
-                    PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
-                    #[cfg(x11_platform)]
-                    PlatformMonitorHandle::X(_) => None,
-                });
-
+                let output = fullscreen_output(monitor);
                 self.window.set_fullscreen(output.as_ref())
             }
             None => self.window.unset_fullscreen(),
@@ -620,6 +778,22 @@ impl Window {
         self.window_state.lock().unwrap().set_cursor_grab(mode)
     }
 
+    #[inline]
+    pub fn set_cursor_confine_region(&self, region: Option<(i32, i32, u32, u32)>) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_cursor_confine_region(region);
+    }
+
+    #[inline]
+    pub fn set_cursor_theme(&self, name: Option<String>, size: u32) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_cursor_theme(name, size);
+    }
+
     #[inline]
     pub fn set_cursor_position(&self, position: Position) -> Result<(), ExternalError> {
         let scale_factor = self.scale_factor();
@@ -632,6 +806,14 @@ impl Window {
             .map(|_| self.request_redraw())
     }
 
+    #[inline]
+    pub fn set_title_bar_region(&self, region: Option<(i32, i32, u32, u32)>) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_title_bar_region(region);
+    }
+
     #[inline]
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         self.window_state.lock().unwrap().drag_window()
@@ -656,13 +838,11 @@ impl Window {
 
     #[inline]
     pub fn set_ime_cursor_area(&self, position: Position, size: Size) {
-        let window_state = self.window_state.lock().unwrap();
-        if window_state.ime_allowed() {
-            let scale_factor = window_state.scale_factor();
-            let position = position.to_logical(scale_factor);
-            let size = size.to_logical(scale_factor);
-            window_state.set_ime_cursor_area(position, size);
-        }
+        let mut window_state = self.window_state.lock().unwrap();
+        let scale_factor = window_state.scale_factor();
+        let position = position.to_logical(scale_factor);
+        let size = size.to_logical(scale_factor);
+        window_state.set_ime_cursor_area(position, size);
     }
 
     #[inline]
@@ -766,7 +946,10 @@ impl Window {
         self.window_state.lock().unwrap().theme()
     }
 
-    pub fn set_content_protected(&self, _protected: bool) {}
+    pub fn set_content_protected(&self, _protected: bool) {
+        // NOTE: there's no protocol for the clients to opt out of the screen capture, like
+        // `wlr_screencopy` or `ext_image_copy_capture`, the compositors decide on their own.
+    }
 
     #[inline]
     pub fn title(&self) -> String {
@@ -806,6 +989,211 @@ impl Window {
             .unwrap()
             .set_keyboard_interactivity(keyboard_interactivity);
     }
+
+    #[inline]
+    pub fn request_keyboard_focus(&self) {
+        self.window_state.lock().unwrap().set_keyboard_focus(true);
+    }
+
+    #[inline]
+    pub fn release_keyboard_focus(&self) {
+        self.window_state.lock().unwrap().set_keyboard_focus(false);
+    }
+
+    #[inline]
+    pub fn show_window_menu_at(&self, x: i32, y: i32) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .show_window_menu(LogicalPosition::new(x, y));
+    }
+
+    #[inline]
+    pub fn suggested_bounds(&self) -> Option<(u32, u32)> {
+        self.window_state
+            .lock()
+            .unwrap()
+            .suggested_bounds()
+            .map(Into::into)
+    }
+
+    #[inline]
+    pub fn has_server_side_decorations(&self) -> bool {
+        self.window_state
+            .lock()
+            .unwrap()
+            .has_server_side_decorations()
+    }
+
+    #[inline]
+    pub fn toplevel_state(&self) -> XdgWindowState {
+        self.window_state.lock().unwrap().toplevel_state()
+    }
+
+    #[inline]
+    pub fn wm_capabilities(&self) -> WindowManagerCapabilities {
+        self.window_state.lock().unwrap().wm_capabilities()
+    }
+
+    #[inline]
+    pub fn cursor_available(&self, cursor_icon: CursorIcon) -> bool {
+        self.window_state
+            .lock()
+            .unwrap()
+            .cursor_available(cursor_icon)
+    }
+
+    #[inline]
+    pub fn instance_name(&self) -> Option<String> {
+        self.instance_name.clone()
+    }
+
+    #[inline]
+    pub fn set_title_and_app_id(&self, title: String, app_id: String) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_title_and_app_id(title, app_id);
+    }
+
+    #[inline]
+    pub fn last_frame_timing(&self) -> Option<FrameTiming> {
+        self.window_state.lock().unwrap().last_frame_timing()
+    }
+
+    #[inline]
+    pub fn set_frame_rate_cap(&self, fps: Option<u32>) {
+        self.window_state.lock().unwrap().set_frame_rate_cap(fps);
+        // Deliver the redraw held by the previous cap.
+        self.event_loop_awakener.ping();
+    }
+
+    #[inline]
+    pub fn set_scale_preference(&self, scale_preference: ScalePreference) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_scale_preference(scale_preference);
+        // Apply the new scale from the event loop.
+        self.event_loop_awakener.ping();
+    }
+
+    #[inline]
+    pub fn current_refresh_interval(&self) -> Option<Duration> {
+        let timing = self.window_state.lock().unwrap().last_frame_timing();
+        timing
+            .and_then(|timing| timing.refresh_interval)
+            .or_else(|| {
+                let millihertz = self.current_monitor()?.refresh_rate_millihertz()?;
+                (millihertz != 0)
+                    .then(|| Duration::from_nanos(1_000_000_000_000 / millihertz as u64))
+            })
+    }
+
+    #[inline]
+    pub fn set_window_geometry(&self, geometry: Option<(i32, i32, i32, i32)>) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_window_geometry(geometry);
+    }
+
+    #[inline]
+    pub fn preferred_buffer_transform(&self) -> Option<OutputTransform> {
+        let surface = self.window.wl_surface();
+        // The preferred transform is only sent starting with `wl_surface` v6.
+        if surface.version() < 6 {
+            return None;
+        }
+
+        let transform = surface.data::<SurfaceData>()?.transform();
+        Some(match transform {
+            Transform::_90 => OutputTransform::Rotate90,
+            Transform::_180 => OutputTransform::Rotate180,
+            Transform::_270 => OutputTransform::Rotate270,
+            Transform::Flipped => OutputTransform::Flipped,
+            Transform::Flipped90 => OutputTransform::Flipped90,
+            Transform::Flipped180 => OutputTransform::Flipped180,
+            Transform::Flipped270 => OutputTransform::Flipped270,
+            _ => OutputTransform::Normal,
+        })
+    }
+
+    #[inline]
+    pub fn preferred_dmabuf_device(&self) -> Option<libc::dev_t> {
+        let mut window_state = self.window_state.lock().unwrap();
+
+        // The feedback is only created once it's asked for, by the event loop.
+        if window_state.request_dmabuf_feedback() {
+            self.event_loop_awakener.ping();
+        }
+
+        window_state.dmabuf_device
+    }
+
+    #[inline]
+    pub fn buffer_scale(&self) -> u32 {
+        self.window_state.lock().unwrap().buffer_scale()
+    }
+
+    #[inline]
+    pub fn set_parent(&self, parent: Option<&XdgToplevel>) {
+        self.window.set_parent(parent);
+    }
+
+    #[inline]
+    pub fn set_modal(&self, modal: bool) {
+        // NOTE: wayland-protocols doesn't ship `xdg_wm_dialog_v1` yet, so the global is never
+        // bound and the hint is handled like when the compositor doesn't advertise it.
+        if modal {
+            warn!("The modal hint is ignored, since xdg_wm_dialog_v1 isn't supported");
+        }
+    }
+
+    #[inline]
+    pub fn set_preferred_drop_action(&self, action: DndAction) {
+        let action = SctkDndAction::from_bits_truncate(action.bits());
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_preferred_drop_action(action);
+    }
+
+    #[inline]
+    pub fn set_drag_accepting(&self, mime_type: Option<String>) {
+        self.window_state
+            .lock()
+            .unwrap()
+            .set_drag_accepting(mime_type);
+    }
+
+    /// The `xdg_toplevel` of the window to use it as a parent of other windows.
+    #[inline]
+    pub fn xdg_toplevel(&self) -> Option<XdgToplevel> {
+        self.window.xdg_toplevel().cloned()
+    }
+
+    /// The layer surface of the window to use it as a parent of popups.
+    #[inline]
+    pub fn layer_surface(&self) -> Option<LayerSurface> {
+        match &self.window {
+            WindowShell::WlrLayer { surface } => Some(surface.clone()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn commit(&self) {
+        // NOTE: all the surface state winit sets is sent right away, thus committing is enough
+        // to apply it together with the user's state.
+        trace_protocol!("-> {}.commit()", self.window.wl_surface().id());
+        self.window.wl_surface().commit();
+
+        // Don't wait for the event loop to flush the commit.
+        if let Err(err) = self.window_state.lock().unwrap().connection.flush() {
+            warn!("Failed to flush the commit: {err}");
+        }
+    }
 }
 
 impl Drop for Window {
@@ -818,6 +1206,7 @@ impl Drop for Window {
 enum WindowShell {
     Xdg { window: SctkWindow },
     WlrLayer { surface: LayerSurface },
+    Popup { popup: Popup },
 }
 
 impl WindowShell {
@@ -833,13 +1222,21 @@ impl WindowShell {
             WindowShell::WlrLayer { .. } => {
                 warn!("Maximizing is ignored for layer_shell windows")
             }
+            WindowShell::Popup { .. } => {
+                warn!("Maximizing is ignored for popups")
+            }
         }
     }
 
     pub fn set_minimized(&self) {
         match self {
             WindowShell::Xdg { window } => window.set_minimized(),
-            WindowShell::WlrLayer { .. } => warn!("Minimizing is ignored for layer_shell windows"),
+            WindowShell::WlrLayer { .. } => {
+                warn!("Minimizing is ignored for layer_shell windows")
+            }
+            WindowShell::Popup { .. } => {
+                warn!("Minimizing is ignored for popups")
+            }
         }
     }
 
@@ -848,14 +1245,43 @@ impl WindowShell {
             WindowShell::Xdg { window } => {
                 window.set_fullscreen(output);
             }
-            WindowShell::WlrLayer { .. } => warn!("Fullscreen is ignored for layer_shell windows"),
+            WindowShell::WlrLayer { .. } => {
+                warn!("Fullscreen is ignored for layer_shell windows")
+            }
+            WindowShell::Popup { .. } => {
+                warn!("Fullscreen is ignored for popups")
+            }
         }
     }
 
     pub fn unset_fullscreen(&self) {
         match self {
             WindowShell::Xdg { window } => window.unset_fullscreen(),
-            WindowShell::WlrLayer { .. } => warn!("Fullscreen is ignored for layer_shell windows"),
+            WindowShell::WlrLayer { .. } => {
+                warn!("Fullscreen is ignored for layer_shell windows")
+            }
+            WindowShell::Popup { .. } => {
+                warn!("Fullscreen is ignored for popups")
+            }
+        }
+    }
+
+    pub fn set_parent(&self, parent: Option<&XdgToplevel>) {
+        match self {
+            WindowShell::Xdg { window } => window.xdg_toplevel().set_parent(parent),
+            WindowShell::WlrLayer { .. } => {
+                warn!("Parent is ignored for layer_shell windows")
+            }
+            WindowShell::Popup { .. } => {
+                warn!("Parent is ignored for popups")
+            }
+        }
+    }
+
+    pub fn xdg_toplevel(&self) -> Option<&XdgToplevel> {
+        match self {
+            WindowShell::Xdg { window } => Some(window.xdg_toplevel()),
+            WindowShell::WlrLayer { .. } | WindowShell::Popup { .. } => None,
         }
     }
 
@@ -863,10 +1289,32 @@ impl WindowShell {
         match self {
             WindowShell::Xdg { window } => window.wl_surface(),
             WindowShell::WlrLayer { surface } => surface.wl_surface(),
+            WindowShell::Popup { popup } => popup.wl_surface(),
         }
     }
 }
 
+/// The output to fullscreen the window on, `None` lets the compositor pick it.
+fn fullscreen_output(monitor: Option<PlatformMonitorHandle>) -> Option<WlOutput> {
+    let output = match monitor? {
+        PlatformMonitorHandle::Wayland(monitor) => monitor.proxy,
+        #[cfg(x11_platform)]
+        PlatformMonitorHandle::X(_) => {
+            warn!("X11 monitors are ignored for Wayland fullscreen");
+            return None;
+        }
+    };
+
+    // The monitor could be unplugged since the handle was obtained, and sending a dead output
+    // would make the request fail.
+    if output.is_alive() {
+        Some(output)
+    } else {
+        warn!("The fullscreen monitor is gone, letting the compositor pick the output");
+        None
+    }
+}
+
 /// The request from the window to the event loop.
 #[derive(Debug)]
 pub struct WindowRequests {
@@ -2,29 +2,39 @@
 
 use std::num::NonZeroU32;
 use std::sync::{Arc, Mutex, Weak};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use ahash::HashSet;
-use log::{error, info, warn};
+use libc::dev_t;
 
+use sctk::reexports::calloop::RegistrationToken;
 use sctk::reexports::client::backend::ObjectId;
+use sctk::reexports::client::protocol::wl_buffer::WlBuffer;
+use sctk::reexports::client::protocol::wl_data_device_manager::DndAction;
+use sctk::reexports::client::protocol::wl_output::WlOutput;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_shm::WlShm;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle};
 use sctk::reexports::csd_frame::{
-    DecorationsFrame, FrameAction, FrameClick, ResizeEdge, WindowState as XdgWindowState,
+    DecorationsFrame, FrameAction, FrameClick, ResizeEdge, WindowManagerCapabilities,
+    WindowState as XdgWindowState,
 };
 use sctk::reexports::protocols::wp::fractional_scale::v1::client::wp_fractional_scale_v1::WpFractionalScaleV1;
+use sctk::reexports::protocols::wp::linux_dmabuf::zv1::client::zwp_linux_dmabuf_feedback_v1::ZwpLinuxDmabufFeedbackV1;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::ZwpTextInputV3;
 use sctk::reexports::protocols::wp::viewporter::client::wp_viewport::WpViewport;
 use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::ResizeEdge as XdgResizeEdge;
 
 use sctk::compositor::{CompositorState, Region, SurfaceData, SurfaceDataExt};
+use sctk::data_device_manager::data_offer::DragOffer;
+use sctk::dmabuf::DmabufState;
+use sctk::output::OutputData;
 use sctk::seat::pointer::{PointerDataExt, ThemedPointer};
 use sctk::shell::wlr_layer::{
     Anchor, KeyboardInteractivity, Layer, LayerSurface, LayerSurfaceConfigure,
 };
+use sctk::shell::xdg::popup::{Popup, PopupConfigure};
 use sctk::shell::xdg::window::{DecorationMode, Window, WindowConfigure};
 use sctk::shell::xdg::XdgSurface;
 use sctk::shell::WaylandSurface;
@@ -36,16 +46,25 @@ use wayland_protocols_plasma::blur::client::org_kde_kwin_blur::OrgKdeKwinBlur;
 use crate::cursor::CustomCursor as RootCustomCursor;
 use crate::dpi::{LogicalPosition, LogicalSize, PhysicalSize, Size};
 use crate::error::{ExternalError, NotSupportedError};
+use crate::platform::wayland::{FrameTiming, ScalePreference};
 use crate::platform_impl::wayland::logical_to_physical_rounded;
-use crate::platform_impl::wayland::types::cursor::{CustomCursor, SelectedCursor};
+use crate::platform_impl::wayland::types::cursor::{
+    closest_cursor_shape, CursorTheme, CustomCursor, SelectedCursor,
+};
 use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
-use crate::platform_impl::{PlatformCustomCursor, WindowId};
+use crate::platform_impl::wayland::types::wp_presentation::{
+    FrameTimingTracker, PresentationManager,
+};
+use crate::platform_impl::{
+    MonitorHandle as PlatformMonitorHandle, PlatformCustomCursor, WindowId,
+};
 use crate::window::{CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme};
 
 use crate::platform_impl::wayland::seat::{
-    PointerConstraintsState, WinitPointerData, WinitPointerDataExt, ZwpTextInputV3Ext,
+    PointerConstraintsState, WindowDrag, WinitPointerData, WinitPointerDataExt, ZwpTextInputV3Ext,
 };
 use crate::platform_impl::wayland::state::{WindowCompositorUpdate, WinitState};
+use crate::platform_impl::wayland::{error, info, warn};
 
 #[cfg(feature = "sctk-adwaita")]
 pub type WinitFrame = sctk_adwaita::AdwaitaFrame<WinitState>;
@@ -55,6 +74,12 @@ pub type WinitFrame = sctk::shell::xdg::fallback_frame::FallbackFrame<WinitState
 // Minimum window inner size.
 const MIN_WINDOW_SIZE: LogicalSize<u32> = LogicalSize::new(2, 1);
 
+// The width of the resize border handled without the decorations, in logical pixels.
+const RESIZE_BORDER_SIZE: f64 = 8.;
+
+// The maximum interval between the presses of a double click on the title bar region.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
 /// The state of the window which is being updated from the [`WinitState`].
 pub struct WindowState {
     /// The connection to Wayland server.
@@ -71,6 +96,21 @@ pub struct WindowState {
 
     selected_cursor: SelectedCursor,
 
+    /// The cursor theme forced by the user, if any.
+    cursor_theme: Option<CursorTheme>,
+
+    /// Whether the compositor draws the named cursors with the cursor shape protocol.
+    cursor_shape: bool,
+
+    /// The system cursor theme, loaded to look the available cursors up.
+    system_cursor_theme: Option<CursorTheme>,
+
+    /// The region of the surface acting as the title bar.
+    title_bar: TitleBarState,
+
+    /// The resize border on the edges of the surface, without the decorations.
+    resize_border: ResizeBorderState,
+
     /// Whether the cursor is visible.
     pub cursor_visible: bool,
 
@@ -112,6 +152,9 @@ pub struct WindowState {
     /// The current IME purpose.
     ime_purpose: ImePurpose,
 
+    /// The IME cursor area, kept to send it again when the text input is enabled.
+    ime_cursor_area: Option<(LogicalPosition<u32>, LogicalSize<u32>)>,
+
     /// The text inputs observed on the window.
     text_inputs: Vec<ZwpTextInputV3>,
 
@@ -122,10 +165,58 @@ pub struct WindowState {
     /// configure.
     initial_size: Option<Size>,
 
+    /// The minimum of the initial size when it's clamped to the bounds of the initial configure.
+    pub initial_min_size: Option<Size>,
+
+    /// Whether the window is mapped or waiting to be mapped by the compositor.
+    pub visible: bool,
+
+    /// Whether the redraws are limited to one per frame callback.
+    pub redraw_throttle: bool,
+
+    /// The minimum interval between the redraws set by the user.
+    frame_rate_cap: Option<Duration>,
+
+    /// The moment of the latest redraw delivered with the frame rate cap.
+    last_redraw: Option<Instant>,
+
+    /// The timer waking the loop up for the redraw held by the frame rate cap.
+    pub frame_rate_timer: Option<RegistrationToken>,
+
     viewport: Option<WpViewport>,
     fractional_scale: Option<WpFractionalScaleV1>,
+
+    /// The integer scale forced by the user instead of the one preferred by the compositor.
+    forced_scale: Option<i32>,
+
+    /// The transparent buffer attached to the input only window, which is never drawn.
+    input_buffer: Option<WlBuffer>,
+
+    /// How the scale is picked when the window is on multiple monitors.
+    scale_preference: ScalePreference,
+
+    /// The latest scale preferred by the compositor.
+    compositor_scale: f64,
+
+    /// The dmabuf feedback for the surface, which is only created once the user asks for it.
+    dmabuf_feedback: DmabufFeedbackRequest,
+
+    /// The device the compositor prefers the buffers to be allocated on.
+    pub dmabuf_device: Option<dev_t>,
+
+    /// The drag and drop offer over the window.
+    drag: Option<WindowDrag>,
+
+    /// The action preferred for the drops on the window, set by the user.
+    preferred_drop_action: Option<DndAction>,
     blur: Option<OrgKdeKwinBlur>,
     blur_manager: Option<KWinBlurManager>,
+
+    /// The presentation time manager to request the presentation feedback.
+    presentation: Option<PresentationManager>,
+
+    /// The timing of the presented frames.
+    frame_timing: FrameTimingTracker,
 }
 
 enum ShellSpecificState {
@@ -145,9 +236,13 @@ enum ShellSpecificState {
         /// Whether we should decorate the frame.
         decorate: bool,
 
-        /// Min size.
-        min_inner_size: LogicalSize<u32>,
-        max_inner_size: Option<LogicalSize<u32>>,
+        /// Min and max sizes as requested by the user, so they could be reapplied when the
+        /// scale factor changes.
+        min_inner_size: Option<Size>,
+        max_inner_size: Option<Size>,
+
+        /// The resize increments requested by the user.
+        resize_increments: Option<Size>,
 
         /// The size of the window when no states were applied to it. The primary use for it
         /// is to fallback to original window size, before it was maximized, if the compositor
@@ -162,6 +257,9 @@ enum ShellSpecificState {
         /// The value is the serial of the event triggered moved.
         has_pending_move: Option<u32>,
 
+        /// The window geometry set by the user, which overrides the computed one.
+        window_geometry: Option<(i32, i32, i32, i32)>,
+
         /// The underlying SCTK window.
         window: Window,
     },
@@ -169,6 +267,29 @@ enum ShellSpecificState {
         surface: LayerSurface,
 
         last_configure: Option<LayerSurfaceConfigure>,
+
+        /// The edges the surface is anchored to, where the compositor picks the size along the
+        /// axes anchored to both edges.
+        anchor: Anchor,
+
+        /// The margin and exclusive zone, which are sent again together with the anchor.
+        margin: (i32, i32, i32, i32),
+        exclusive_zone: i32,
+
+        /// The keyboard interactivity set by the user.
+        keyboard_interactivity: KeyboardInteractivity,
+
+        /// The interactivity temporarily sent instead to move the keyboard focus, until the
+        /// focus moved.
+        focus_interactivity: Option<KeyboardInteractivity>,
+    },
+    Popup {
+        popup: Popup,
+
+        last_configure: Option<PopupConfigure>,
+
+        /// Whether the popup was unmapped, after which it can't be mapped again.
+        unmapped: bool,
     },
 }
 
@@ -191,6 +312,7 @@ impl WindowState {
         match &self.shell_specific {
             ShellSpecificState::Xdg { window, .. } => window.wl_surface(),
             ShellSpecificState::WlrLayer { surface, .. } => surface.wl_surface(),
+            ShellSpecificState::Popup { popup, .. } => popup.wl_surface(),
         }
     }
 
@@ -201,7 +323,9 @@ impl WindowState {
                 frame_callback_state,
                 ..
             } => frame_callback_state,
-            ShellSpecificState::WlrLayer { .. } => FrameCallbackState::None,
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => {
+                FrameCallbackState::None
+            }
         }
     }
 
@@ -214,7 +338,7 @@ impl WindowState {
             } => {
                 *frame_callback_state = FrameCallbackState::Received;
             }
-            ShellSpecificState::WlrLayer { .. } => {}
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => {}
         }
     }
 
@@ -227,7 +351,7 @@ impl WindowState {
             } => {
                 *frame_callback_state = FrameCallbackState::None;
             }
-            ShellSpecificState::WlrLayer { .. } => {}
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => {}
         }
     }
 
@@ -242,14 +366,57 @@ impl WindowState {
                 FrameCallbackState::None | FrameCallbackState::Received => {
                     *frame_callback_state = FrameCallbackState::Requested;
                     let surface = window.wl_surface();
+                    trace_protocol!("-> {}.frame()", surface.id());
                     surface.frame(&self.queue_handle, surface.clone());
                 }
                 FrameCallbackState::Requested => (),
             },
-            ShellSpecificState::WlrLayer { .. } => {}
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => {}
+        }
+    }
+
+    /// Request the presentation feedback for the next commit of the surface.
+    pub fn request_presentation_feedback(&self) {
+        if let Some(presentation) = self.presentation.as_ref() {
+            presentation.feedback(self.wl_surface(), &self.queue_handle);
+        }
+    }
+
+    /// Record the frame presented by the compositor.
+    #[inline]
+    pub fn frame_presented(&mut self, presented_at: Duration, refresh_interval: Duration) {
+        self.frame_timing.presented(presented_at, refresh_interval);
+    }
+
+    /// Limit the redraws to `fps` per second, or remove the limit with `None`.
+    pub fn set_frame_rate_cap(&mut self, fps: Option<u32>) {
+        self.frame_rate_cap = fps
+            .filter(|&fps| fps != 0)
+            .map(|fps| Duration::from_secs(1) / fps);
+        if self.frame_rate_cap.is_none() {
+            self.last_redraw = None;
+        }
+    }
+
+    /// The moment the next redraw is allowed by the frame rate cap, when it's after `now`.
+    pub fn frame_rate_deadline(&self, now: Instant) -> Option<Instant> {
+        let deadline = self.last_redraw? + self.frame_rate_cap?;
+        (deadline > now).then_some(deadline)
+    }
+
+    /// Record the redraw delivered to the user at `now`.
+    pub fn redraw_delivered(&mut self, now: Instant) {
+        if self.frame_rate_cap.is_some() {
+            self.last_redraw = Some(now);
         }
     }
 
+    /// The timing of the latest presented frames.
+    #[inline]
+    pub fn last_frame_timing(&self) -> Option<FrameTiming> {
+        self.frame_timing.timing()
+    }
+
     pub fn configure_xdg(
         &mut self,
         configure: WindowConfigure,
@@ -263,6 +430,9 @@ impl WindowState {
             ref mut csd_fails,
             decorate,
             ref mut stateless_size,
+            resize_increments,
+            min_inner_size,
+            max_inner_size,
             ..
         } = self.shell_specific
         else {
@@ -277,6 +447,10 @@ impl WindowState {
             self.size = initial_size.to_logical(self.scale_factor);
             *stateless_size = self.size;
         }
+        let initial_min_size = self
+            .initial_min_size
+            .take()
+            .map(|size| size.to_logical::<u32>(self.scale_factor));
 
         if let Some(subcompositor) = subcompositor.as_ref().filter(|_| {
             configure.decoration_mode == DecorationMode::Client && frame.is_none() && !*csd_fails
@@ -345,6 +519,30 @@ impl WindowState {
                 .unwrap_or(new_size.height);
         }
 
+        // Only snap the stateless configures to the resize increments, since the compositor
+        // expects the exact size for the maximized, fullscreen and tiled states.
+        if let Some(increments) = resize_increments.filter(|_| stateless) {
+            new_size = snap_to_increments(new_size, increments.to_logical(self.scale_factor));
+
+            // Snapping rounds the size down, so clamp it back to the limits of the user.
+            if let Some(min_size) = min_inner_size {
+                let min_size: LogicalSize<u32> = min_size.to_logical(self.scale_factor);
+                new_size.width = new_size.width.max(min_size.width);
+                new_size.height = new_size.height.max(min_size.height);
+            }
+            if let Some(max_size) = max_inner_size {
+                let max_size: LogicalSize<u32> = max_size.to_logical(self.scale_factor);
+                new_size.width = new_size.width.min(max_size.width);
+                new_size.height = new_size.height.min(max_size.height);
+            }
+        }
+
+        // The bounds shouldn't shrink the initial size below the minimum of the user.
+        if let Some(min_size) = initial_min_size.filter(|_| constrain) {
+            new_size.width = new_size.width.max(min_size.width);
+            new_size.height = new_size.height.max(min_size.height);
+        }
+
         let new_state = configure.state;
         let old_state = last_configure.as_ref().map(|configure| configure.state);
 
@@ -389,6 +587,24 @@ impl WindowState {
         true
     }
 
+    pub fn configure_popup(&mut self, configure: PopupConfigure) -> bool {
+        let ShellSpecificState::Popup { last_configure, .. } = &mut self.shell_specific else {
+            error!("configure_popup called in non-popup context");
+            return true;
+        };
+
+        // The compositor always sends the size of the popup, but stay defensive.
+        let new_size = match (configure.width, configure.height) {
+            (width, height) if width > 0 && height > 0 => (width as u32, height as u32).into(),
+            _ => self.size,
+        };
+
+        *last_configure = Some(configure);
+        self.resize(new_size);
+
+        true
+    }
+
     /// Compute the bounds for the inner size of the surface.
     fn inner_size_bounds(
         frame: &Option<WinitFrame>,
@@ -420,39 +636,107 @@ impl WindowState {
 
     /// Start interacting drag resize.
     pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), ExternalError> {
-        match &self.shell_specific {
-            ShellSpecificState::Xdg { window, .. } => {
-                let xdg_toplevel = window.xdg_toplevel();
+        let ShellSpecificState::Xdg { window, .. } = &self.shell_specific else {
+            return Ok(());
+        };
 
-                // TODO(kchibisov) handle touch serials.
-                self.apply_on_pointer(|_, data| {
-                    let serial = data.latest_button_serial();
-                    let seat = data.seat();
-                    xdg_toplevel.resize(seat, serial, direction.into());
-                });
+        let xdg_toplevel = window.xdg_toplevel();
+        let mut resized = false;
+        // TODO(kchibisov) handle touch serials.
+        for pointer in self.pointers.iter().filter_map(Weak::upgrade) {
+            let data = pointer.pointer().winit_data();
+            // The compositor will ignore the request without a button held down.
+            if let Some(serial) = data.latest_button_press_serial() {
+                xdg_toplevel.resize(data.seat(), serial, direction.into());
+                resized = true;
             }
-            ShellSpecificState::WlrLayer { .. } => {}
         }
 
-        Ok(())
+        if resized {
+            Ok(())
+        } else {
+            Err(ExternalError::NotSupported(NotSupportedError::new()))
+        }
     }
 
     /// Start the window drag.
     pub fn drag_window(&self) -> Result<(), ExternalError> {
-        match &self.shell_specific {
-            ShellSpecificState::Xdg { window, .. } => {
-                let xdg_toplevel = window.xdg_toplevel();
-                // TODO(kchibisov) handle touch serials.
-                self.apply_on_pointer(|_, data| {
-                    let serial = data.latest_button_serial();
-                    let seat = data.seat();
-                    xdg_toplevel._move(seat, serial);
-                });
+        let ShellSpecificState::Xdg { window, .. } = &self.shell_specific else {
+            return Ok(());
+        };
+
+        let xdg_toplevel = window.xdg_toplevel();
+        let mut dragged = false;
+        // TODO(kchibisov) handle touch serials.
+        for pointer in self.pointers.iter().filter_map(Weak::upgrade) {
+            let data = pointer.pointer().winit_data();
+            // The compositor will ignore the request without a button held down.
+            if let Some(serial) = data.latest_button_press_serial() {
+                xdg_toplevel._move(data.seat(), serial);
+                dragged = true;
             }
-            ShellSpecificState::WlrLayer { .. } => {} // TODO(theonlymrcat): This match should be replaced with let...else
         }
 
-        Ok(())
+        if dragged {
+            Ok(())
+        } else {
+            Err(ExternalError::NotSupported(NotSupportedError::new()))
+        }
+    }
+
+    /// Track the drag and drop offer entering the window.
+    pub fn set_drag_offer(&mut self, offer: DragOffer) {
+        if let Some(action) = self.preferred_drop_action {
+            set_drop_actions(&offer, action);
+        }
+
+        self.drag = Some(WindowDrag {
+            offer,
+            accepted_mime_type: None,
+        });
+    }
+
+    /// Stop tracking the drag and drop offer, once it left the window or was dropped on it.
+    pub fn take_drag(&mut self) -> Option<WindowDrag> {
+        self.drag.take()
+    }
+
+    /// Accept the current drag and drop offer as the mime type, or reject it with `None`.
+    pub fn set_drag_accepting(&mut self, mime_type: Option<String>) {
+        let Some(drag) = self.drag.as_mut() else {
+            return;
+        };
+
+        let mime_type = mime_type.filter(|mime_type| {
+            let offered = drag
+                .offer
+                .with_mime_types(|mime_types| mime_types.contains(mime_type));
+            if !offered {
+                warn!("The drag and drop isn't offered as {mime_type}, so it's rejected");
+            }
+            offered
+        });
+
+        drag.offer
+            .accept_mime_type(drag.offer.serial, mime_type.clone());
+        drag.accepted_mime_type = mime_type;
+    }
+
+    /// Set the action preferred for the drops on the window.
+    pub fn set_preferred_drop_action(&mut self, action: DndAction) {
+        // The compositor only takes a single preferred action.
+        let action = if action.bits().count_ones() > 1 {
+            warn!("Only the first of the preferred drop actions {action:?} is used");
+            DndAction::from_bits_truncate(action.bits() & action.bits().wrapping_neg())
+        } else {
+            action
+        };
+
+        if let Some(drag) = self.drag.as_ref() {
+            set_drop_actions(&drag.offer, action);
+        }
+
+        self.preferred_drop_action = Some(action);
     }
 
     /// Tells whether the window should be closed.
@@ -499,7 +783,7 @@ impl WindowState {
                     _ => (),
                 };
             }
-            ShellSpecificState::WlrLayer { .. } => {} // TODO(theonlymrcat): This match should be replaced with let...else
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => {} // TODO(theonlymrcat): This match should be replaced with let...else
         }
 
         Some(false)
@@ -512,7 +796,7 @@ impl WindowState {
                     frame.click_point_left();
                 }
             }
-            ShellSpecificState::WlrLayer { .. } => {} // TODO(theonlymrcat): This match should be replaced with let...else
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => {} // TODO(theonlymrcat): This match should be replaced with let...else
         }
     }
 
@@ -549,8 +833,186 @@ impl WindowState {
                     None
                 }
             }
-            ShellSpecificState::WlrLayer { .. } => None,
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => None,
+        }
+    }
+
+    /// Set the region of the surface acting as the title bar.
+    #[inline]
+    pub fn set_title_bar_region(&mut self, region: Option<(i32, i32, u32, u32)>) {
+        self.title_bar.region = region;
+    }
+
+    /// Handle the press or release of the main button on the surface.
+    ///
+    /// Returns `true` when the click happened on the title bar region and must not be delivered
+    /// to the user.
+    pub fn title_bar_click(
+        &mut self,
+        pressed: bool,
+        serial: u32,
+        timestamp: Duration,
+        x: f64,
+        y: f64,
+    ) -> bool {
+        let ShellSpecificState::Xdg {
+            window,
+            last_configure,
+            has_pending_move,
+            ..
+        } = &mut self.shell_specific
+        else {
+            return false;
+        };
+
+        if !pressed {
+            if !self.title_bar.pressed {
+                return false;
+            }
+
+            // Drop the move when the button was released without moving the pointer.
+            self.title_bar.pressed = false;
+            *has_pending_move = None;
+            return true;
+        }
+
+        if !self.title_bar.contains(x, y) {
+            return false;
+        }
+
+        self.title_bar.pressed = true;
+        let double_click = self
+            .title_bar
+            .last_press
+            .and_then(|last_press| timestamp.checked_sub(last_press))
+            .is_some_and(|interval| interval <= DOUBLE_CLICK_INTERVAL);
+        if double_click {
+            self.title_bar.last_press = None;
+            let maximized = last_configure
+                .as_ref()
+                .is_some_and(|configure| configure.is_maximized());
+            if maximized {
+                window.unset_maximized();
+            } else {
+                window.set_maximized();
+            }
+        } else {
+            self.title_bar.last_press = Some(timestamp);
+            // Start the move once the pointer moves, so the double click could still happen.
+            *has_pending_move = Some(serial);
+        }
+
+        true
+    }
+
+    /// Start the pending move of the window with the title bar region.
+    pub fn title_bar_point_moved(&mut self, seat: &WlSeat) {
+        if !self.title_bar.pressed {
+            return;
+        }
+
+        if let ShellSpecificState::Xdg {
+            window,
+            has_pending_move,
+            ..
+        } = &mut self.shell_specific
+        {
+            if let Some(serial) = has_pending_move.take() {
+                window.move_(seat, serial);
+            }
+        }
+    }
+
+    /// Reset the title bar state once the pointer left the surface, e.g. during the move.
+    #[inline]
+    pub fn title_bar_point_left(&mut self) {
+        self.title_bar.pressed = false;
+    }
+
+    /// Handle the resize border on the edges of the surface, when winit doesn't draw the
+    /// decorations.
+    #[inline]
+    pub fn set_resize_border(&mut self, enabled: bool) {
+        self.resize_border.enabled = enabled;
+    }
+
+    /// The edge of the resize border at the given point of the surface.
+    fn resize_border_edge(&self, x: f64, y: f64) -> Option<ResizeDirection> {
+        let ShellSpecificState::Xdg {
+            last_configure,
+            frame,
+            resizable,
+            ..
+        } = &self.shell_specific
+        else {
+            return None;
+        };
+
+        // The compositor or the frame handle the resizing when they draw the decorations.
+        let frame_visible = frame.as_ref().is_some_and(|frame| !frame.is_hidden());
+        let configure = last_configure.as_ref()?;
+        if !self.resize_border.enabled
+            || !*resizable
+            || frame_visible
+            || configure.decoration_mode == DecorationMode::Server
+            || !Self::is_stateless(configure)
+        {
+            return None;
+        }
+
+        let (width, height) = (self.size.width as f64, self.size.height as f64);
+        let left = x < RESIZE_BORDER_SIZE;
+        let right = x >= width - RESIZE_BORDER_SIZE;
+        let top = y < RESIZE_BORDER_SIZE;
+        let bottom = y >= height - RESIZE_BORDER_SIZE;
+        match (left, right, top, bottom) {
+            (true, _, true, _) => Some(ResizeDirection::NorthWest),
+            (_, true, true, _) => Some(ResizeDirection::NorthEast),
+            (true, _, _, true) => Some(ResizeDirection::SouthWest),
+            (_, true, _, true) => Some(ResizeDirection::SouthEast),
+            (true, ..) => Some(ResizeDirection::West),
+            (_, true, ..) => Some(ResizeDirection::East),
+            (_, _, true, _) => Some(ResizeDirection::North),
+            (.., true) => Some(ResizeDirection::South),
+            _ => None,
+        }
+    }
+
+    /// Update the cursor when the pointer moves over the resize border.
+    pub fn resize_border_point_moved(&mut self, x: f64, y: f64) {
+        let edge = self.resize_border_edge(x, y);
+        if edge == self.resize_border.edge {
+            return;
+        }
+
+        self.resize_border.edge = edge;
+        self.reload_cursor_style();
+    }
+
+    /// Handle the press or release of the main button on the resize border.
+    ///
+    /// Returns `true` when the window is resized and the click must not be delivered to the user.
+    pub fn resize_border_click(&mut self, pressed: bool, seat: &WlSeat, serial: u32) -> bool {
+        if !pressed {
+            return std::mem::take(&mut self.resize_border.pressed);
         }
+
+        let (Some(edge), ShellSpecificState::Xdg { window, .. }) =
+            (self.resize_border.edge, &self.shell_specific)
+        else {
+            return false;
+        };
+
+        window.resize(seat, serial, edge.into());
+        self.resize_border.pressed = true;
+        true
+    }
+
+    /// Reset the resize border state once the pointer left the surface.
+    #[inline]
+    pub fn resize_border_point_left(&mut self) {
+        self.resize_border.edge = None;
+        self.resize_border.pressed = false;
     }
 
     /// Get the stored resizable state.
@@ -558,7 +1020,7 @@ impl WindowState {
     pub fn resizable(&self) -> bool {
         match self.shell_specific {
             ShellSpecificState::Xdg { resizable, .. } => resizable,
-            ShellSpecificState::WlrLayer { .. } => false,
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => false,
         }
     }
 
@@ -584,15 +1046,16 @@ impl WindowState {
                 }
                 return false;
             }
+            ShellSpecificState::Popup { .. } => {
+                if resizable {
+                    warn!("Resizable is ignored for popups");
+                }
+                return false;
+            }
         }
 
-        if resizable {
-            // Restore min/max sizes of the window.
-            self.reload_min_max_hints();
-        } else {
-            self.set_min_inner_size(Some(self.size));
-            self.set_max_inner_size(Some(self.size));
-        }
+        // Restore min/max sizes of the window or lock them to the current size.
+        self.reload_min_max_hints();
 
         // Reload the state on the frame as well.
         match &mut self.shell_specific {
@@ -602,7 +1065,9 @@ impl WindowState {
                 frame.set_resizable(resizable);
             }
             ShellSpecificState::Xdg { frame: None, .. } => {}
-            ShellSpecificState::WlrLayer { .. } => unreachable!(),
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => {
+                unreachable!()
+            }
         }
 
         true
@@ -614,6 +1079,14 @@ impl WindowState {
         !self.seat_focus.is_empty()
     }
 
+    /// Whether any pointer is over the window.
+    #[inline]
+    pub fn has_pointer_focus(&self) -> bool {
+        self.pointers
+            .iter()
+            .any(|pointer| pointer.strong_count() > 0)
+    }
+
     /// Whether the IME is allowed.
     #[inline]
     pub fn ime_allowed(&self) -> bool {
@@ -626,12 +1099,25 @@ impl WindowState {
         self.size
     }
 
+    /// Whether the compositor suspended the window, e.g. when it's minimized.
+    #[inline]
+    pub fn is_suspended(&self) -> bool {
+        match &self.shell_specific {
+            ShellSpecificState::Xdg { last_configure, .. } => last_configure
+                .as_ref()
+                .map(|configure| configure.state.contains(XdgWindowState::SUSPENDED))
+                .unwrap_or_default(),
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => false,
+        }
+    }
+
     /// Whether the window received initial configure event from the compositor.
     #[inline]
     pub fn is_configured(&self) -> bool {
         match &self.shell_specific {
             ShellSpecificState::Xdg { last_configure, .. } => last_configure.is_some(),
             ShellSpecificState::WlrLayer { last_configure, .. } => last_configure.is_some(),
+            ShellSpecificState::Popup { last_configure, .. } => last_configure.is_some(),
         }
     }
 
@@ -654,7 +1140,7 @@ impl WindowState {
                     true
                 }
             }
-            ShellSpecificState::WlrLayer { .. } => false,
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => false,
         }
     }
 
@@ -672,25 +1158,41 @@ impl WindowState {
         let viewport = winit_state
             .viewporter_state
             .as_ref()
+            .filter(|_| winit_state.fractional_scaling_manager.is_some())
             .map(|state| state.get_viewport(window.wl_surface(), queue_handle));
         let fractional_scale = winit_state
             .fractional_scaling_manager
             .as_ref()
             .map(|fsm| fsm.fractional_scaling(window.wl_surface(), queue_handle));
-
         Self {
             blur: None,
             blur_manager: winit_state.kwin_blur_manager.clone(),
+            presentation: winit_state.presentation.clone(),
+            frame_timing: Default::default(),
             compositor,
             connection,
             theme,
             cursor_grab_mode: GrabState::new(),
             selected_cursor: Default::default(),
+            cursor_theme: None,
+            cursor_shape: winit_state.cursor_shape,
+            system_cursor_theme: None,
+            title_bar: Default::default(),
+            resize_border: Default::default(),
             cursor_visible: true,
             fractional_scale,
+            forced_scale: None,
+            input_buffer: None,
+            drag: None,
+            preferred_drop_action: None,
+            scale_preference: ScalePreference::Compositor,
+            compositor_scale: 1.,
+            dmabuf_feedback: DmabufFeedbackRequest::None,
+            dmabuf_device: None,
             seat_focus: Default::default(),
             ime_allowed: false,
             ime_purpose: ImePurpose::Normal,
+            ime_cursor_area: None,
             pointer_constraints,
             pointers: Default::default(),
             queue_handle: queue_handle.clone(),
@@ -703,19 +1205,27 @@ impl WindowState {
                 has_pending_move: None,
                 last_configure: None,
                 max_inner_size: None,
-                min_inner_size: MIN_WINDOW_SIZE,
+                min_inner_size: None,
+                resize_increments: None,
                 resizable: true,
                 stateless_size: initial_size.to_logical(1.),
+                window_geometry: None,
                 window,
             },
             shm: winit_state.shm.wl_shm().clone(),
             custom_cursor_pool: winit_state.custom_cursor_pool.clone(),
             size: initial_size.to_logical(1.),
             initial_size: Some(initial_size),
+            initial_min_size: None,
             text_inputs: Vec::new(),
             title: String::default(),
             transparent: false,
             viewport,
+            visible: true,
+            redraw_throttle: false,
+            frame_rate_cap: None,
+            last_redraw: None,
+            frame_rate_timer: None,
         }
     }
 
@@ -732,26 +1242,42 @@ impl WindowState {
         let viewport = winit_state
             .viewporter_state
             .as_ref()
+            .filter(|_| winit_state.fractional_scaling_manager.is_some())
             .map(|state| state.get_viewport(layer_surface.wl_surface(), queue_handle));
         let fractional_scale = winit_state
             .fractional_scaling_manager
             .as_ref()
             .map(|fsm| fsm.fractional_scaling(layer_surface.wl_surface(), queue_handle));
-
         Self {
             blur: None,
             blur_manager: winit_state.kwin_blur_manager.clone(),
+            presentation: winit_state.presentation.clone(),
+            frame_timing: Default::default(),
             compositor,
             connection,
             theme,
             cursor_grab_mode: GrabState::new(),
             selected_cursor: Default::default(),
+            cursor_theme: None,
+            cursor_shape: winit_state.cursor_shape,
+            system_cursor_theme: None,
+            title_bar: Default::default(),
+            resize_border: Default::default(),
             cursor_visible: true,
             custom_cursor_pool: winit_state.custom_cursor_pool.clone(),
             fractional_scale,
+            forced_scale: None,
+            input_buffer: None,
+            drag: None,
+            preferred_drop_action: None,
+            scale_preference: ScalePreference::Compositor,
+            compositor_scale: 1.,
+            dmabuf_feedback: DmabufFeedbackRequest::None,
+            dmabuf_device: None,
             seat_focus: Default::default(),
             ime_allowed: false,
             ime_purpose: ImePurpose::Normal,
+            ime_cursor_area: None,
             pointer_constraints,
             pointers: Default::default(),
             queue_handle: queue_handle.clone(),
@@ -759,60 +1285,163 @@ impl WindowState {
             shell_specific: ShellSpecificState::WlrLayer {
                 surface: layer_surface,
                 last_configure: None,
+                anchor: Anchor::empty(),
+                margin: (0, 0, 0, 0),
+                exclusive_zone: 0,
+                keyboard_interactivity: KeyboardInteractivity::None,
+                focus_interactivity: None,
             },
             shm: winit_state.shm.wl_shm().clone(),
 
             size: initial_size.to_logical(1.),
             text_inputs: Vec::new(),
             initial_size: Some(initial_size),
+            initial_min_size: None,
             title: String::default(),
             transparent: false,
             viewport,
+            visible: true,
+            redraw_throttle: false,
+            frame_rate_cap: None,
+            last_redraw: None,
+            frame_rate_timer: None,
         }
     }
 
-    /// Get the outer size of the window.
-    #[inline]
-    pub fn outer_size(&self) -> LogicalSize<u32> {
-        match &self.shell_specific {
-            ShellSpecificState::Xdg { frame, .. } => frame
-                .as_ref()
-                .map(|frame| frame.add_borders(self.size.width, self.size.height).into())
-                .unwrap_or(self.size),
-            ShellSpecificState::WlrLayer { .. } => self.size,
-        }
-    }
-
-    /// Register pointer on the top-level.
-    pub fn pointer_entered(&mut self, added: Weak<ThemedPointer<WinitPointerData>>) {
-        self.pointers.push(added);
-        self.reload_cursor_style();
-
-        let mode = self.cursor_grab_mode.user_grab_mode;
-        let _ = self.set_cursor_grab_inner(mode);
-    }
-
-    /// Pointer has left the top-level.
-    pub fn pointer_left(&mut self, removed: Weak<ThemedPointer<WinitPointerData>>) {
-        let mut new_pointers = Vec::new();
-        for pointer in self.pointers.drain(..) {
-            if let Some(pointer) = pointer.upgrade() {
-                if pointer.pointer() != removed.upgrade().unwrap().pointer() {
-                    new_pointers.push(Arc::downgrade(&pointer));
-                }
-            }
-        }
-
-        self.pointers = new_pointers;
-    }
-
-    /// Refresh the decorations frame if it's present returning whether the client should redraw.
-    pub fn refresh_frame(&mut self) -> bool {
-        if let ShellSpecificState::Xdg {
-            frame: Some(ref mut frame),
-            ..
-        } = self.shell_specific
-        {
+    pub fn new_popup(
+        connection: Connection,
+        queue_handle: &QueueHandle<WinitState>,
+        winit_state: &WinitState,
+        initial_size: Size,
+        popup: Popup,
+        theme: Option<Theme>,
+    ) -> Self {
+        let compositor = winit_state.compositor_state.clone();
+        let pointer_constraints = winit_state.pointer_constraints.clone();
+        let viewport = winit_state
+            .viewporter_state
+            .as_ref()
+            .filter(|_| winit_state.fractional_scaling_manager.is_some())
+            .map(|state| state.get_viewport(popup.wl_surface(), queue_handle));
+        let fractional_scale = winit_state
+            .fractional_scaling_manager
+            .as_ref()
+            .map(|fsm| fsm.fractional_scaling(popup.wl_surface(), queue_handle));
+        Self {
+            blur: None,
+            blur_manager: winit_state.kwin_blur_manager.clone(),
+            presentation: winit_state.presentation.clone(),
+            frame_timing: Default::default(),
+            compositor,
+            connection,
+            theme,
+            cursor_grab_mode: GrabState::new(),
+            selected_cursor: Default::default(),
+            cursor_theme: None,
+            cursor_shape: winit_state.cursor_shape,
+            system_cursor_theme: None,
+            title_bar: Default::default(),
+            resize_border: Default::default(),
+            cursor_visible: true,
+            custom_cursor_pool: winit_state.custom_cursor_pool.clone(),
+            fractional_scale,
+            forced_scale: None,
+            input_buffer: None,
+            drag: None,
+            preferred_drop_action: None,
+            scale_preference: ScalePreference::Compositor,
+            compositor_scale: 1.,
+            dmabuf_feedback: DmabufFeedbackRequest::None,
+            dmabuf_device: None,
+            seat_focus: Default::default(),
+            ime_allowed: false,
+            ime_purpose: ImePurpose::Normal,
+            ime_cursor_area: None,
+            pointer_constraints,
+            pointers: Default::default(),
+            queue_handle: queue_handle.clone(),
+            scale_factor: 1.,
+            shell_specific: ShellSpecificState::Popup {
+                popup,
+                last_configure: None,
+                unmapped: false,
+            },
+            shm: winit_state.shm.wl_shm().clone(),
+
+            size: initial_size.to_logical(1.),
+            text_inputs: Vec::new(),
+            initial_size: Some(initial_size),
+            initial_min_size: None,
+            title: String::default(),
+            transparent: false,
+            viewport,
+            visible: true,
+            redraw_throttle: false,
+            frame_rate_cap: None,
+            last_redraw: None,
+            frame_rate_timer: None,
+        }
+    }
+
+    /// Get the outer size of the window.
+    #[inline]
+    pub fn outer_size(&self) -> LogicalSize<u32> {
+        match &self.shell_specific {
+            ShellSpecificState::Xdg { frame, .. } => frame
+                .as_ref()
+                .map(|frame| frame.add_borders(self.size.width, self.size.height).into())
+                .unwrap_or(self.size),
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => self.size,
+        }
+    }
+
+    /// Get the `(top, right, bottom, left)` insets of the decorations drawn by winit.
+    pub fn decoration_insets(&self) -> (u32, u32, u32, u32) {
+        let ShellSpecificState::Xdg {
+            frame: Some(frame), ..
+        } = &self.shell_specific
+        else {
+            return (0, 0, 0, 0);
+        };
+
+        let (x, y) = frame.location();
+        let (width, height) = frame.add_borders(self.size.width, self.size.height);
+        let (left, top) = (x.unsigned_abs(), y.unsigned_abs());
+        let right = width.saturating_sub(self.size.width + left);
+        let bottom = height.saturating_sub(self.size.height + top);
+        (top, right, bottom, left)
+    }
+
+    /// Register pointer on the top-level.
+    pub fn pointer_entered(&mut self, added: Weak<ThemedPointer<WinitPointerData>>) {
+        self.pointers.push(added);
+        self.reload_cursor_style();
+
+        let mode = self.cursor_grab_mode.user_grab_mode;
+        let _ = self.set_cursor_grab_inner(mode);
+    }
+
+    /// Pointer has left the top-level.
+    pub fn pointer_left(&mut self, removed: Weak<ThemedPointer<WinitPointerData>>) {
+        let mut new_pointers = Vec::new();
+        for pointer in self.pointers.drain(..) {
+            if let Some(pointer) = pointer.upgrade() {
+                if pointer.pointer() != removed.upgrade().unwrap().pointer() {
+                    new_pointers.push(Arc::downgrade(&pointer));
+                }
+            }
+        }
+
+        self.pointers = new_pointers;
+    }
+
+    /// Refresh the decorations frame if it's present returning whether the client should redraw.
+    pub fn refresh_frame(&mut self) -> bool {
+        if let ShellSpecificState::Xdg {
+            frame: Some(ref mut frame),
+            ..
+        } = self.shell_specific
+        {
             if !frame.is_hidden() && frame.is_dirty() {
                 return frame.draw();
             }
@@ -823,10 +1452,13 @@ impl WindowState {
 
     /// Reload the cursor style on the given window.
     pub fn reload_cursor_style(&mut self) {
-        if self.cursor_visible {
-            match &self.selected_cursor {
-                SelectedCursor::Named(icon) => self.set_cursor(*icon),
-                SelectedCursor::Custom(cursor) => self.apply_custom_cursor(cursor),
+        if let Some(edge) = self.resize_border.edge {
+            // Show the resize cursor over the resize border, even when the cursor is hidden.
+            self.show_cursor(edge.into());
+        } else if self.cursor_visible {
+            match self.selected_cursor {
+                SelectedCursor::Named(icon) => self.set_cursor(icon),
+                SelectedCursor::Custom(_) => self.apply_custom_cursor(),
             }
         } else {
             self.set_cursor_visible(self.cursor_visible);
@@ -854,6 +1486,12 @@ impl WindowState {
             ShellSpecificState::Xdg {
                 ref last_configure, ..
             } => {
+                // The window wasn't mapped yet, so update the size we'll use on the first
+                // configure.
+                if self.initial_size.is_some() {
+                    self.initial_size = Some(inner_size);
+                }
+
                 if last_configure
                     .as_ref()
                     .map(Self::is_stateless)
@@ -862,7 +1500,9 @@ impl WindowState {
                     self.resize(inner_size.to_logical(scale_factor))
                 }
             }
-            ShellSpecificState::WlrLayer { .. } => self.resize(inner_size.to_logical(scale_factor)),
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => {
+                self.resize(inner_size.to_logical(scale_factor))
+            }
         };
 
         logical_to_physical_rounded(self.inner_size(), scale_factor)
@@ -870,6 +1510,24 @@ impl WindowState {
 
     /// Resize the window to the new inner size.
     fn resize(&mut self, inner_size: LogicalSize<u32>) {
+        // Some compositors send zero sizes in configures, keep the previous size in such cases.
+        let inner_size = if inner_size.width == 0 || inner_size.height == 0 {
+            warn!("Ignoring degenerate window size {inner_size:?}, keeping the previous size");
+            let width = if inner_size.width == 0 {
+                self.size.width
+            } else {
+                inner_size.width
+            };
+            let height = if inner_size.height == 0 {
+                self.size.height
+            } else {
+                inner_size.height
+            };
+            LogicalSize::new(width.max(1), height.max(1))
+        } else {
+            inner_size
+        };
+
         self.size = inner_size;
 
         // Update the stateless size.
@@ -883,7 +1541,7 @@ impl WindowState {
                     *stateless_size = inner_size;
                 }
             }
-            ShellSpecificState::WlrLayer { .. } => {}
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => {}
         }
 
         // Update the inner frame.
@@ -913,20 +1571,39 @@ impl WindowState {
 
         // Set the window geometry.
         match &self.shell_specific {
-            ShellSpecificState::Xdg { window, .. } => {
-                window.xdg_surface().set_window_geometry(
+            ShellSpecificState::Xdg {
+                window,
+                window_geometry,
+                ..
+            } => {
+                let (x, y, width, height) = window_geometry.unwrap_or((
                     x,
                     y,
                     outer_size.width as i32,
                     outer_size.height as i32,
-                );
+                ));
+                window
+                    .xdg_surface()
+                    .set_window_geometry(x, y, width, height);
             }
-            ShellSpecificState::WlrLayer { surface, .. } => {
-                surface.set_size(outer_size.width, outer_size.height)
+            ShellSpecificState::WlrLayer {
+                surface, anchor, ..
+            } => {
+                let (width, height) = layer_size(*anchor, outer_size);
+                surface.set_size(width, height)
+            }
+            ShellSpecificState::Popup { popup, .. } => {
+                popup.xdg_surface().set_window_geometry(
+                    0,
+                    0,
+                    outer_size.width as i32,
+                    outer_size.height as i32,
+                );
             }
         }
 
-        // Update the target viewport, this is used if and only if fractional scaling is in use.
+        // Update the target viewport, this is used if and only if fractional scaling is in use, or
+        // the window is input only.
         if let Some(viewport) = self.viewport.as_ref() {
             // Set inner size without the borders.
             viewport.set_destination(self.size.width as _, self.size.height as _);
@@ -943,10 +1620,44 @@ impl WindowState {
     pub fn set_cursor(&mut self, cursor_icon: CursorIcon) {
         self.selected_cursor = SelectedCursor::Named(cursor_icon);
 
-        if !self.cursor_visible {
+        // The resize cursor is kept until the pointer leaves the resize border.
+        if self.cursor_visible && self.resize_border.edge.is_none() {
+            self.show_cursor(cursor_icon);
+        }
+    }
+
+    /// Show the cursor icon on the pointers.
+    fn show_cursor(&mut self, cursor_icon: CursorIcon) {
+        // Draw the cursors from the forced theme ourselves, since the compositor would use its
+        // own theme with the cursor shape protocol.
+        if let Some(cursor_theme) = self.cursor_theme.as_mut() {
+            for pointer in self.pointers.iter().filter_map(Weak::upgrade) {
+                let scale = cursor_surface_scale(&pointer);
+                match cursor_theme.get_cursor(
+                    &self.connection,
+                    &self.shm,
+                    cursor_icon,
+                    scale as u32,
+                ) {
+                    Some(image) => {
+                        let (w, h) = image.dimensions();
+                        let (hotspot_x, hotspot_y) = image.hotspot();
+                        attach_cursor_buffer(
+                            &pointer,
+                            image,
+                            (w as i32, h as i32),
+                            (hotspot_x as i32, hotspot_y as i32),
+                        );
+                    }
+                    None => warn!("Failed to set cursor to {:?}", cursor_icon),
+                }
+            }
+
             return;
         }
 
+        // The legacy themes have the closest icon in the alternative names as well.
+        let cursor_icon = closest_cursor_shape(cursor_icon);
         self.apply_on_pointer(|pointer, _| {
             if pointer.set_cursor(&self.connection, cursor_icon).is_err() {
                 warn!("Failed to set cursor to {:?}", cursor_icon);
@@ -954,6 +1665,34 @@ impl WindowState {
         })
     }
 
+    /// Whether the cursor icon could be shown without falling back to another icon.
+    pub fn cursor_available(&mut self, cursor_icon: CursorIcon) -> bool {
+        if let Some(cursor_theme) = self.cursor_theme.as_mut() {
+            return cursor_theme.has_cursor(&self.connection, &self.shm, cursor_icon);
+        }
+
+        // The compositor always has a cursor for the shapes.
+        if self.cursor_shape {
+            return true;
+        }
+
+        // The size doesn't matter to the cursor names, so use the usual default.
+        let system_cursor_theme = self
+            .system_cursor_theme
+            .get_or_insert_with(|| CursorTheme::new(None, 24));
+        system_cursor_theme.has_cursor(
+            &self.connection,
+            &self.shm,
+            closest_cursor_shape(cursor_icon),
+        )
+    }
+
+    /// Set the theme and size of the named cursors, instead of the system ones.
+    pub fn set_cursor_theme(&mut self, name: Option<String>, size: u32) {
+        self.cursor_theme = Some(CursorTheme::new(name, size));
+        self.reload_cursor_style();
+    }
+
     /// Set the custom cursor icon.
     pub(crate) fn set_custom_cursor(&mut self, cursor: RootCustomCursor) {
         let cursor = match cursor {
@@ -964,55 +1703,29 @@ impl WindowState {
             RootCustomCursor {
                 inner: PlatformCustomCursor::X(_),
             } => {
-                log::error!("passed a X11 cursor to Wayland backend");
+                error!("passed a X11 cursor to Wayland backend");
                 return;
             }
         };
 
-        let cursor = {
-            let mut pool = self.custom_cursor_pool.lock().unwrap();
-            CustomCursor::new(&mut pool, &cursor)
-        };
+        self.selected_cursor = SelectedCursor::Custom(CustomCursor::new(cursor));
 
-        if self.cursor_visible {
-            self.apply_custom_cursor(&cursor);
+        if self.cursor_visible && self.resize_border.edge.is_none() {
+            self.apply_custom_cursor();
         }
-
-        self.selected_cursor = SelectedCursor::Custom(cursor);
     }
 
-    fn apply_custom_cursor(&self, cursor: &CustomCursor) {
-        self.apply_on_pointer(|pointer, _| {
-            let surface = pointer.surface();
-
-            let scale = surface
-                .data::<SurfaceData>()
-                .unwrap()
-                .surface_data()
-                .scale_factor();
-
-            surface.set_buffer_scale(scale);
-            surface.attach(Some(cursor.buffer.wl_buffer()), 0, 0);
-            if surface.version() >= 4 {
-                surface.damage_buffer(0, 0, cursor.w, cursor.h);
-            } else {
-                surface.damage(0, 0, cursor.w / scale, cursor.h / scale);
-            }
-            surface.commit();
+    /// Attach the selected custom cursor, upscaled to the scale of each cursor surface.
+    fn apply_custom_cursor(&mut self) {
+        let SelectedCursor::Custom(cursor) = &mut self.selected_cursor else {
+            return;
+        };
 
-            let serial = pointer
-                .pointer()
-                .data::<WinitPointerData>()
-                .and_then(|data| data.pointer_data().latest_enter_serial())
-                .unwrap();
-
-            pointer.pointer().set_cursor(
-                serial,
-                Some(surface),
-                cursor.hotspot_x / scale,
-                cursor.hotspot_y / scale,
-            );
-        });
+        let mut pool = self.custom_cursor_pool.lock().unwrap();
+        for pointer in self.pointers.iter().filter_map(Weak::upgrade) {
+            let (buffer, size, hotspot) = cursor.buffer(&mut pool, cursor_surface_scale(&pointer));
+            attach_cursor_buffer(&pointer, buffer.wl_buffer(), size, hotspot);
+        }
     }
 
     pub fn is_maximized(&self) -> bool {
@@ -1021,7 +1734,57 @@ impl WindowState {
                 .as_ref()
                 .map(|last_configure| last_configure.is_maximized())
                 .unwrap_or_default(),
-            ShellSpecificState::WlrLayer { .. } => false,
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => false,
+        }
+    }
+
+    /// The bounds suggested by the compositor in the last configure.
+    pub fn suggested_bounds(&self) -> Option<LogicalSize<u32>> {
+        match &self.shell_specific {
+            ShellSpecificState::Xdg { last_configure, .. } => last_configure
+                .as_ref()
+                .and_then(|last_configure| last_configure.suggested_bounds)
+                // Zero means that the bounds are unknown.
+                .filter(|&(width, height)| width != 0 && height != 0)
+                .map(Into::into),
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => None,
+        }
+    }
+
+    /// Whether the compositor draws the decorations, as negotiated in the last configure.
+    pub fn has_server_side_decorations(&self) -> bool {
+        match &self.shell_specific {
+            ShellSpecificState::Xdg { last_configure, .. } => last_configure
+                .as_ref()
+                .map(|last_configure| last_configure.decoration_mode == DecorationMode::Server)
+                .unwrap_or_default(),
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => false,
+        }
+    }
+
+    /// The state of the `xdg_toplevel` from the latest configure.
+    pub fn toplevel_state(&self) -> XdgWindowState {
+        match &self.shell_specific {
+            ShellSpecificState::Xdg { last_configure, .. } => last_configure
+                .as_ref()
+                .map(|last_configure| last_configure.state)
+                .unwrap_or_else(XdgWindowState::empty),
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => {
+                XdgWindowState::empty()
+            }
+        }
+    }
+
+    /// The window management actions the compositor supports for the window.
+    pub fn wm_capabilities(&self) -> WindowManagerCapabilities {
+        match &self.shell_specific {
+            ShellSpecificState::Xdg { last_configure, .. } => last_configure
+                .as_ref()
+                .map(|last_configure| last_configure.capabilities)
+                .unwrap_or(WindowManagerCapabilities::all()),
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => {
+                WindowManagerCapabilities::empty()
+            }
         }
     }
 
@@ -1031,61 +1794,64 @@ impl WindowState {
                 .as_ref()
                 .map(|last_configure| last_configure.is_fullscreen())
                 .unwrap_or_default(),
-            ShellSpecificState::WlrLayer { .. } => false,
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => false,
         }
     }
 
-    /// Set maximum inner window size.
-    pub fn set_min_inner_size(&mut self, size: Option<LogicalSize<u32>>) {
+    /// Set minimum inner window size.
+    pub fn set_min_inner_size(&mut self, size: Option<Size>) {
         match &mut self.shell_specific {
-            ShellSpecificState::Xdg {
-                window,
-                frame,
-                min_inner_size,
-                ..
-            } => {
-                // Ensure that the window has the right minimum size.
-                let mut size = size.unwrap_or(MIN_WINDOW_SIZE);
-                size.width = size.width.max(MIN_WINDOW_SIZE.width);
-                size.height = size.height.max(MIN_WINDOW_SIZE.height);
-
-                // Add the borders.
-                let size = frame
-                    .as_ref()
-                    .map(|frame| frame.add_borders(size.width, size.height).into())
-                    .unwrap_or(size);
-
+            ShellSpecificState::Xdg { min_inner_size, .. } => {
                 *min_inner_size = size;
-                window.set_min_size(Some(size.into()));
+                self.reload_min_max_hints();
             }
             ShellSpecificState::WlrLayer { .. } => {
                 warn!("Minimum size is ignored for layer_shell windows")
             }
+            ShellSpecificState::Popup { .. } => {
+                warn!("Minimum size is ignored for popups")
+            }
         }
     }
 
-    /// Set maximum inner window size.
-    pub fn set_max_inner_size(&mut self, size: Option<LogicalSize<u32>>) {
+    /// The resize increments of the window.
+    pub fn resize_increments(&self) -> Option<Size> {
+        match &self.shell_specific {
+            ShellSpecificState::Xdg {
+                resize_increments, ..
+            } => *resize_increments,
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => None,
+        }
+    }
+
+    /// Set the resize increments, applied to the size picked on the next configure.
+    pub fn set_resize_increments(&mut self, increments: Option<Size>) {
         match &mut self.shell_specific {
             ShellSpecificState::Xdg {
-                window,
-                frame,
-                max_inner_size,
-                ..
-            } => {
-                let size = size.map(|size| {
-                    frame
-                        .as_ref()
-                        .map(|frame| frame.add_borders(size.width, size.height).into())
-                        .unwrap_or(size)
-                });
+                resize_increments, ..
+            } => *resize_increments = increments,
+            ShellSpecificState::WlrLayer { .. } => {
+                warn!("Resize increments are ignored for layer_shell windows")
+            }
+            ShellSpecificState::Popup { .. } => {
+                warn!("Resize increments are ignored for popups")
+            }
+        }
+    }
 
+    /// Set maximum inner window size.
+    pub fn set_max_inner_size(&mut self, size: Option<Size>) {
+        match &mut self.shell_specific {
+            ShellSpecificState::Xdg { max_inner_size, .. } => {
                 *max_inner_size = size;
-                window.set_max_size(size.map(Into::into));
+                self.reload_min_max_hints();
             }
             ShellSpecificState::WlrLayer { .. } => {
                 warn!("Maximum size is ignored for layer_shell windows")
             }
+            ShellSpecificState::Popup { .. } => {
+                warn!("Maximum size is ignored for popups")
+            }
         }
     }
 
@@ -1104,6 +1870,11 @@ impl WindowState {
                     warn!("Theme is ignored for layer_shell windows")
                 }
             }
+            ShellSpecificState::Popup { .. } => {
+                if theme.is_some() {
+                    warn!("Theme is ignored for popups")
+                }
+            }
         }
     }
 
@@ -1112,10 +1883,34 @@ impl WindowState {
     pub fn theme(&self) -> Option<Theme> {
         match &self.shell_specific {
             ShellSpecificState::Xdg { .. } => self.theme,
-            ShellSpecificState::WlrLayer { .. } => None,
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => None,
         }
     }
 
+    /// Set the region to confine the cursor to, `None` for the whole surface.
+    ///
+    /// The active confinement is updated on the next commit of the surface.
+    pub fn set_cursor_confine_region(&mut self, region: Option<(i32, i32, u32, u32)>) {
+        self.cursor_grab_mode.confine_region = region;
+        if self.cursor_grab_mode.current_grab_mode != CursorGrabMode::Confined {
+            return;
+        }
+
+        let region = self.cursor_confine_region();
+        let region = region.as_ref().map(Region::wl_region);
+        self.apply_on_pointer(|_, data| data.set_confine_region(region));
+    }
+
+    /// Create the region the cursor is confined to.
+    fn cursor_confine_region(&self) -> Option<Region> {
+        let (x, y, width, height) = self.cursor_grab_mode.confine_region?;
+        let region = Region::new(&*self.compositor).ok()?;
+        let width = width.min(i32::MAX as u32) as i32;
+        let height = height.min(i32::MAX as u32) as i32;
+        region.add(x, y, width, height);
+        Some(region)
+    }
+
     /// Set the cursor grabbing state on the top-level.
     pub fn set_cursor_grab(&mut self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         // Replace the user grabbing mode.
@@ -1124,18 +1919,47 @@ impl WindowState {
     }
 
     /// Reload the hints for minimum and maximum sizes.
+    ///
+    /// The hints are in the surface local coordinates, so they must be reloaded when the scale
+    /// factor changes.
     pub fn reload_min_max_hints(&mut self) {
-        match self.shell_specific {
-            ShellSpecificState::Xdg {
-                min_inner_size,
-                max_inner_size,
-                ..
-            } => {
-                self.set_min_inner_size(Some(min_inner_size));
-                self.set_max_inner_size(max_inner_size);
-            }
-            ShellSpecificState::WlrLayer { .. } => {}
-        }
+        let ShellSpecificState::Xdg {
+            ref window,
+            ref frame,
+            resizable,
+            min_inner_size,
+            max_inner_size,
+            ..
+        } = self.shell_specific
+        else {
+            return;
+        };
+
+        // Non-resizable implies that the min and max sizes are set to the current size.
+        let (min_size, max_size) = if resizable {
+            (
+                min_inner_size.map(|size| size.to_logical(self.scale_factor)),
+                max_inner_size.map(|size| size.to_logical(self.scale_factor)),
+            )
+        } else {
+            (Some(self.size), Some(self.size))
+        };
+
+        // Ensure that the window has the right minimum size.
+        let mut min_size = min_size.unwrap_or(MIN_WINDOW_SIZE);
+        min_size.width = min_size.width.max(MIN_WINDOW_SIZE.width);
+        min_size.height = min_size.height.max(MIN_WINDOW_SIZE.height);
+
+        // Add the borders.
+        let add_borders = |size: LogicalSize<u32>| {
+            frame
+                .as_ref()
+                .map(|frame| frame.add_borders(size.width, size.height).into())
+                .unwrap_or(size)
+        };
+
+        window.set_min_size(Some(add_borders(min_size).into()));
+        window.set_max_size(max_size.map(add_borders).map(Into::into));
     }
 
     /// Set the grabbing state on the surface.
@@ -1165,10 +1989,20 @@ impl WindowState {
                 let pointer = pointer.pointer();
                 data.lock_pointer(pointer_constraints, surface, pointer, &self.queue_handle)
             }),
-            CursorGrabMode::Confined => self.apply_on_pointer(|pointer, data| {
-                let pointer = pointer.pointer();
-                data.confine_pointer(pointer_constraints, surface, pointer, &self.queue_handle)
-            }),
+            CursorGrabMode::Confined => {
+                let region = self.cursor_confine_region();
+                let region = region.as_ref().map(Region::wl_region);
+                self.apply_on_pointer(|pointer, data| {
+                    let pointer = pointer.pointer();
+                    data.confine_pointer(
+                        pointer_constraints,
+                        surface,
+                        pointer,
+                        region,
+                        &self.queue_handle,
+                    );
+                })
+            }
             CursorGrabMode::None => {
                 // Current lock/confine was already removed.
             }
@@ -1177,7 +2011,7 @@ impl WindowState {
         Ok(())
     }
 
-    pub fn show_window_menu(&self, position: LogicalPosition<u32>) {
+    pub fn show_window_menu(&self, position: LogicalPosition<i32>) {
         match &self.shell_specific {
             ShellSpecificState::Xdg { window, .. } => {
                 // TODO(kchibisov) handle touch serials.
@@ -1187,7 +2021,7 @@ impl WindowState {
                     window.show_window_menu(seat, serial, position.into());
                 });
             }
-            ShellSpecificState::WlrLayer { .. } => {}
+            ShellSpecificState::WlrLayer { .. } | ShellSpecificState::Popup { .. } => {}
         }
     }
 
@@ -1197,20 +2031,24 @@ impl WindowState {
             return Err(ExternalError::NotSupported(NotSupportedError::new()));
         }
 
-        // Position can be set only for locked cursor.
+        // Position can be set only for locked cursor, since it's just a hint for the compositor
+        // where to put the cursor once the lock is released.
         if self.cursor_grab_mode.current_grab_mode != CursorGrabMode::Locked {
-            return Err(ExternalError::Os(os_error!(
-                crate::platform_impl::OsError::Misc(
-                    "cursor position can be set only for locked cursor."
-                )
-            )));
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
         }
 
-        self.apply_on_pointer(|_, data| {
-            data.set_locked_cursor_position(position.x, position.y);
-        });
+        let mut hint_set = false;
+        for pointer in self.pointers.iter().filter_map(Weak::upgrade) {
+            let data = pointer.pointer().winit_data();
+            hint_set |= data.set_locked_cursor_position(position.x, position.y);
+        }
 
-        Ok(())
+        if hint_set {
+            Ok(())
+        } else {
+            // The lock is not active, since the pointer is not over the window.
+            Err(ExternalError::NotSupported(NotSupportedError::new()))
+        }
     }
 
     /// Set the visibility state of the cursor.
@@ -1218,9 +2056,9 @@ impl WindowState {
         self.cursor_visible = cursor_visible;
 
         if self.cursor_visible {
-            match &self.selected_cursor {
-                SelectedCursor::Named(icon) => self.set_cursor(*icon),
-                SelectedCursor::Custom(cursor) => self.apply_custom_cursor(cursor),
+            match self.selected_cursor {
+                SelectedCursor::Named(icon) => self.set_cursor(icon),
+                SelectedCursor::Custom(_) => self.apply_custom_cursor(),
             }
         } else {
             for pointer in self.pointers.iter().filter_map(|pointer| pointer.upgrade()) {
@@ -1275,6 +2113,11 @@ impl WindowState {
                     warn!("Client-side decorations are ignored for layer_shell windows");
                 }
             }
+            ShellSpecificState::Popup { .. } => {
+                if decorate {
+                    warn!("Client-side decorations are ignored for popups");
+                }
+            }
         }
     }
 
@@ -1282,12 +2125,16 @@ impl WindowState {
     #[inline]
     pub fn add_seat_focus(&mut self, seat: ObjectId) {
         self.seat_focus.insert(seat);
+        self.restore_keyboard_interactivity(KeyboardInteractivity::Exclusive);
     }
 
     /// Remove seat focus from the window.
     #[inline]
     pub fn remove_seat_focus(&mut self, seat: &ObjectId) {
         self.seat_focus.remove(seat);
+        if !self.has_focus() {
+            self.restore_keyboard_interactivity(KeyboardInteractivity::None);
+        }
     }
 
     /// Returns `true` if the requested state was applied.
@@ -1298,8 +2145,7 @@ impl WindowState {
         for text_input in &self.text_inputs {
             applied = true;
             if allowed {
-                text_input.enable();
-                text_input.set_content_type_by_purpose(self.ime_purpose);
+                self.enable_text_input(text_input);
             } else {
                 text_input.disable();
             }
@@ -1309,8 +2155,32 @@ impl WindowState {
         applied
     }
 
+    /// Enable the text input with the IME state of the window, without committing it.
+    ///
+    /// The `enable` request resets the state of the text input, so the purpose and the cursor
+    /// area are sent again.
+    pub fn enable_text_input(&self, text_input: &ZwpTextInputV3) {
+        text_input.enable();
+        text_input.set_content_type_by_purpose(self.ime_purpose);
+        if let Some((position, size)) = self.ime_cursor_area {
+            text_input.set_cursor_rectangle(
+                position.x as i32,
+                position.y as i32,
+                size.width as i32,
+                size.height as i32,
+            );
+        }
+    }
+
     /// Set the IME position.
-    pub fn set_ime_cursor_area(&self, position: LogicalPosition<u32>, size: LogicalSize<u32>) {
+    ///
+    /// The area is kept while the IME isn't allowed and sent once it's enabled.
+    pub fn set_ime_cursor_area(&mut self, position: LogicalPosition<u32>, size: LogicalSize<u32>) {
+        self.ime_cursor_area = Some((position, size));
+        if !self.ime_allowed {
+            return;
+        }
+
         // FIXME: This won't fly unless user will have a way to request IME window per seat, since
         // the ime windows will be overlapping, but winit doesn't expose API to specify for
         // which seat we're setting IME position.
@@ -1342,8 +2212,9 @@ impl WindowState {
     pub fn set_scale_factor(&mut self, scale_factor: f64) {
         self.scale_factor = scale_factor;
 
-        // NOTE: When fractional scaling is not used update the buffer scale.
-        if self.fractional_scale.is_none() {
+        // NOTE: When fractional scaling is not used update the buffer scale. The buffer of the
+        // input only window is scaled with the viewport instead.
+        if self.fractional_scale.is_none() && self.input_buffer.is_none() {
             let _ = self.wl_surface().set_buffer_scale(self.scale_factor as _);
         }
 
@@ -1354,6 +2225,183 @@ impl WindowState {
         {
             frame.set_scaling_factor(scale_factor);
         }
+
+        // The min and max sizes could be requested in physical pixels.
+        self.reload_min_max_hints();
+    }
+
+    /// Force the integer scale of the surface, ignoring the scale preferred by the compositor.
+    pub fn force_integer_scale(&mut self, scale: i32) {
+        if scale < 1 {
+            warn!("Ignoring invalid forced scale {scale}");
+            return;
+        }
+
+        // The buffer scale is only used without fractional scaling.
+        if let Some(fractional_scale) = self.fractional_scale.take() {
+            fractional_scale.destroy();
+        }
+
+        self.forced_scale = Some(scale);
+        self.set_scale_factor(scale as f64);
+    }
+
+    /// Make the window input only, attaching a transparent buffer scaled to the window with the
+    /// viewport on every configure, so it's mapped without being drawn.
+    ///
+    /// Returns `false` when the compositor doesn't support the single pixel buffers or the
+    /// viewporter.
+    pub fn set_input_only(
+        &mut self,
+        winit_state: &WinitState,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> bool {
+        let (Some(single_pixel_buffer), Some(viewporter_state)) = (
+            winit_state.single_pixel_buffer.as_ref(),
+            winit_state.viewporter_state.as_ref(),
+        ) else {
+            return false;
+        };
+
+        if self.viewport.is_none() {
+            self.viewport = Some(viewporter_state.get_viewport(self.wl_surface(), queue_handle));
+        }
+
+        // The 1x1 buffer can't have a buffer scale, the viewport sets the size of the surface.
+        self.wl_surface().set_buffer_scale(1);
+        self.input_buffer = Some(single_pixel_buffer.create_transparent_buffer(queue_handle));
+        true
+    }
+
+    /// Attach the buffer of the input only window and commit the configure.
+    pub fn commit_input_buffer(&self) {
+        let Some(buffer) = self.input_buffer.as_ref() else {
+            return;
+        };
+
+        let surface = self.wl_surface();
+        surface.attach(Some(buffer), 0, 0);
+        surface.damage(0, 0, i32::MAX, i32::MAX);
+        trace_protocol!("-> {}.commit()", surface.id());
+        surface.commit();
+    }
+
+    /// Whether the user forced the scale of the surface.
+    #[inline]
+    pub fn has_forced_scale(&self) -> bool {
+        self.forced_scale.is_some()
+    }
+
+    /// Record the scale preferred by the compositor, returning whether it was applied.
+    ///
+    /// The compositor's scale is only used with [`ScalePreference::Compositor`], otherwise the
+    /// scale is picked from the monitors in [`Self::apply_scale_preference`].
+    pub fn compositor_scale_changed(&mut self, scale_factor: f64) -> bool {
+        self.compositor_scale = scale_factor;
+        if self.scale_preference != ScalePreference::Compositor {
+            return false;
+        }
+
+        self.set_scale_factor(scale_factor);
+        true
+    }
+
+    /// Set how the scale is picked when the window is on multiple monitors.
+    ///
+    /// The new scale is applied with [`Self::apply_scale_preference`].
+    #[inline]
+    pub fn set_scale_preference(&mut self, scale_preference: ScalePreference) {
+        self.scale_preference = scale_preference;
+    }
+
+    /// Update the scale following the user's preference, e.g. after the window moved to another
+    /// monitor, returning whether it changed.
+    pub fn apply_scale_preference(&mut self) -> bool {
+        if self.has_forced_scale() {
+            return false;
+        }
+
+        let output_scale = |output: &WlOutput| {
+            output
+                .data::<OutputData>()
+                .map(|data| data.with_output_info(|info| info.scale_factor))
+        };
+        let outputs = || {
+            self.wl_surface()
+                .data::<SurfaceData>()
+                .into_iter()
+                .flat_map(|data| data.outputs())
+        };
+        let scale = match &self.scale_preference {
+            ScalePreference::Compositor => Some(self.compositor_scale),
+            ScalePreference::Highest => outputs()
+                .filter_map(|o| output_scale(&o))
+                .max()
+                .map(f64::from),
+            ScalePreference::Lowest => outputs()
+                .filter_map(|o| output_scale(&o))
+                .min()
+                .map(f64::from),
+            ScalePreference::Monitor(monitor) => match &monitor.inner {
+                PlatformMonitorHandle::Wayland(monitor) => {
+                    output_scale(&monitor.proxy).map(f64::from)
+                }
+                #[cfg(x11_platform)]
+                PlatformMonitorHandle::X(_) => None,
+            },
+        };
+
+        match scale {
+            Some(scale) if scale != self.scale_factor => {
+                self.set_scale_factor(scale);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The scale of the buffers attached to the surface.
+    ///
+    /// When fractional scaling is used, the buffers are always attached with the scale of `1` and
+    /// scaled with the viewport instead.
+    #[inline]
+    pub fn buffer_scale(&self) -> u32 {
+        if self.fractional_scale.is_some() {
+            1
+        } else {
+            self.scale_factor as u32
+        }
+    }
+
+    /// Whether the dmabuf feedback belongs to this window.
+    #[inline]
+    pub fn has_dmabuf_feedback(&self, feedback: &ZwpLinuxDmabufFeedbackV1) -> bool {
+        matches!(&self.dmabuf_feedback, DmabufFeedbackRequest::Created(own) if own == feedback)
+    }
+
+    /// Ask for the dmabuf feedback on the next event loop iteration, returning whether it wasn't
+    /// asked for yet.
+    #[inline]
+    pub fn request_dmabuf_feedback(&mut self) -> bool {
+        let requested = matches!(self.dmabuf_feedback, DmabufFeedbackRequest::None);
+        if requested {
+            self.dmabuf_feedback = DmabufFeedbackRequest::Requested;
+        }
+        requested
+    }
+
+    /// Create the dmabuf feedback asked for with [`Self::request_dmabuf_feedback`].
+    pub fn create_dmabuf_feedback(&mut self, dmabuf_state: &DmabufState) {
+        if !matches!(self.dmabuf_feedback, DmabufFeedbackRequest::Requested) {
+            return;
+        }
+
+        self.dmabuf_feedback =
+            match dmabuf_state.get_surface_feedback(self.wl_surface(), &self.queue_handle) {
+                Ok(feedback) => DmabufFeedbackRequest::Created(feedback),
+                // The compositor doesn't support the feedback, don't ask again.
+                Err(_) => DmabufFeedbackRequest::Unsupported,
+            };
     }
 
     /// Make window background blurred
@@ -1376,16 +2424,8 @@ impl WindowState {
     /// Set the window title to a new value.
     ///
     /// This will automatically truncate the title to something meaningful.
-    pub fn set_title(&mut self, mut title: String) {
-        // Truncate the title to at most 1024 bytes, so that it does not blow up the protocol
-        // messages
-        if title.len() > 1024 {
-            let mut new_len = 1024;
-            while !title.is_char_boundary(new_len) {
-                new_len -= 1;
-            }
-            title.truncate(new_len);
-        }
+    pub fn set_title(&mut self, title: String) {
+        let title = sanitize_title(title);
 
         match &mut self.shell_specific {
             ShellSpecificState::Xdg { window, frame, .. } => {
@@ -1395,11 +2435,62 @@ impl WindowState {
                 }
                 window.set_title(&title);
             }
-            ShellSpecificState::WlrLayer { .. } => {}
+            ShellSpecificState::WlrLayer { .. } => {
+                warn!("Title is ignored for layer shell windows")
+            }
+            ShellSpecificState::Popup { .. } => warn!("Title is ignored for popups"),
         }
         self.title = title;
     }
 
+    /// Set the window title and the application ID in one go.
+    pub fn set_title_and_app_id(&mut self, title: String, app_id: String) {
+        if let ShellSpecificState::Xdg { window, .. } = &self.shell_specific {
+            // The application ID has the same restrictions as the title.
+            window.set_app_id(sanitize_title(app_id));
+        }
+        self.set_title(title);
+    }
+
+    /// Map or unmap the window.
+    ///
+    /// Mapping is done by committing the surface without a buffer, so the compositor will send a
+    /// new initial configure. Unmapping is done by attaching a null buffer.
+    ///
+    /// An unmapped `xdg_popup` can't be mapped again, so the popups stay hidden.
+    pub fn set_visible(&mut self, visible: bool) {
+        if self.visible == visible {
+            return;
+        }
+
+        if let ShellSpecificState::Popup { unmapped, .. } = &mut self.shell_specific {
+            if visible && *unmapped {
+                warn!("Hidden popups can't be shown again, create a new popup instead");
+                return;
+            }
+
+            *unmapped |= !visible;
+        }
+
+        self.visible = visible;
+
+        // The pending frame callback won't be delivered for the unmapped surface.
+        self.frame_callback_reset();
+
+        match &mut self.shell_specific {
+            ShellSpecificState::Xdg { last_configure, .. } => *last_configure = None,
+            ShellSpecificState::WlrLayer { last_configure, .. } => *last_configure = None,
+            ShellSpecificState::Popup { last_configure, .. } => *last_configure = None,
+        }
+
+        let surface = self.wl_surface();
+        if !visible {
+            surface.attach(None, 0, 0);
+        }
+        trace_protocol!("-> {}.commit()", surface.id());
+        surface.commit();
+    }
+
     /// Mark the window as transparent.
     #[inline]
     pub fn set_transparent(&mut self, transparent: bool) {
@@ -1429,55 +2520,217 @@ impl WindowState {
         &self.title
     }
 
+    /// Set the window geometry, overriding the one computed by winit, or go back to the computed
+    /// one with `None`.
+    pub fn set_window_geometry(&mut self, geometry: Option<(i32, i32, i32, i32)>) {
+        match &mut self.shell_specific {
+            ShellSpecificState::Xdg {
+                window_geometry, ..
+            } => {
+                // The protocol forbids empty geometry.
+                *window_geometry =
+                    geometry.map(|(x, y, width, height)| (x, y, width.max(1), height.max(1)));
+
+                // Send the geometry again, computing it when the override is cleared.
+                self.resize(self.size);
+            }
+            ShellSpecificState::WlrLayer { .. } => {
+                warn!("Window geometry is ignored for layer_shell windows")
+            }
+            ShellSpecificState::Popup { .. } => {
+                warn!("Window geometry is ignored for popups")
+            }
+        }
+    }
+
     /// Change the render layer.
     #[inline]
     pub fn set_layer(&self, layer: Layer) {
         match &self.shell_specific {
             ShellSpecificState::WlrLayer { surface, .. } => surface.set_layer(layer),
-            ShellSpecificState::Xdg { .. } => warn!("Layer is ignored for XDG windows"),
+            ShellSpecificState::Xdg { .. } => {
+                warn!("Layer is ignored for XDG windows")
+            }
+            ShellSpecificState::Popup { .. } => {
+                warn!("Layer is ignored for popups")
+            }
         }
     }
     /// Change the anchor direction(s).
+    ///
+    /// The size, margin and exclusive zone are sent again and committed together with the anchor,
+    /// so the compositor moves the reserved area to the new edges in a single configure.
     #[inline]
-    pub fn set_anchor(&self, anchor: Anchor) {
-        match &self.shell_specific {
-            ShellSpecificState::WlrLayer { surface, .. } => surface.set_anchor(anchor),
-            ShellSpecificState::Xdg { .. } => warn!("Anchor is ignored for XDG windows"),
+    pub fn set_anchor(&mut self, anchor: Anchor) {
+        match &mut self.shell_specific {
+            ShellSpecificState::WlrLayer {
+                surface,
+                last_configure,
+                anchor: current_anchor,
+                margin,
+                exclusive_zone,
+                ..
+            } => {
+                *current_anchor = anchor;
+                let configured = last_configure.is_some();
+                for request in
+                    reanchor_requests(anchor, self.size, *margin, *exclusive_zone, configured)
+                {
+                    match request {
+                        LayerRequest::Anchor(anchor) => surface.set_anchor(anchor),
+                        LayerRequest::Size(width, height) => surface.set_size(width, height),
+                        LayerRequest::Margin(top, right, bottom, left) => {
+                            surface.set_margin(top, right, bottom, left)
+                        }
+                        LayerRequest::ExclusiveZone(zone) => surface.set_exclusive_zone(zone),
+                        LayerRequest::Commit => {
+                            trace_protocol!("-> {}.commit()", surface.wl_surface().id());
+                            surface.commit()
+                        }
+                    }
+                }
+            }
+            ShellSpecificState::Xdg { .. } => {
+                warn!("Anchor is ignored for XDG windows")
+            }
+            ShellSpecificState::Popup { .. } => {
+                warn!("Anchor is ignored for popups")
+            }
         }
     }
 
     /// Change the margin for each direction.
     #[inline]
-    pub fn set_margin(&self, top: i32, right: i32, bottom: i32, left: i32) {
-        match &self.shell_specific {
-            ShellSpecificState::WlrLayer { surface, .. } => {
+    pub fn set_margin(&mut self, top: i32, right: i32, bottom: i32, left: i32) {
+        match &mut self.shell_specific {
+            ShellSpecificState::WlrLayer {
+                surface, margin, ..
+            } => {
+                *margin = (top, right, bottom, left);
                 surface.set_margin(top, right, bottom, left)
             }
-            ShellSpecificState::Xdg { .. } => warn!("Margin is ignored for XDG windows"),
+            ShellSpecificState::Xdg { .. } => {
+                warn!("Margin is ignored for XDG windows")
+            }
+            ShellSpecificState::Popup { .. } => {
+                warn!("Margin is ignored for popups")
+            }
         }
     }
 
     /// Change the size of the exclusive zone.
     #[inline]
-    pub fn set_exclusive_zone(&self, exclusive_zone: i32) {
-        match &self.shell_specific {
-            ShellSpecificState::WlrLayer { surface, .. } => {
-                surface.set_exclusive_zone(exclusive_zone);
+    pub fn set_exclusive_zone(&mut self, new_exclusive_zone: i32) {
+        match &mut self.shell_specific {
+            ShellSpecificState::WlrLayer {
+                surface,
+                exclusive_zone,
+                ..
+            } => {
+                // Don't make the compositor lay the windows out again for nothing.
+                if *exclusive_zone == new_exclusive_zone {
+                    return;
+                }
+
+                *exclusive_zone = new_exclusive_zone;
+                surface.set_exclusive_zone(new_exclusive_zone);
+            }
+            ShellSpecificState::Xdg { .. } => {
+                warn!("Exclusive zone is ignored for XDG windows")
+            }
+            ShellSpecificState::Popup { .. } => {
+                warn!("Exclusive zone is ignored for popups")
             }
-            ShellSpecificState::Xdg { .. } => warn!("Exclusive zone is ignored for XDG windows"),
         }
     }
 
     /// Change the keyboard interactivity.
     #[inline]
-    pub fn set_keyboard_interactivity(&self, keyboard_interactivity: KeyboardInteractivity) {
-        match &self.shell_specific {
-            ShellSpecificState::WlrLayer { surface, .. } => {
-                surface.set_keyboard_interactivity(keyboard_interactivity)
+    pub fn set_keyboard_interactivity(
+        &mut self,
+        new_keyboard_interactivity: KeyboardInteractivity,
+    ) {
+        match &mut self.shell_specific {
+            ShellSpecificState::WlrLayer {
+                surface,
+                keyboard_interactivity,
+                focus_interactivity,
+                ..
+            } => {
+                *keyboard_interactivity = new_keyboard_interactivity;
+                *focus_interactivity = None;
+                surface.set_keyboard_interactivity(new_keyboard_interactivity)
             }
             ShellSpecificState::Xdg { .. } => {
                 warn!("Keyboard interactivity is ignored for XDG windows")
             }
+            ShellSpecificState::Popup { .. } => {
+                warn!("Keyboard interactivity is ignored for popups")
+            }
+        }
+    }
+
+    /// Move the keyboard focus to or from the `OnDemand` layer shell window.
+    ///
+    /// The focus is moved by committing a temporary interactivity, `Exclusive` to take the focus
+    /// and `None` to give it up, and the user's interactivity is restored once the focus moved.
+    pub fn set_keyboard_focus(&mut self, focus: bool) {
+        let has_focus = self.has_focus();
+        let ShellSpecificState::WlrLayer {
+            surface,
+            last_configure,
+            keyboard_interactivity,
+            focus_interactivity,
+            ..
+        } = &mut self.shell_specific
+        else {
+            warn!("Keyboard focus requests are only supported for layer_shell windows");
+            return;
+        };
+
+        if *keyboard_interactivity != KeyboardInteractivity::OnDemand {
+            warn!("Keyboard focus requests are only supported with the OnDemand interactivity");
+            return;
+        }
+
+        if has_focus == focus {
+            // Cancel the pending request in the other direction, if any.
+            if focus_interactivity.take().is_some() {
+                surface.set_keyboard_interactivity(KeyboardInteractivity::OnDemand);
+            }
+            return;
+        }
+
+        let interactivity = if focus {
+            KeyboardInteractivity::Exclusive
+        } else {
+            KeyboardInteractivity::None
+        };
+        *focus_interactivity = Some(interactivity);
+        surface.set_keyboard_interactivity(interactivity);
+
+        // Before the initial configure, the state is applied by the initial commit.
+        if last_configure.is_some() {
+            trace_protocol!("-> {}.commit()", surface.wl_surface().id());
+            surface.commit();
+        }
+    }
+
+    /// Restore the user's keyboard interactivity once the focus moved as requested.
+    ///
+    /// It's applied with the next commit of the surface, which doesn't move the focus again.
+    fn restore_keyboard_interactivity(&mut self, moved_by: KeyboardInteractivity) {
+        if let ShellSpecificState::WlrLayer {
+            surface,
+            keyboard_interactivity,
+            focus_interactivity,
+            ..
+        } = &mut self.shell_specific
+        {
+            if *focus_interactivity == Some(moved_by) {
+                *focus_interactivity = None;
+                surface.set_keyboard_interactivity(*keyboard_interactivity);
+            }
         }
     }
 }
@@ -1492,10 +2745,18 @@ impl Drop for WindowState {
             fs.destroy();
         }
 
+        if let DmabufFeedbackRequest::Created(feedback) = &self.dmabuf_feedback {
+            feedback.destroy();
+        }
+
         if let Some(viewport) = self.viewport.take() {
             viewport.destroy();
         }
 
+        if let Some(buffer) = self.input_buffer.take() {
+            buffer.destroy();
+        }
+
         // NOTE: the wl_surface used by the window is being cleaned up when
         // dropping SCTK `Window`.
     }
@@ -1509,6 +2770,9 @@ struct GrabState {
 
     /// The current grab mode.
     current_grab_mode: CursorGrabMode,
+
+    /// The region to confine the cursor to, the whole surface when `None`.
+    confine_region: Option<(i32, i32, u32, u32)>,
 }
 
 impl GrabState {
@@ -1516,10 +2780,112 @@ impl GrabState {
         Self {
             user_grab_mode: CursorGrabMode::None,
             current_grab_mode: CursorGrabMode::None,
+            confine_region: None,
         }
     }
 }
 
+/// The state of the title bar region of the surface.
+#[derive(Debug, Default, Clone, Copy)]
+struct TitleBarState {
+    /// The region as `(x, y, width, height)` in surface local coordinates.
+    region: Option<(i32, i32, u32, u32)>,
+
+    /// The time of the last press in the region, to detect the double clicks.
+    last_press: Option<Duration>,
+
+    /// Whether the button pressed in the region is still held down.
+    pressed: bool,
+}
+
+impl TitleBarState {
+    fn contains(&self, x: f64, y: f64) -> bool {
+        let Some((rx, ry, width, height)) = self.region else {
+            return false;
+        };
+        let (rx, ry) = (rx as f64, ry as f64);
+        x >= rx && y >= ry && x < rx + width as f64 && y < ry + height as f64
+    }
+}
+
+/// The state of the resize border on the edges of the surface.
+#[derive(Debug, Default, Clone, Copy)]
+struct ResizeBorderState {
+    /// Whether the border is handled.
+    enabled: bool,
+
+    /// The edge under the pointer.
+    edge: Option<ResizeDirection>,
+
+    /// Whether the button pressed on the edge is still held down.
+    pressed: bool,
+}
+
+/// The size to request for the layer surface, which is zero along the axes anchored to both
+/// edges, so the compositor keeps the surface stretched when the output changes.
+fn layer_size(anchor: Anchor, size: LogicalSize<u32>) -> (u32, u32) {
+    let width = if anchor.contains(Anchor::LEFT | Anchor::RIGHT) {
+        0
+    } else {
+        size.width
+    };
+    let height = if anchor.contains(Anchor::TOP | Anchor::BOTTOM) {
+        0
+    } else {
+        size.height
+    };
+    (width, height)
+}
+
+/// A request changing the state of the layer surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LayerRequest {
+    Anchor(Anchor),
+    Size(u32, u32),
+    Margin(i32, i32, i32, i32),
+    ExclusiveZone(i32),
+    Commit,
+}
+
+/// The requests moving the layer surface to the new anchor.
+///
+/// The margin and exclusive zone are sent again along with the anchor and committed together,
+/// so the compositor moves the reserved area to the new edges in a single configure. Before the
+/// initial configure, the state is applied by the initial commit instead.
+fn reanchor_requests(
+    anchor: Anchor,
+    size: LogicalSize<u32>,
+    margin: (i32, i32, i32, i32),
+    exclusive_zone: i32,
+    configured: bool,
+) -> Vec<LayerRequest> {
+    let (width, height) = layer_size(anchor, size);
+    let (top, right, bottom, left) = margin;
+    let mut requests = vec![
+        LayerRequest::Anchor(anchor),
+        LayerRequest::Size(width, height),
+        LayerRequest::Margin(top, right, bottom, left),
+        LayerRequest::ExclusiveZone(exclusive_zone),
+    ];
+    if configured {
+        requests.push(LayerRequest::Commit);
+    }
+    requests
+}
+
+/// The state of the dmabuf feedback of the surface.
+#[derive(Debug)]
+enum DmabufFeedbackRequest {
+    /// The feedback wasn't asked for.
+    None,
+    /// The feedback was asked for, and is created on the next event loop iteration.
+    Requested,
+    /// The feedback is tracked.
+    Created(ZwpLinuxDmabufFeedbackV1),
+    /// The compositor doesn't support the surface feedback.
+    Unsupported,
+}
+
 /// The state of the frame callback.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FrameCallbackState {
@@ -1556,3 +2922,143 @@ fn into_sctk_adwaita_config(theme: Option<Theme>) -> sctk_adwaita::FrameConfig {
         None => sctk_adwaita::FrameConfig::auto(),
     }
 }
+
+/// Accept all the actions of the drag and drop offer, preferring the action, or none of them
+/// when it's empty.
+fn set_drop_actions(offer: &DragOffer, preferred_action: DndAction) {
+    let actions = if preferred_action.is_empty() {
+        DndAction::empty()
+    } else {
+        DndAction::Copy | DndAction::Move | DndAction::Ask
+    };
+    offer.set_actions(actions, preferred_action);
+}
+
+/// The scale of the output the cursor surface is on.
+fn cursor_surface_scale(pointer: &ThemedPointer<WinitPointerData>) -> i32 {
+    pointer
+        .surface()
+        .data::<SurfaceData>()
+        .unwrap()
+        .surface_data()
+        .scale_factor()
+}
+
+/// Show the buffer as the cursor of the pointer, the size and hotspot are in buffer pixels.
+fn attach_cursor_buffer(
+    pointer: &ThemedPointer<WinitPointerData>,
+    buffer: &WlBuffer,
+    (w, h): (i32, i32),
+    (hotspot_x, hotspot_y): (i32, i32),
+) {
+    let surface = pointer.surface();
+    let scale = cursor_surface_scale(pointer);
+
+    surface.set_buffer_scale(scale);
+    surface.attach(Some(buffer), 0, 0);
+    if surface.version() >= 4 {
+        surface.damage_buffer(0, 0, w, h);
+    } else {
+        surface.damage(0, 0, w / scale, h / scale);
+    }
+    surface.commit();
+
+    let serial = pointer
+        .pointer()
+        .data::<WinitPointerData>()
+        .and_then(|data| data.pointer_data().latest_enter_serial())
+        .unwrap();
+
+    pointer
+        .pointer()
+        .set_cursor(serial, Some(surface), hotspot_x / scale, hotspot_y / scale);
+}
+
+/// Shrink the size to a multiple of the increments, keeping at least one increment.
+fn snap_to_increments(size: LogicalSize<u32>, increments: LogicalSize<f64>) -> LogicalSize<u32> {
+    let snap = |length: u32, increment: f64| {
+        if increment < 1. {
+            return length;
+        }
+
+        let count = (length as f64 / increment).floor().max(1.);
+        (count * increment).round() as u32
+    };
+
+    LogicalSize::new(
+        snap(size.width, increments.width),
+        snap(size.height, increments.height),
+    )
+}
+
+/// Make the title safe to send over the protocol.
+fn sanitize_title(mut title: String) -> String {
+    // The protocol strings are NUL terminated, so the interior NULs can't be sent.
+    title.retain(|c| c != '\0');
+
+    // Truncate the title to at most 1024 bytes, so that it does not blow up the protocol
+    // messages
+    if title.len() > 1024 {
+        let mut new_len = 1024;
+        while !title.is_char_boundary(new_len) {
+            new_len -= 1;
+        }
+        title.truncate(new_len);
+    }
+
+    title
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitizes_titles() {
+        assert_eq!(sanitize_title("🦀 winit".into()), "🦀 winit");
+        assert_eq!(sanitize_title("foo\0bar".into()), "foobar");
+
+        // The multi-byte characters aren't split by the truncation.
+        let title = sanitize_title("🦀".repeat(300));
+        assert_eq!(title.len(), 1024);
+        assert!(title.chars().all(|c| c == '🦀'));
+
+        let title = sanitize_title(format!("a{}", "🦀".repeat(300)));
+        assert_eq!(title.len(), 1021);
+    }
+
+    #[test]
+    fn snaps_to_resize_increments() {
+        let snap = |size: (u32, u32), increments: (f64, f64)| {
+            let size = snap_to_increments(size.into(), increments.into());
+            (size.width, size.height)
+        };
+
+        assert_eq!(snap((805, 613), (10., 20.)), (800, 600));
+        assert_eq!(snap((800, 600), (10., 20.)), (800, 600));
+        // At least one increment is kept.
+        assert_eq!(snap((5, 5), (10., 20.)), (10, 20));
+        // Fractional increments, e.g. from a fractional scale factor.
+        assert_eq!(snap((100, 100), (7.5, 0.)), (98, 100));
+    }
+
+    #[test]
+    fn reanchors_with_the_reservation() {
+        let bottom = Anchor::BOTTOM | Anchor::LEFT | Anchor::RIGHT;
+        let size = (1920, 32).into();
+        assert_eq!(
+            reanchor_requests(bottom, size, (0, 4, 8, 4), 32, true),
+            [
+                LayerRequest::Anchor(bottom),
+                LayerRequest::Size(0, 32),
+                LayerRequest::Margin(0, 4, 8, 4),
+                LayerRequest::ExclusiveZone(32),
+                LayerRequest::Commit,
+            ]
+        );
+
+        // The initial commit applies the state before the initial configure.
+        let requests = reanchor_requests(Anchor::TOP, size, (0, 0, 0, 0), 32, false);
+        assert_eq!(requests.last(), Some(&LayerRequest::ExclusiveZone(32)));
+    }
+}
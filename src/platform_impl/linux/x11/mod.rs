@@ -139,6 +139,9 @@ pub struct EventLoopWindowTarget {
     redraw_sender: WakeSender<WindowId>,
     activation_sender: WakeSender<ActivationToken>,
     device_events: Cell<DeviceEvents>,
+    /// The callbacks to call on the next iteration of the event loop.
+    pending_callbacks: RefCell<Vec<Box<dyn FnOnce()>>>,
+    waker: Ping,
 }
 
 pub struct EventLoop<T: 'static> {
@@ -304,6 +307,8 @@ impl<T: 'static> EventLoop<T> {
                 waker: waker.clone(),
             },
             device_events: Default::default(),
+            pending_callbacks: Default::default(),
+            waker: waker.clone(),
         };
 
         // Set initial device event filter.
@@ -441,9 +446,12 @@ impl<T: 'static> EventLoop<T> {
     }
 
     fn has_pending(&mut self) -> bool {
+        let window_target = EventProcessor::window_target(&self.event_processor.target);
+        let has_pending_callbacks = !window_target.pending_callbacks.borrow().is_empty();
         self.event_processor.poll()
             || self.user_receiver.has_incoming()
             || self.redraw_receiver.has_incoming()
+            || has_pending_callbacks
     }
 
     pub fn poll_events_with_timeout<F>(&mut self, mut timeout: Option<Duration>, mut callback: F)
@@ -572,6 +580,15 @@ impl<T: 'static> EventLoop<T> {
             }
         }
 
+        // Call the callbacks deferred to this iteration.
+        {
+            let window_target = EventProcessor::window_target(&self.event_processor.target);
+            let pending_callbacks = window_target.pending_callbacks.take();
+            for pending_callback in pending_callbacks {
+                pending_callback();
+            }
+        }
+
         // Empty the redraw requests
         {
             let mut windows = HashSet::new();
@@ -662,6 +679,13 @@ impl EventLoopWindowTarget {
         &self.xconn
     }
 
+    /// Call `callback` on the next iteration of the event loop, for the asynchronous requests
+    /// completing right away.
+    pub(crate) fn call_later(&self, callback: Box<dyn FnOnce()>) {
+        self.pending_callbacks.borrow_mut().push(callback);
+        self.waker.ping();
+    }
+
     pub fn available_monitors(&self) -> impl Iterator<Item = MonitorHandle> {
         self.xconn.available_monitors().into_iter().flatten()
     }
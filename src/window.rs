@@ -489,7 +489,10 @@ impl WindowBuilder {
     ///
     /// ## Platform-specific:
     ///
-    /// **Android / iOS / X11 / Wayland / Orbital:** Unsupported.
+    /// - **Wayland:** `false` skips the activation with the startup notification token and keeps
+    ///   the layer shell windows without keyboard interactivity, unless it's set explicitly.
+    ///   Compositors could still focus new toplevels on their own.
+    /// - **Android / iOS / X11 / Orbital:** Unsupported.
     ///
     /// [`WindowEvent::Focused`]: crate::event::WindowEvent::Focused.
     #[inline]
@@ -597,6 +600,9 @@ impl Window {
     ///
     /// - **X11:** This respects Xft.dpi, and can be overridden using the `WINIT_X11_SCALE_FACTOR` environment variable.
     /// - **Wayland:** Uses the wp-fractional-scale protocol if available. Falls back to integer-scale factors otherwise.
+    ///   The value is updated as soon as the compositor reports the new scale, so it may be ahead of the
+    ///   [`WindowEvent::ScaleFactorChanged`] delivered later in the same event loop iteration, but it's
+    ///   never behind it.
     /// - **Android:** Always returns 1.0.
     /// - **iOS:** Can only be called on the main thread. Returns the underlying `UIView`'s
     ///   [`contentScaleFactor`].
@@ -669,8 +675,15 @@ impl Window {
     /// ## Platform-specific
     ///
     /// **Wayland:** - schedules a frame callback to throttle [`WindowEvent::RedrawRequested`].
+    ///   The configure from the compositor is usually applied together with the next buffer you
+    ///   submit, so avoid submitting buffers that were drawn before the last
+    ///   [`WindowEvent::Resized`]. Winit only commits the surface on your behalf in
+    ///   [`Window::set_visible`] to map or unmap it, in the anchor and keyboard focus changes of
+    ///   configured layer shell windows, and after each configure of input only windows, which
+    ///   have no content of their own. Those commits also apply the pending configure.
     ///
     /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
     #[inline]
     pub fn pre_present_notify(&self) {
         self.window.maybe_queue_on_main(|w| w.pre_present_notify());
@@ -778,6 +791,9 @@ impl Window {
     /// - **iOS:** Can only be called on the main thread. Returns the `PhysicalSize` of the window's
     ///   [safe area] in screen space coordinates.
     /// - **Web:** Returns the size of the canvas element. Doesn't account for CSS [`transform`].
+    /// - **Wayland:** Building a visible window blocks until its initial configure, so the size
+    ///   assigned by the compositor is returned right away. Invisible windows aren't configured
+    ///   until they're shown, and report the requested size until then.
     ///
     /// [safe area]: https://developer.apple.com/documentation/uikit/uiview/2891103-safeareainsets?language=objc
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
@@ -899,7 +915,7 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Wayland / Windows / Orbital:** Always returns [`None`].
+    /// - **iOS / Android / Web / Windows / Orbital:** Always returns [`None`].
     #[inline]
     pub fn resize_increments(&self) -> Option<PhysicalSize<u32>> {
         self.window.maybe_wait_on_main(|w| w.resize_increments())
@@ -913,7 +929,10 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **macOS:** Increments are converted to logical size and then macOS rounds them to whole numbers.
-    /// - **Wayland / Windows:** Not implemented.
+    /// - **Wayland:** The compositor doesn't know about the increments, so the window picks the
+    ///   largest multiple of them fitting in the size the compositor suggests. This only happens
+    ///   on the next configure, and not when the window is maximized, fullscreen or tiled.
+    /// - **Windows:** Not implemented.
     /// - **iOS / Android / Web / Orbital:** Unsupported.
     #[inline]
     pub fn set_resize_increments<S: Into<Size>>(&self, increments: Option<S>) {
@@ -973,8 +992,13 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **Android / Wayland / Web:** Unsupported.
+    /// - **Android / Web:** Unsupported.
     /// - **iOS:** Can only be called on the main thread.
+    /// - **Wayland:** Hiding the window unmaps its surface. [`WindowEvent::RedrawRequested`] is
+    ///   not delivered until the compositor configures the window again after showing it, and
+    ///   you must not present to the window before that.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
     #[inline]
     pub fn set_visible(&self, visible: bool) {
         self.window
@@ -988,7 +1012,7 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **X11:** Not implemented.
-    /// - **Wayland / iOS / Android / Web:** Unsupported.
+    /// - **iOS / Android / Web:** Unsupported.
     #[inline]
     pub fn is_visible(&self) -> Option<bool> {
         self.window.maybe_wait_on_main(|w| w.is_visible())
@@ -1113,7 +1137,9 @@ impl Window {
     ///
     ///   The dock and the menu bar are disabled in exclusive fullscreen mode.
     /// - **iOS:** Can only be called on the main thread.
-    /// - **Wayland:** Does not support exclusive fullscreen mode and will no-op a request.
+    /// - **Wayland:** Does not support exclusive fullscreen mode and will no-op a request. The
+    ///   monitor of the borderless mode is sent to the compositor, which makes the final decision,
+    ///   and `None` lets it pick the monitor.
     /// - **Windows:** Screen saver is disabled in fullscreen mode.
     /// - **Android / Orbital:** Unsupported.
     /// - **Web:** Does nothing without a [transient activation].
@@ -1411,7 +1437,10 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Wayland / Orbital:** Always returns an [`ExternalError::NotSupported`].
+    /// - **iOS / Android / Web / Orbital:** Always returns an [`ExternalError::NotSupported`].
+    /// - **Wayland:** Only works while the cursor is locked with [`CursorGrabMode::Locked`] and
+    ///   sets the position where the cursor will appear once the lock is released. Returns an
+    ///   [`ExternalError::NotSupported`] otherwise.
     #[inline]
     pub fn set_cursor_position<P: Into<Position>>(&self, position: P) -> Result<(), ExternalError> {
         let position = position.into();
@@ -1447,7 +1476,10 @@ impl Window {
     ///
     /// - **Windows:** The cursor is only hidden within the confines of the window.
     /// - **X11:** The cursor is only hidden within the confines of the window.
-    /// - **Wayland:** The cursor is only hidden within the confines of the window.
+    /// - **Wayland:** The cursor is only hidden within the confines of the window. The visibility
+    ///   is kept when the cursor leaves and enters the window again, moves or changes the output,
+    ///   until it's changed by the user, though the cursor is still shown over the client side
+    ///   decorations and the resize border of `DecorationsMode::ResizeOnly`.
     /// - **macOS:** The cursor is hidden as long as the window has input focus, even if the cursor is
     ///   outside of the window.
     /// - **iOS / Android:** Unsupported.
@@ -1465,7 +1497,8 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **X11:** Un-grabs the cursor.
-    /// - **Wayland:** Requires the cursor to be inside the window to be dragged.
+    /// - **Wayland:** Requires the cursor to be inside the window to be dragged. Returns an
+    ///   [`ExternalError::NotSupported`] when no mouse button is held down.
     /// - **macOS:** May prevent the button release event to be triggered.
     /// - **iOS / Android / Web:** Always returns an [`ExternalError::NotSupported`].
     #[inline]
@@ -1482,6 +1515,8 @@ impl Window {
     ///
     /// - **macOS:** Always returns an [`ExternalError::NotSupported`]
     /// - **iOS / Android / Web:** Always returns an [`ExternalError::NotSupported`].
+    /// - **Wayland:** Returns an [`ExternalError::NotSupported`] when no mouse button is held
+    ///   down.
     #[inline]
     pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), ExternalError> {
         self.window
@@ -1494,7 +1529,7 @@ impl Window {
     /// the title bar. This is useful when implementing custom decorations.
     ///
     /// ## Platform-specific
-    /// **Android / iOS / macOS / Orbital / Wayland / Web / X11:** Unsupported.
+    /// **Android / iOS / macOS / Orbital / Web / X11:** Unsupported.
     ///
     /// [window menu]: https://en.wikipedia.org/wiki/Common_menus_in_Microsoft_Windows#System_menu
     pub fn show_window_menu(&self, position: impl Into<Position>) {
@@ -1767,6 +1802,8 @@ pub enum WindowLevel {
 ///
 /// ## Platform-specific
 ///
+/// - **Wayland:** Sent as the content purpose and hints of `zwp_text_input_v3` when the IME is
+///   enabled and whenever the purpose changes.
 /// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[non_exhaustive]
@@ -1779,6 +1816,17 @@ pub enum ImePurpose {
     ///
     /// For example, that could alter OSK on Wayland to show extra buttons.
     Terminal,
+    /// The IME is used to input a number, e.g. the OSK could show digits with a decimal
+    /// separator and a sign.
+    Number,
+    /// The IME is used to input a PIN, which is only digits and hidden like a password.
+    Pin,
+    /// The IME is used to input a phone number.
+    Phone,
+    /// The IME is used to input a URL.
+    Url,
+    /// The IME is used to input an email address.
+    Email,
 }
 
 impl Default for ImePurpose {